@@ -57,6 +57,8 @@ builtin_macros_bad_derive_target = `derive` may only be applied to `struct`s, `e
     .label = not applicable here
     .label2 = not a `struct`, `enum` or `union`
 
+builtin_macros_bench_case = `#[case]` can only be used on tests, not benchmarks
+
 builtin_macros_bench_sig = functions used as benches must have signature `fn(&mut Bencher) -> impl Termination`
 
 
@@ -230,8 +232,26 @@ builtin_macros_test_args = functions used as tests can not have any arguments
 builtin_macros_test_bad_fn = {$kind} functions cannot be used for tests
     .label = `{$kind}` because of this
 
+builtin_macros_test_case_args = this case passes {$found} {$found ->
+    [one] argument
+    *[other] arguments
+    } to a test that takes {$expected}
+    .label = test defined here
+
+builtin_macros_test_case_malformed = malformed `case` attribute input
+    .help = the arguments of a case go in parentheses: `#[case(arg1, arg2, ...)]`
+
 builtin_macros_test_case_non_item = `#[test_case]` attribute is only allowed on items
 
+builtin_macros_test_case_options = malformed options of a `case`
+    .help = a case can be ignored with `#[case(arg1, arg2, ...; ignore)]` or `#[case(arg1, arg2, ...; ignore = "reason")]`
+
+builtin_macros_test_timeout_malformed = malformed `timeout` attribute input
+    .help = the timeout of a test is a string such as `#[timeout = "30s"]`
+
+builtin_macros_test_timeout_multiple = multiple `timeout` attributes
+    .note = the first timeout is here
+
 builtin_macros_test_runner_invalid = `test_runner` argument must be a path
 builtin_macros_test_runner_nargs = `#![test_runner(..)]` accepts exactly 1 argument
 
@@ -674,6 +674,40 @@ pub(crate) struct TestCaseNonItem {
     pub(crate) span: Span,
 }
 
+#[derive(Diagnostic)]
+#[diag(builtin_macros_test_case_malformed)]
+#[help]
+pub(crate) struct TestCaseMalformed {
+    #[primary_span]
+    pub(crate) span: Span,
+}
+
+#[derive(Diagnostic)]
+#[diag(builtin_macros_test_case_options)]
+#[help]
+pub(crate) struct TestCaseOptions {
+    #[primary_span]
+    pub(crate) span: Span,
+}
+
+#[derive(Diagnostic)]
+#[diag(builtin_macros_test_case_args)]
+pub(crate) struct TestCaseArgs {
+    #[primary_span]
+    pub(crate) span: Span,
+    #[label]
+    pub(crate) test: Span,
+    pub(crate) found: usize,
+    pub(crate) expected: usize,
+}
+
+#[derive(Diagnostic)]
+#[diag(builtin_macros_bench_case)]
+pub(crate) struct BenchCase {
+    #[primary_span]
+    pub(crate) span: Span,
+}
+
 #[derive(Diagnostic)]
 #[diag(builtin_macros_test_bad_fn)]
 pub(crate) struct TestBadFn {
@@ -684,6 +718,23 @@ pub(crate) struct TestBadFn {
     pub(crate) kind: &'static str,
 }
 
+#[derive(Diagnostic)]
+#[diag(builtin_macros_test_timeout_malformed)]
+#[help]
+pub(crate) struct TestTimeoutMalformed {
+    #[primary_span]
+    pub(crate) span: Span,
+}
+
+#[derive(Diagnostic)]
+#[diag(builtin_macros_test_timeout_multiple)]
+pub(crate) struct TestTimeoutMultiple {
+    #[primary_span]
+    pub(crate) span: Span,
+    #[note]
+    pub(crate) first: Span,
+}
+
 #[derive(Diagnostic)]
 #[diag(builtin_macros_asm_explicit_register_name)]
 pub(crate) struct AsmExplicitRegisterName {
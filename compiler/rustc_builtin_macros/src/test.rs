@@ -3,11 +3,14 @@
 /// Ideally, this code would be in libtest but for efficiency and error messages it lives here.
 use crate::util::{check_builtin_macro_attribute, warn_on_duplicate_attribute};
 use rustc_ast::ptr::P;
+use rustc_ast::token::{self, Delimiter};
+use rustc_ast::tokenstream::{TokenStream, TokenTree};
 use rustc_ast::{self as ast, attr, GenericParamKind};
 use rustc_ast_pretty::pprust;
 use rustc_errors::Applicability;
 use rustc_expand::base::*;
-use rustc_span::symbol::{sym, Ident, Symbol};
+use rustc_session::parse::feature_err;
+use rustc_span::symbol::{kw, sym, Ident, Symbol};
 use rustc_span::{ErrorGuaranteed, FileNameDisplayPreference, Span};
 use std::iter;
 use thin_vec::{thin_vec, ThinVec};
@@ -105,7 +108,7 @@ pub fn expand_test_or_bench(
         return vec![];
     }
 
-    let (item, is_stmt) = match item {
+    let (mut item, is_stmt) = match item {
         Annotatable::Item(i) => (i, false),
         Annotatable::Stmt(stmt) if matches!(stmt.kind, ast::StmtKind::Item(_)) => {
             // FIXME: Use an 'if let' guard once they are implemented
@@ -121,6 +124,12 @@ pub fn expand_test_or_bench(
         }
     };
 
+    // `#[timeout = "..."]` and `#[case(...)]` are only attributes of tests, rather than builtin
+    // attributes, so that they don't take the names from attribute macros such as `#[timeout(ms)]`
+    // of test crates.
+    let timeout = take_timeout(cx, &mut item.attrs);
+    let cases = take_cases(cx, &mut item.attrs);
+
     let ast::ItemKind::Fn(fn_) = &item.kind else {
         not_testable_error(cx, attr_sp, Some(&item));
         return if is_stmt {
@@ -134,17 +143,23 @@ pub fn expand_test_or_bench(
     // will fail. We shouldn't try to expand in this case because the errors
     // would be spurious.
     let check_result = if is_bench {
-        check_bench_signature(cx, &item, &fn_)
+        check_bench_signature(cx, &item, &fn_, &cases)
     } else {
-        check_test_signature(cx, &item, &fn_)
+        check_test_signature(cx, &item, &fn_, !cases.is_empty())
+    };
+    // The arguments of each `#[case(...)]`, if the test has any.
+    let case_args = match check_result {
+        Ok(()) if cases.is_empty() => Some(None),
+        Ok(()) => test_case_args(cx, &item, &fn_, &cases).map(Some),
+        Err(_) => None,
     };
-    if check_result.is_err() {
+    let Some(case_args) = case_args else {
         return if is_stmt {
             vec![Annotatable::Stmt(P(cx.stmt_item(item.span, item)))]
         } else {
             vec![Annotatable::Item(item)]
         };
-    }
+    };
 
     let sp = cx.with_def_site_ctxt(item.span);
     let ret_ty_sp = cx.with_def_site_ctxt(fn_.sig.decl.output.span());
@@ -182,57 +197,62 @@ pub fn expand_test_or_bench(
     // creates $name: $expr
     let field = |name, expr| cx.field_imm(sp, Ident::from_str_and_span(name, sp), expr);
 
-    let test_fn = if is_bench {
-        // A simple ident for a lambda
-        let b = Ident::from_str_and_span("b", attr_sp);
-
+    // creates test::StaticTestFn(|| test::assert_test_result($test_fn($args)))
+    let static_test_fn = |args| {
         cx.expr_call(
             sp,
-            cx.expr_path(test_path("StaticBenchFn")),
+            cx.expr_path(test_path("StaticTestFn")),
             thin_vec![
-                // |b| self::test::assert_test_result(
-                cx.lambda1(
+                // || {
+                cx.lambda0(
                     sp,
+                    // test::assert_test_result(
                     cx.expr_call(
                         sp,
                         cx.expr_path(test_path("assert_test_result")),
                         thin_vec![
-                            // super::$test_fn(b)
+                            // $test_fn($args)
                             cx.expr_call(
                                 ret_ty_sp,
                                 cx.expr_path(cx.path(sp, vec![item.ident])),
-                                thin_vec![cx.expr_ident(sp, b)],
-                            ),
+                                args,
+                            ), // )
                         ],
-                    ),
-                    b,
+                    ), // }
                 ), // )
             ],
         )
-    } else {
+    };
+
+    let test_fn = if is_bench {
+        // A simple ident for a lambda
+        let b = Ident::from_str_and_span("b", attr_sp);
+
         cx.expr_call(
             sp,
-            cx.expr_path(test_path("StaticTestFn")),
+            cx.expr_path(test_path("StaticBenchFn")),
             thin_vec![
-                // || {
-                cx.lambda0(
+                // |b| self::test::assert_test_result(
+                cx.lambda1(
                     sp,
-                    // test::assert_test_result(
                     cx.expr_call(
                         sp,
                         cx.expr_path(test_path("assert_test_result")),
                         thin_vec![
-                            // $test_fn()
+                            // super::$test_fn(b)
                             cx.expr_call(
                                 ret_ty_sp,
                                 cx.expr_path(cx.path(sp, vec![item.ident])),
-                                ThinVec::new(),
-                            ), // )
+                                thin_vec![cx.expr_ident(sp, b)],
+                            ),
                         ],
-                    ), // }
+                    ),
+                    b,
                 ), // )
             ],
         )
+    } else {
+        static_test_fn(ThinVec::new())
     };
 
     let test_path_symbol = Symbol::intern(&item_path(
@@ -241,150 +261,221 @@ pub fn expand_test_or_bench(
         &item.ident,
     ));
 
+    // Every case of a parameterized test gets its own test, named `path::to::test::case_N`. The
+    // numbers are zero-padded to the same width, so that the cases are sorted in order.
+    let ignore = should_ignore(&item);
+    let ignore_message = should_ignore_message(&item);
+    let tests = match case_args {
+        None => vec![(
+            Ident::new(item.ident.name, sp),
+            test_path_symbol,
+            test_fn,
+            ignore,
+            ignore_message,
+        )],
+        Some(cases) => {
+            let width = cases.len().to_string().len();
+            cases
+                .into_iter()
+                .enumerate()
+                .map(|(i, case)| {
+                    let n = i + 1;
+                    (
+                        Ident::from_str_and_span(&format!("{}_case_{n:0width$}", item.ident), sp),
+                        Symbol::intern(&format!("{test_path_symbol}::case_{n:0width$}")),
+                        static_test_fn(case.args),
+                        ignore || case.ignore.is_some(),
+                        case.ignore.flatten().or(ignore_message),
+                    )
+                })
+                .collect()
+        }
+    };
+
     let location_info = get_location_info(cx, &item);
+    let should_panic = should_panic(cx, &item);
 
-    let mut test_const =
-        cx.item(
+    // const _: u64 = test::timeout_attr_ms("...");
+    // The timeout is parsed by libtest, and checked by evaluating this constant.
+    let timeout_check = timeout.map(|(span, value)| {
+        cx.item_const(
             sp,
-            Ident::new(item.ident.name, sp),
-            thin_vec![
-                // #[cfg(test)]
-                cx.attr_nested_word(sym::cfg, sym::test, attr_sp),
-                // #[rustc_test_marker = "test_case_sort_key"]
-                cx.attr_name_value_str(sym::rustc_test_marker, test_path_symbol, attr_sp),
-            ],
-            // const $ident: test::TestDescAndFn =
-            ast::ItemKind::Const(
-                ast::ConstItem {
-                    defaultness: ast::Defaultness::Final,
-                    generics: ast::Generics::default(),
-                    ty: cx.ty(sp, ast::TyKind::Path(None, test_path("TestDescAndFn"))),
-                    // test::TestDescAndFn {
-                    expr: Some(
-                        cx.expr_struct(
-                            sp,
-                            test_path("TestDescAndFn"),
-                            thin_vec![
-                        // desc: test::TestDesc {
-                        field(
-                            "desc",
+            Ident::new(kw::Underscore, sp),
+            cx.ty_ident(sp, Ident::new(sym::u64, sp)),
+            cx.expr_call(
+                span,
+                cx.expr_path(test_path("timeout_attr_ms")),
+                thin_vec![cx.expr_str(span, value)],
+            ),
+        )
+    });
+
+    let test_consts: Vec<_> = tests
+        .into_iter()
+        .map(|(ident, name, testfn, ignore, ignore_message)| {
+            let test_const = cx.item(
+                sp,
+                ident,
+                thin_vec![
+                    // #[cfg(test)]
+                    cx.attr_nested_word(sym::cfg, sym::test, attr_sp),
+                    // #[rustc_test_marker = "test_case_sort_key"]
+                    cx.attr_name_value_str(sym::rustc_test_marker, name, attr_sp),
+                ],
+                // const $ident: test::TestDescAndFn =
+                ast::ItemKind::Const(
+                    ast::ConstItem {
+                        defaultness: ast::Defaultness::Final,
+                        generics: ast::Generics::default(),
+                        ty: cx.ty(sp, ast::TyKind::Path(None, test_path("TestDescAndFn"))),
+                        // test::TestDescAndFn {
+                        expr: Some(
                             cx.expr_struct(
                                 sp,
-                                test_path("TestDesc"),
+                                test_path("TestDescAndFn"),
                                 thin_vec![
-                                    // name: "path::to::test"
+                                    // desc: test::TestDesc {
                                     field(
-                                        "name",
-                                        cx.expr_call(
+                                        "desc",
+                                        cx.expr_struct(
                                             sp,
-                                            cx.expr_path(test_path("StaticTestName")),
-                                            thin_vec![cx.expr_str(sp, test_path_symbol)],
+                                            test_path("TestDesc"),
+                                            thin_vec![
+                                                // name: "path::to::test"
+                                                field(
+                                                    "name",
+                                                    cx.expr_call(
+                                                        sp,
+                                                        cx.expr_path(test_path("StaticTestName")),
+                                                        thin_vec![cx.expr_str(sp, name)],
+                                                    ),
+                                                ),
+                                                // ignore: true | false
+                                                field("ignore", cx.expr_bool(sp, ignore)),
+                                                // ignore_message: Some("...") | None
+                                                field(
+                                                    "ignore_message",
+                                                    if let Some(msg) = ignore_message {
+                                                        cx.expr_some(sp, cx.expr_str(sp, msg))
+                                                    } else {
+                                                        cx.expr_none(sp)
+                                                    },
+                                                ),
+                                                // source_file: <relative_path_of_source_file>
+                                                field(
+                                                    "source_file",
+                                                    cx.expr_str(sp, location_info.0)
+                                                ),
+                                                // start_line: start line of the test fn identifier.
+                                                field(
+                                                    "start_line",
+                                                    cx.expr_usize(sp, location_info.1)
+                                                ),
+                                                // start_col: start column of the test fn identifier.
+                                                field(
+                                                    "start_col",
+                                                    cx.expr_usize(sp, location_info.2)
+                                                ),
+                                                // end_line: end line of the test fn identifier.
+                                                field(
+                                                    "end_line",
+                                                    cx.expr_usize(sp, location_info.3)
+                                                ),
+                                                // end_col: end column of the test fn identifier.
+                                                field(
+                                                    "end_col",
+                                                    cx.expr_usize(sp, location_info.4)
+                                                ),
+                                                // compile_fail: true | false
+                                                field("compile_fail", cx.expr_bool(sp, false)),
+                                                // no_run: true | false
+                                                field("no_run", cx.expr_bool(sp, false)),
+                                                // should_panic: ...
+                                                field(
+                                                    "should_panic",
+                                                    match should_panic {
+                                                        // test::ShouldPanic::No
+                                                        ShouldPanic::No => {
+                                                            cx.expr_path(should_panic_path("No"))
+                                                        }
+                                                        // test::ShouldPanic::Yes
+                                                        ShouldPanic::Yes(None) => {
+                                                            cx.expr_path(should_panic_path("Yes"))
+                                                        }
+                                                        // test::ShouldPanic::YesWithMessage("...")
+                                                        ShouldPanic::Yes(Some(sym)) => cx
+                                                            .expr_call(
+                                                                sp,
+                                                                cx.expr_path(should_panic_path(
+                                                                    "YesWithMessage"
+                                                                )),
+                                                                thin_vec![cx.expr_str(sp, sym)],
+                                                            ),
+                                                    },
+                                                ),
+                                                // timeout: Some("...") | None
+                                                field(
+                                                    "timeout",
+                                                    if let Some((_, value)) = timeout {
+                                                        cx.expr_some(sp, cx.expr_str(sp, value))
+                                                    } else {
+                                                        cx.expr_none(sp)
+                                                    },
+                                                ),
+                                                // test_type: ...
+                                                field(
+                                                    "test_type",
+                                                    match test_type(cx) {
+                                                        // test::TestType::UnitTest
+                                                        TestType::UnitTest => {
+                                                            cx.expr_path(test_type_path("UnitTest"))
+                                                        }
+                                                        // test::TestType::IntegrationTest
+                                                        TestType::IntegrationTest => {
+                                                            cx.expr_path(test_type_path(
+                                                                "IntegrationTest",
+                                                            ))
+                                                        }
+                                                        // test::TestPath::Unknown
+                                                        TestType::Unknown => {
+                                                            cx.expr_path(test_type_path("Unknown"))
+                                                        }
+                                                    },
+                                                ),
+                                                // },
+                                            ],
                                         ),
                                     ),
-                                    // ignore: true | false
-                                    field("ignore", cx.expr_bool(sp, should_ignore(&item)),),
-                                    // ignore_message: Some("...") | None
-                                    field(
-                                        "ignore_message",
-                                        if let Some(msg) = should_ignore_message(&item) {
-                                            cx.expr_some(sp, cx.expr_str(sp, msg))
-                                        } else {
-                                            cx.expr_none(sp)
-                                        },
-                                    ),
-                                    // source_file: <relative_path_of_source_file>
-                                    field("source_file", cx.expr_str(sp, location_info.0)),
-                                    // start_line: start line of the test fn identifier.
-                                    field("start_line", cx.expr_usize(sp, location_info.1)),
-                                    // start_col: start column of the test fn identifier.
-                                    field("start_col", cx.expr_usize(sp, location_info.2)),
-                                    // end_line: end line of the test fn identifier.
-                                    field("end_line", cx.expr_usize(sp, location_info.3)),
-                                    // end_col: end column of the test fn identifier.
-                                    field("end_col", cx.expr_usize(sp, location_info.4)),
-                                    // compile_fail: true | false
-                                    field("compile_fail", cx.expr_bool(sp, false)),
-                                    // no_run: true | false
-                                    field("no_run", cx.expr_bool(sp, false)),
-                                    // should_panic: ...
-                                    field(
-                                        "should_panic",
-                                        match should_panic(cx, &item) {
-                                            // test::ShouldPanic::No
-                                            ShouldPanic::No => {
-                                                cx.expr_path(should_panic_path("No"))
-                                            }
-                                            // test::ShouldPanic::Yes
-                                            ShouldPanic::Yes(None) => {
-                                                cx.expr_path(should_panic_path("Yes"))
-                                            }
-                                            // test::ShouldPanic::YesWithMessage("...")
-                                            ShouldPanic::Yes(Some(sym)) => cx.expr_call(
-                                                sp,
-                                                cx.expr_path(should_panic_path("YesWithMessage")),
-                                                thin_vec![cx.expr_str(sp, sym)],
-                                            ),
-                                        },
-                                    ),
-                                    // test_type: ...
-                                    field(
-                                        "test_type",
-                                        match test_type(cx) {
-                                            // test::TestType::UnitTest
-                                            TestType::UnitTest => {
-                                                cx.expr_path(test_type_path("UnitTest"))
-                                            }
-                                            // test::TestType::IntegrationTest
-                                            TestType::IntegrationTest => {
-                                                cx.expr_path(test_type_path("IntegrationTest"))
-                                            }
-                                            // test::TestPath::Unknown
-                                            TestType::Unknown => {
-                                                cx.expr_path(test_type_path("Unknown"))
-                                            }
-                                        },
-                                    ),
-                                    // },
+                                    // testfn: test::StaticTestFn(...) | test::StaticBenchFn(...)
+                                    field("testfn", testfn), // }
                                 ],
-                            ),
+                            ), // }
                         ),
-                        // testfn: test::StaticTestFn(...) | test::StaticBenchFn(...)
-                        field("testfn", test_fn), // }
-                    ],
-                        ), // }
-                    ),
-                }
-                .into(),
-            ),
-        );
-    test_const = test_const.map(|mut tc| {
-        tc.vis.kind = ast::VisibilityKind::Public;
-        tc
-    });
+                    }
+                    .into(),
+                ),
+            );
+            test_const.map(|mut tc| {
+                tc.vis.kind = ast::VisibilityKind::Public;
+                tc
+            })
+        })
+        .collect();
 
     // extern crate test
     let test_extern = cx.item(sp, test_id, ast::AttrVec::new(), ast::ItemKind::ExternCrate(None));
 
-    debug!("synthetic test item:\n{}\n", pprust::item_to_string(&test_const));
+    for test_const in &test_consts {
+        debug!("synthetic test item:\n{}\n", pprust::item_to_string(test_const));
+    }
 
+    // Access to libtest under a hygienic name, the generated test cases and the original item
+    let items =
+        iter::once(test_extern).chain(timeout_check).chain(test_consts).chain(iter::once(item));
     if is_stmt {
-        vec![
-            // Access to libtest under a hygienic name
-            Annotatable::Stmt(P(cx.stmt_item(sp, test_extern))),
-            // The generated test case
-            Annotatable::Stmt(P(cx.stmt_item(sp, test_const))),
-            // The original item
-            Annotatable::Stmt(P(cx.stmt_item(sp, item))),
-        ]
+        items.map(|item| Annotatable::Stmt(P(cx.stmt_item(sp, item)))).collect()
     } else {
-        vec![
-            // Access to libtest under a hygienic name
-            Annotatable::Item(test_extern),
-            // The generated test case
-            Annotatable::Item(test_const),
-            // The original item
-            Annotatable::Item(item),
-        ]
+        items.map(Annotatable::Item).collect()
     }
 }
 
@@ -440,6 +531,7 @@ fn item_path(mod_path: &[Ident], item_ident: &Ident) -> String {
         .join("::")
 }
 
+#[derive(Clone, Copy)]
 enum ShouldPanic {
     No,
     Yes(Option<Symbol>),
@@ -501,6 +593,61 @@ fn should_panic(cx: &ExtCtxt<'_>, i: &ast::Item) -> ShouldPanic {
     }
 }
 
+/// Removes the `#[timeout = "..."]` attribute from a test, returning its span and value. Other
+/// forms of `#[timeout]` are left to name resolution.
+fn take_timeout(cx: &ExtCtxt<'_>, attrs: &mut ast::AttrVec) -> Option<(Span, Symbol)> {
+    let mut timeout = None;
+    attrs.retain(|attr| {
+        if !attr.has_name(sym::timeout)
+            || !matches!(attr.get_normal_item().args, ast::AttrArgs::Eq(..))
+        {
+            return true;
+        }
+        if !cx.ecfg.features.test_timeout {
+            feature_err(
+                &cx.sess.parse_sess,
+                sym::test_timeout,
+                attr.span,
+                "the `#[timeout]` attribute is an experimental feature",
+            )
+            .emit();
+        }
+        match (attr.value_str(), timeout) {
+            (Some(_), Some((first, _))) => {
+                cx.emit_err(errors::TestTimeoutMultiple { span: attr.span, first });
+            }
+            (Some(value), None) => timeout = Some((attr.span, value)),
+            (None, _) => {
+                cx.emit_err(errors::TestTimeoutMalformed { span: attr.span });
+            }
+        }
+        false
+    });
+    timeout
+}
+
+/// Removes the `#[case(...)]` attributes from a test, which are checked along with its signature.
+fn take_cases(cx: &ExtCtxt<'_>, attrs: &mut ast::AttrVec) -> Vec<ast::Attribute> {
+    let mut cases = Vec::new();
+    attrs.retain(|attr| {
+        if !attr.has_name(sym::case) {
+            return true;
+        }
+        if !cx.ecfg.features.parameterized_tests {
+            feature_err(
+                &cx.sess.parse_sess,
+                sym::parameterized_tests,
+                attr.span,
+                "the `#[case]` attribute is an experimental feature",
+            )
+            .emit();
+        }
+        cases.push(attr.clone());
+        false
+    });
+    cases
+}
+
 enum TestType {
     UnitTest,
     IntegrationTest,
@@ -533,6 +680,7 @@ fn check_test_signature(
     cx: &ExtCtxt<'_>,
     i: &ast::Item,
     f: &ast::Fn,
+    has_cases: bool,
 ) -> Result<(), ErrorGuaranteed> {
     let has_should_panic_attr = attr::contains_name(&i.attrs, sym::should_panic);
     let sd = &cx.sess.parse_sess.span_diagnostic;
@@ -553,7 +701,8 @@ fn check_test_signature(
         _ => true,
     };
 
-    if !f.sig.decl.inputs.is_empty() {
+    // The arguments of parameterized tests are checked against each case.
+    if !has_cases && !f.sig.decl.inputs.is_empty() {
         return Err(sd.span_err(i.span, "functions used as tests can not have any arguments"));
     }
 
@@ -575,7 +724,15 @@ fn check_bench_signature(
     cx: &ExtCtxt<'_>,
     i: &ast::Item,
     f: &ast::Fn,
+    cases: &[ast::Attribute],
 ) -> Result<(), ErrorGuaranteed> {
+    if let Some(case) = cases.first() {
+        return Err(cx
+            .sess
+            .parse_sess
+            .span_diagnostic
+            .emit_err(errors::BenchCase { span: case.span }));
+    }
     // N.B., inadequate check, but we're running
     // well before resolve, can't get too deep.
     if f.sig.decl.inputs.len() != 1 {
@@ -583,3 +740,90 @@ fn check_bench_signature(
     }
     Ok(())
 }
+
+/// A `#[case(...)]` of a parameterized test.
+struct TestCase {
+    args: ThinVec<P<ast::Expr>>,
+    /// `Some` if this case is ignored, with the reason if one was given.
+    ignore: Option<Option<Symbol>>,
+}
+
+/// Parses the arguments of each `#[case(...)]` attribute of a parameterized test, checking
+/// that every case passes as many arguments as the test takes. Returns `None` if there were
+/// any errors, after reporting them.
+fn test_case_args(
+    cx: &mut ExtCtxt<'_>,
+    i: &ast::Item,
+    f: &ast::Fn,
+    cases: &[ast::Attribute],
+) -> Option<Vec<TestCase>> {
+    let mut case_args = Vec::with_capacity(cases.len());
+    let mut has_errors = false;
+    for case in cases {
+        let ast::AttrArgs::Delimited(ast::DelimArgs {
+            delim: Delimiter::Parenthesis, tokens, ..
+        }) = &case.get_normal_item().args
+        else {
+            cx.emit_err(errors::TestCaseMalformed { span: case.span });
+            has_errors = true;
+            continue;
+        };
+        // The options of a case, such as `ignore`, come after the arguments and a `;`.
+        let is_semi = |tt: &TokenTree| {
+            matches!(tt, TokenTree::Token(token::Token { kind: token::Semi, .. }, _))
+        };
+        let mut trees = tokens.trees();
+        let args = trees.by_ref().take_while(|tt| !is_semi(tt)).cloned().collect();
+        let args = TokenStream::new(args);
+        let ignore = if tokens.trees().any(is_semi) {
+            let options = TokenStream::new(trees.cloned().collect());
+            let Some(reason) = test_case_ignore(cx, options) else {
+                cx.emit_err(errors::TestCaseOptions { span: case.span });
+                has_errors = true;
+                continue;
+            };
+            Some(reason)
+        } else {
+            None
+        };
+        // Errors in the arguments themselves are reported by the parser.
+        let Some(args) = get_exprs_from_tts(cx, args) else {
+            has_errors = true;
+            continue;
+        };
+        let expected = f.sig.decl.inputs.len();
+        if args.len() != expected {
+            cx.emit_err(errors::TestCaseArgs {
+                span: case.span,
+                test: i.ident.span,
+                found: args.len(),
+                expected,
+            });
+            has_errors = true;
+            continue;
+        }
+        case_args.push(TestCase { args: args.into_iter().collect(), ignore });
+    }
+    (!has_errors).then_some(case_args)
+}
+
+/// Parses the options of a `#[case(...; ignore)]` or `#[case(...; ignore = "reason")]`,
+/// returning the reason. Returns `None` if the options are malformed.
+fn test_case_ignore(cx: &ExtCtxt<'_>, options: TokenStream) -> Option<Option<Symbol>> {
+    let mut p = cx.new_parser_from_tts(options);
+    let meta = match p.parse_meta_item() {
+        Ok(meta) => meta,
+        Err(err) => {
+            err.cancel();
+            return None;
+        }
+    };
+    if p.token != token::Eof || !meta.has_name(sym::ignore) {
+        return None;
+    }
+    match &meta.kind {
+        ast::MetaItemKind::Word => Some(None),
+        ast::MetaItemKind::NameValue(_) => meta.value_str().map(Some),
+        ast::MetaItemKind::List(_) => None,
+    }
+}
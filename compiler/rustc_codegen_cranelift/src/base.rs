@@ -10,7 +10,7 @@
 use rustc_middle::ty::print::with_no_trimmed_paths;
 
 use crate::constant::ConstantCx;
-use crate::debuginfo::FunctionDebugContext;
+use crate::debuginfo::{FunctionDebugContext, TypeDebugContext};
 use crate::prelude::*;
 use crate::pretty_clif::CommentWriter;
 
@@ -25,6 +25,7 @@ pub(crate) struct CodegenedFunction {
 pub(crate) fn codegen_fn<'tcx>(
     tcx: TyCtxt<'tcx>,
     cx: &mut crate::CodegenCx,
+    type_dbg: &mut TypeDebugContext<'tcx>,
     cached_func: Function,
     module: &mut dyn Module,
     instance: Instance<'tcx>,
@@ -102,6 +103,12 @@ pub(crate) fn codegen_fn<'tcx>(
     fx.bcx.seal_all_blocks();
     fx.bcx.finalize();
 
+    if let (Some(debug_context), Some(func_debug_cx)) =
+        (&mut fx.cx.debug_context, &mut fx.func_debug_cx)
+    {
+        debug_context.define_variables(tcx, type_dbg, func_debug_cx, mir, &fx.local_map);
+    }
+
     // Recover all necessary data from fx, before accessing func will prevent future access to it.
     let symbol_name = fx.symbol_name;
     let clif_comments = fx.clif_comments;
@@ -220,6 +227,7 @@ pub(crate) fn compile_fn(
                 debug_context,
                 codegened_func.func_id,
                 context,
+                isa,
             );
         }
         unwind_context.add_function(codegened_func.func_id, &context, isa);
@@ -368,6 +376,39 @@ fn codegen_fn_body(fx: &mut FunctionCx<'_, '_, '_>, start_block: Block) {
                             source_info.span,
                         );
                     }
+                    AssertKind::NullPointerDereference => {
+                        let location = fx.get_caller_location(source_info).load_scalar(fx);
+
+                        codegen_panic_inner(
+                            fx,
+                            rustc_hir::LangItem::PanicNullPointerDereference,
+                            &[location],
+                            source_info.span,
+                        );
+                    }
+                    AssertKind::InvalidValue { ref value } => {
+                        let value = codegen_operand(fx, value).load_scalar(fx);
+                        let location = fx.get_caller_location(source_info).load_scalar(fx);
+
+                        codegen_panic_inner(
+                            fx,
+                            rustc_hir::LangItem::PanicInvalidValue,
+                            &[value, location],
+                            source_info.span,
+                        );
+                    }
+                    AssertKind::SliceFromRawPartsOverflow { ref len, ref size } => {
+                        let len = codegen_operand(fx, len).load_scalar(fx);
+                        let size = codegen_operand(fx, size).load_scalar(fx);
+                        let location = fx.get_caller_location(source_info).load_scalar(fx);
+
+                        codegen_panic_inner(
+                            fx,
+                            rustc_hir::LangItem::PanicSliceFromRawPartsOverflow,
+                            &[len, size, location],
+                            source_info.span,
+                        );
+                    }
                     _ => {
                         let msg_str = msg.description();
                         codegen_panic(fx, msg_str, source_info);
@@ -1,20 +1,35 @@
 //! Handling of everything related to debuginfo.
+//!
+//! Variable debuginfo is less complete than with the LLVM backend:
+//!
+//! * Enums, closures, coroutines and a few other types are only described by their name and size,
+//!   so debuggers can't show their contents.
+//! * Variables that are a projection of a local, that are a constant or that were split into
+//!   several locals by MIR optimizations aren't emitted at all. Unsized locals are emitted without
+//!   a location.
+//! * All variables are attached to their function rather than to the lexical block of their
+//!   scope, so debuggers can't tell shadowed variables apart.
 
 mod emit;
 mod line_info;
 mod object;
+mod types;
 mod unwind;
 
-use cranelift_codegen::ir::Endianness;
+use cranelift_codegen::ir::{Endianness, LabelValueLoc, ValueLabel};
 use cranelift_codegen::isa::TargetIsa;
+use cranelift_codegen::ValueLocRange;
 use gimli::write::{
-    Address, AttributeValue, DwarfUnit, FileId, LineProgram, LineString, Range, RangeList,
-    UnitEntryId,
+    Address, AttributeValue, DwarfUnit, Expression, FileId, LineProgram, LineString, Location,
+    LocationList, Range, RangeList, UnitEntryId,
 };
 use gimli::{Encoding, Format, LineEncoding, RunTimeEndian};
 use indexmap::IndexSet;
+use rustc_index::IndexVec;
+use target_lexicon::Architecture;
 
 pub(crate) use self::emit::{DebugReloc, DebugRelocName};
+pub(crate) use self::types::TypeDebugContext;
 pub(crate) use self::unwind::UnwindContext;
 use crate::prelude::*;
 
@@ -39,6 +54,21 @@ pub(crate) struct FunctionDebugContext {
     entry_id: UnitEntryId,
     function_source_loc: (FileId, u64, u64),
     source_loc_set: IndexSet<(FileId, u64, u64)>,
+    variables: Vec<(UnitEntryId, VariableLocation)>,
+}
+
+/// Where the value of a user variable lives during the execution of a function.
+#[derive(Copy, Clone, Debug)]
+pub(crate) enum VariableLocation {
+    /// The variable is stored in the Cranelift variable with the given value label. The actual
+    /// register or spill slot is only known after register allocation.
+    Ssa(ValueLabel),
+    /// The variable is a scalar pair, e.g. a fat pointer, stored in two Cranelift variables. The
+    /// sizes are those of the DWARF pieces: the first one includes the padding before the second
+    /// scalar.
+    SsaPair((ValueLabel, u64), (ValueLabel, u64)),
+    /// The variable is stored in a stack slot at the given byte offset.
+    Stack(StackSlot, i64),
 }
 
 impl DebugContext {
@@ -142,10 +172,66 @@ pub(crate) fn define_function(
         entry.set(gimli::DW_AT_decl_line, AttributeValue::Udata(line));
         entry.set(gimli::DW_AT_decl_column, AttributeValue::Udata(column));
 
+        // Spill slots of variables are described relative to the CFA.
+        let mut frame_base = Expression::new();
+        frame_base.op(gimli::DW_OP_call_frame_cfa);
+        entry.set(gimli::DW_AT_frame_base, AttributeValue::Exprloc(frame_base));
+
         FunctionDebugContext {
             entry_id,
             function_source_loc: (file_id, line, column),
             source_loc_set: IndexSet::new(),
+            variables: vec![],
+        }
+    }
+
+    /// Emit `DW_TAG_formal_parameter` and `DW_TAG_variable` entries for all user variables of a
+    /// function. Their locations are filled in by [`FunctionDebugContext::finalize`] once the
+    /// function has been compiled.
+    pub(crate) fn define_variables<'tcx>(
+        &mut self,
+        tcx: TyCtxt<'tcx>,
+        type_dbg: &mut TypeDebugContext<'tcx>,
+        func_debug_cx: &mut FunctionDebugContext,
+        mir: &Body<'tcx>,
+        local_map: &IndexVec<Local, CPlace<'tcx>>,
+    ) {
+        for var in &mir.var_debug_info {
+            // FIXME support composite variables and variables pointing into a projection of a
+            // local or at a constant
+            if var.composite.is_some() {
+                continue;
+            }
+            let VarDebugInfoContents::Place(place) = var.value else { continue };
+            let Some(local) = place.as_local() else { continue };
+            let place = local_map[local];
+            let location = place.debuginfo_location(tcx);
+
+            let (file, line, column) =
+                DebugContext::get_span_loc(tcx, mir.span, var.source_info.span);
+            let file_id = self.add_source_file(&file);
+            let type_id = self.debug_type(tcx, type_dbg, place.layout().ty);
+
+            // FIXME add to the lexical block of the variable's scope instead of the function
+            let tag = if var.argument_index.is_some() {
+                gimli::DW_TAG_formal_parameter
+            } else {
+                gimli::DW_TAG_variable
+            };
+            let var_id = self.dwarf.unit.add(func_debug_cx.entry_id, tag);
+            let var_entry = self.dwarf.unit.get_mut(var_id);
+            let name_id = self.dwarf.strings.add(var.name.as_str());
+            var_entry.set(gimli::DW_AT_name, AttributeValue::StringRef(name_id));
+            var_entry.set(gimli::DW_AT_type, AttributeValue::UnitRef(type_id));
+            var_entry.set(gimli::DW_AT_decl_file, AttributeValue::FileIndex(Some(file_id)));
+            var_entry.set(gimli::DW_AT_decl_line, AttributeValue::Udata(line));
+            var_entry.set(gimli::DW_AT_decl_column, AttributeValue::Udata(column));
+
+            // Variables without a location are still emitted so that the debugger shows them as
+            // optimized out rather than not existing at all.
+            if let Some(location) = location {
+                func_debug_cx.variables.push((var_id, location));
+            }
         }
     }
 }
@@ -156,10 +242,12 @@ pub(crate) fn finalize(
         debug_context: &mut DebugContext,
         func_id: FuncId,
         context: &Context,
+        isa: &dyn TargetIsa,
     ) {
         let symbol = func_id.as_u32() as usize;
 
         let end = self.create_debug_lines(debug_context, symbol, context);
+        self.add_variable_locations(debug_context, symbol, context, isa);
 
         debug_context.unit_range_list.0.push(Range::StartLength {
             begin: Address::Symbol { symbol, addend: 0 },
@@ -176,3 +264,131 @@ pub(crate) fn finalize(
         func_entry.set(gimli::DW_AT_high_pc, AttributeValue::Udata(u64::from(end)));
     }
 }
+
+impl FunctionDebugContext {
+    fn add_variable_locations(
+        &self,
+        debug_context: &mut DebugContext,
+        symbol: usize,
+        context: &Context,
+        isa: &dyn TargetIsa,
+    ) {
+        let compiled_code = context.compiled_code().unwrap();
+
+        for &(var_id, location) in &self.variables {
+            let location = match location {
+                VariableLocation::Ssa(label) => {
+                    let Some(ranges) = compiled_code.value_labels_ranges.get(&label) else {
+                        continue;
+                    };
+                    let locations = ranges
+                        .iter()
+                        .filter_map(|range| {
+                            let mut expr = Expression::new();
+                            push_value_label_location(&mut expr, isa, range.loc)?;
+                            Some(Location::StartEnd {
+                                begin: Address::Symbol { symbol, addend: i64::from(range.start) },
+                                end: Address::Symbol { symbol, addend: i64::from(range.end) },
+                                data: expr,
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    if locations.is_empty() {
+                        continue;
+                    }
+                    let list_id = debug_context.dwarf.unit.locations.add(LocationList(locations));
+                    AttributeValue::LocationListRef(list_id)
+                }
+                VariableLocation::SsaPair((label_a, size_a), (label_b, size_b)) => {
+                    let ranges_a = compiled_code.value_labels_ranges.get(&label_a);
+                    let ranges_b = compiled_code.value_labels_ranges.get(&label_b);
+                    let ranges_a = ranges_a.map_or(&[][..], |ranges| &ranges[..]);
+                    let ranges_b = ranges_b.map_or(&[][..], |ranges| &ranges[..]);
+
+                    // The two halves are tracked separately and can move independently, so split
+                    // the function at every point where either of them does.
+                    let mut bounds = ranges_a
+                        .iter()
+                        .chain(ranges_b)
+                        .flat_map(|range| [range.start, range.end])
+                        .collect::<Vec<_>>();
+                    bounds.sort_unstable();
+                    bounds.dedup();
+
+                    let loc_at = |ranges: &[ValueLocRange], offset: u32| {
+                        ranges
+                            .iter()
+                            .find(|range| range.start <= offset && offset < range.end)
+                            .map(|range| range.loc)
+                    };
+                    let locations = bounds
+                        .windows(2)
+                        .filter_map(|bounds| {
+                            let (start, end) = (bounds[0], bounds[1]);
+                            let loc_a = loc_at(ranges_a, start);
+                            let loc_b = loc_at(ranges_b, start);
+                            if loc_a.is_none() && loc_b.is_none() {
+                                return None;
+                            }
+
+                            // A piece without a location is shown as optimized out.
+                            let mut expr = Expression::new();
+                            for (loc, size) in [(loc_a, size_a), (loc_b, size_b)] {
+                                if let Some(loc) = loc {
+                                    push_value_label_location(&mut expr, isa, loc);
+                                }
+                                expr.op_piece(size);
+                            }
+                            Some(Location::StartEnd {
+                                begin: Address::Symbol { symbol, addend: i64::from(start) },
+                                end: Address::Symbol { symbol, addend: i64::from(end) },
+                                data: expr,
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    if locations.is_empty() {
+                        continue;
+                    }
+                    let list_id = debug_context.dwarf.unit.locations.add(LocationList(locations));
+                    AttributeValue::LocationListRef(list_id)
+                }
+                VariableLocation::Stack(stack_slot, offset) => {
+                    let Some(sp) = stack_pointer_register(isa) else { continue };
+                    // Stack slot offsets are relative to the stack pointer after the prologue.
+                    let slot_offset = compiled_code.sized_stackslot_offsets[stack_slot];
+                    let mut expr = Expression::new();
+                    expr.op_breg(sp, i64::from(slot_offset) + offset);
+                    AttributeValue::Exprloc(expr)
+                }
+            };
+
+            debug_context.dwarf.unit.get_mut(var_id).set(gimli::DW_AT_location, location);
+        }
+    }
+}
+
+/// Appends the location of a value to `expr`. Returns `None`, leaving `expr` untouched, if the
+/// location can't be described.
+fn push_value_label_location(
+    expr: &mut Expression,
+    isa: &dyn TargetIsa,
+    loc: LabelValueLoc,
+) -> Option<()> {
+    match loc {
+        LabelValueLoc::Reg(reg) => {
+            expr.op_reg(gimli::Register(isa.map_regalloc_reg_to_dwarf(reg).ok()?));
+        }
+        // The subprogram's DW_AT_frame_base is DW_OP_call_frame_cfa.
+        LabelValueLoc::CFAOffset(offset) => expr.op_fbreg(offset),
+    }
+    Some(())
+}
+
+fn stack_pointer_register(isa: &dyn TargetIsa) -> Option<gimli::Register> {
+    match isa.triple().architecture {
+        Architecture::X86_64 => Some(gimli::X86_64::RSP),
+        Architecture::Aarch64(_) => Some(gimli::AArch64::SP),
+        Architecture::Riscv64(_) => Some(gimli::RiscV::SP),
+        _ => None,
+    }
+}
@@ -0,0 +1,225 @@
+//! Type debuginfo generation (`DW_TAG_*_type` entries)
+
+use gimli::write::{AttributeValue, UnitEntryId};
+use rustc_codegen_ssa::debuginfo::type_names;
+use rustc_span::Symbol;
+
+use crate::prelude::*;
+
+/// Type DIEs that have already been emitted for the current codegen unit.
+#[derive(Default)]
+pub(crate) struct TypeDebugContext<'tcx> {
+    type_map: FxHashMap<Ty<'tcx>, UnitEntryId>,
+}
+
+impl DebugContext {
+    pub(crate) fn debug_type<'tcx>(
+        &mut self,
+        tcx: TyCtxt<'tcx>,
+        type_dbg: &mut TypeDebugContext<'tcx>,
+        ty: Ty<'tcx>,
+    ) -> UnitEntryId {
+        if let Some(&type_id) = type_dbg.type_map.get(&ty) {
+            return type_id;
+        }
+
+        let type_id = match ty.kind() {
+            ty::Never | ty::Bool | ty::Char | ty::Int(_) | ty::Uint(_) | ty::Float(_) => {
+                self.basic_type(tcx, ty)
+            }
+            ty::Ref(_, pointee_ty, _) | ty::RawPtr(TypeAndMut { ty: pointee_ty, mutbl: _ }) => {
+                self.pointer_type(tcx, type_dbg, ty, *pointee_ty)
+            }
+            ty::Array(elem_ty, len) => self.array_type(tcx, type_dbg, ty, *elem_ty, *len),
+            ty::Adt(adt_def, args) if adt_def.is_struct() => {
+                let variant = adt_def.non_enum_variant();
+                let field_tys =
+                    variant.fields.iter().map(|field| (field.name, field.ty(tcx, args)));
+                self.struct_type(tcx, type_dbg, ty, field_tys.collect())
+            }
+            ty::Tuple(components) => {
+                let field_tys = components
+                    .iter()
+                    .enumerate()
+                    .map(|(i, ty)| (Symbol::intern(&format!("__{i}")), ty));
+                self.struct_type(tcx, type_dbg, ty, field_tys.collect())
+            }
+            // FIXME emit proper type info for enums, closures, ...
+            _ => self.placeholder_for_type(tcx, ty),
+        };
+
+        type_dbg.type_map.insert(ty, type_id);
+
+        type_id
+    }
+
+    fn add_type_name<'tcx>(&mut self, tcx: TyCtxt<'tcx>, ty: Ty<'tcx>, type_id: UnitEntryId) {
+        let name = type_names::compute_debuginfo_type_name(tcx, ty, false);
+        let name_id = self.dwarf.strings.add(name);
+        self.dwarf.unit.get_mut(type_id).set(gimli::DW_AT_name, AttributeValue::StringRef(name_id));
+    }
+
+    fn basic_type<'tcx>(&mut self, tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> UnitEntryId {
+        let encoding = match ty.kind() {
+            ty::Never | ty::Uint(_) => gimli::DW_ATE_unsigned,
+            ty::Bool => gimli::DW_ATE_boolean,
+            ty::Char => gimli::DW_ATE_UTF,
+            ty::Int(_) => gimli::DW_ATE_signed,
+            ty::Float(_) => gimli::DW_ATE_float,
+            _ => unreachable!(),
+        };
+        let size = RevealAllLayoutCx(tcx).layout_of(ty).size.bytes();
+
+        let type_id = self.dwarf.unit.add(self.dwarf.unit.root(), gimli::DW_TAG_base_type);
+        let type_entry = self.dwarf.unit.get_mut(type_id);
+        type_entry.set(gimli::DW_AT_encoding, AttributeValue::Encoding(encoding));
+        type_entry.set(gimli::DW_AT_byte_size, AttributeValue::Udata(size));
+        self.add_type_name(tcx, ty, type_id);
+
+        type_id
+    }
+
+    fn pointer_type<'tcx>(
+        &mut self,
+        tcx: TyCtxt<'tcx>,
+        type_dbg: &mut TypeDebugContext<'tcx>,
+        ptr_ty: Ty<'tcx>,
+        pointee_ty: Ty<'tcx>,
+    ) -> UnitEntryId {
+        if has_ptr_meta(tcx, pointee_ty) {
+            return self.fat_pointer_type(tcx, type_dbg, ptr_ty, pointee_ty);
+        }
+
+        let pointee_type_id = self.debug_type(tcx, type_dbg, pointee_ty);
+
+        let type_id = self.dwarf.unit.add(self.dwarf.unit.root(), gimli::DW_TAG_pointer_type);
+        let type_entry = self.dwarf.unit.get_mut(type_id);
+        type_entry.set(gimli::DW_AT_type, AttributeValue::UnitRef(pointee_type_id));
+        type_entry.set(
+            gimli::DW_AT_byte_size,
+            AttributeValue::Udata(tcx.data_layout.pointer_size.bytes()),
+        );
+        self.add_type_name(tcx, ptr_ty, type_id);
+
+        type_id
+    }
+
+    /// Describes a fat pointer as a struct of the data pointer and the metadata, using the same
+    /// field names as the LLVM backend so that the debugger pretty-printers recognize them.
+    fn fat_pointer_type<'tcx>(
+        &mut self,
+        tcx: TyCtxt<'tcx>,
+        type_dbg: &mut TypeDebugContext<'tcx>,
+        ptr_ty: Ty<'tcx>,
+        pointee_ty: Ty<'tcx>,
+    ) -> UnitEntryId {
+        let tail = tcx.struct_tail_erasing_lifetimes(pointee_ty, ParamEnv::reveal_all());
+        let (data_ptr_name, data_pointee_ty, metadata_name, metadata_ty) = match tail.kind() {
+            ty::Slice(elem_ty) if pointee_ty.is_slice() => {
+                ("data_ptr", *elem_ty, "length", tcx.types.usize)
+            }
+            ty::Str if pointee_ty.is_str() => ("data_ptr", tcx.types.u8, "length", tcx.types.usize),
+            // Custom DSTs, the data pointer points to the whole struct.
+            ty::Slice(_) | ty::Str => ("data_ptr", pointee_ty, "length", tcx.types.usize),
+            ty::Dynamic(..) => {
+                let vtable_ty = Ty::new_imm_ptr(tcx, Ty::new_array(tcx, tcx.types.usize, 3));
+                ("pointer", Ty::new_unit(tcx), "vtable", vtable_ty)
+            }
+            _ => return self.placeholder_for_type(tcx, ptr_ty),
+        };
+        let fields = [
+            (data_ptr_name, Ty::new_imm_ptr(tcx, data_pointee_ty), 0),
+            (metadata_name, metadata_ty, tcx.data_layout.pointer_size.bytes()),
+        ];
+
+        let layout = RevealAllLayoutCx(tcx).layout_of(ptr_ty);
+
+        let type_id = self.dwarf.unit.add(self.dwarf.unit.root(), gimli::DW_TAG_structure_type);
+        let type_entry = self.dwarf.unit.get_mut(type_id);
+        type_entry.set(gimli::DW_AT_byte_size, AttributeValue::Udata(layout.size.bytes()));
+        type_entry.set(gimli::DW_AT_alignment, AttributeValue::Udata(layout.align.abi.bytes()));
+        self.add_type_name(tcx, ptr_ty, type_id);
+
+        for (field_name, field_ty, field_offset) in fields {
+            let field_type_id = self.debug_type(tcx, type_dbg, field_ty);
+
+            let field_id = self.dwarf.unit.add(type_id, gimli::DW_TAG_member);
+            let field_entry = self.dwarf.unit.get_mut(field_id);
+            let name_id = self.dwarf.strings.add(field_name);
+            field_entry.set(gimli::DW_AT_name, AttributeValue::StringRef(name_id));
+            field_entry.set(gimli::DW_AT_type, AttributeValue::UnitRef(field_type_id));
+            field_entry.set(gimli::DW_AT_data_member_location, AttributeValue::Udata(field_offset));
+        }
+
+        type_id
+    }
+
+    fn array_type<'tcx>(
+        &mut self,
+        tcx: TyCtxt<'tcx>,
+        type_dbg: &mut TypeDebugContext<'tcx>,
+        array_ty: Ty<'tcx>,
+        elem_ty: Ty<'tcx>,
+        len: ty::Const<'tcx>,
+    ) -> UnitEntryId {
+        let elem_type_id = self.debug_type(tcx, type_dbg, elem_ty);
+
+        let type_id = self.dwarf.unit.add(self.dwarf.unit.root(), gimli::DW_TAG_array_type);
+        let type_entry = self.dwarf.unit.get_mut(type_id);
+        type_entry.set(gimli::DW_AT_type, AttributeValue::UnitRef(elem_type_id));
+
+        let subrange_id = self.dwarf.unit.add(type_id, gimli::DW_TAG_subrange_type);
+        let subrange_entry = self.dwarf.unit.get_mut(subrange_id);
+        subrange_entry.set(gimli::DW_AT_lower_bound, AttributeValue::Udata(0));
+        let len = len.eval_target_usize(tcx, ParamEnv::reveal_all());
+        subrange_entry.set(gimli::DW_AT_count, AttributeValue::Udata(len));
+
+        self.add_type_name(tcx, array_ty, type_id);
+
+        type_id
+    }
+
+    fn struct_type<'tcx>(
+        &mut self,
+        tcx: TyCtxt<'tcx>,
+        type_dbg: &mut TypeDebugContext<'tcx>,
+        struct_ty: Ty<'tcx>,
+        fields: Vec<(Symbol, Ty<'tcx>)>,
+    ) -> UnitEntryId {
+        let layout = RevealAllLayoutCx(tcx).layout_of(struct_ty);
+
+        let type_id = self.dwarf.unit.add(self.dwarf.unit.root(), gimli::DW_TAG_structure_type);
+        let type_entry = self.dwarf.unit.get_mut(type_id);
+        type_entry.set(gimli::DW_AT_byte_size, AttributeValue::Udata(layout.size.bytes()));
+        type_entry.set(gimli::DW_AT_alignment, AttributeValue::Udata(layout.align.abi.bytes()));
+        self.add_type_name(tcx, struct_ty, type_id);
+
+        // Insert the struct before describing the fields to support recursive types.
+        type_dbg.type_map.insert(struct_ty, type_id);
+
+        for (i, (field_name, field_ty)) in fields.into_iter().enumerate() {
+            let field_type_id = self.debug_type(tcx, type_dbg, field_ty);
+            let field_offset = layout.fields.offset(i).bytes();
+
+            let field_id = self.dwarf.unit.add(type_id, gimli::DW_TAG_member);
+            let field_entry = self.dwarf.unit.get_mut(field_id);
+            let name_id = self.dwarf.strings.add(field_name.as_str());
+            field_entry.set(gimli::DW_AT_name, AttributeValue::StringRef(name_id));
+            field_entry.set(gimli::DW_AT_type, AttributeValue::UnitRef(field_type_id));
+            field_entry.set(gimli::DW_AT_data_member_location, AttributeValue::Udata(field_offset));
+        }
+
+        type_id
+    }
+
+    fn placeholder_for_type<'tcx>(&mut self, tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> UnitEntryId {
+        let layout = RevealAllLayoutCx(tcx).layout_of(ty);
+
+        let type_id = self.dwarf.unit.add(self.dwarf.unit.root(), gimli::DW_TAG_structure_type);
+        let type_entry = self.dwarf.unit.get_mut(type_id);
+        type_entry.set(gimli::DW_AT_byte_size, AttributeValue::Udata(layout.size.bytes()));
+        self.add_type_name(tcx, ty, type_id);
+
+        type_id
+    }
+}
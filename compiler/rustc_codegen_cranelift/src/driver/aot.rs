@@ -20,6 +20,7 @@
 use rustc_session::Session;
 
 use crate::concurrency_limiter::{ConcurrencyLimiter, ConcurrencyLimiterToken};
+use crate::debuginfo::TypeDebugContext;
 use crate::global_asm::GlobalAsmConfig;
 use crate::{prelude::*, BackendConfig};
 
@@ -283,6 +284,7 @@ fn module_codegen(
                 cgu_name,
             );
             super::predefine_mono_items(tcx, &mut module, &mono_items);
+            let mut type_dbg = TypeDebugContext::default();
             let mut codegened_functions = vec![];
             for (mono_item, _) in mono_items {
                 match mono_item {
@@ -290,6 +292,7 @@ fn module_codegen(
                         let codegened_function = crate::base::codegen_fn(
                             tcx,
                             &mut cx,
+                            &mut type_dbg,
                             Function::new(),
                             &mut module,
                             inst,
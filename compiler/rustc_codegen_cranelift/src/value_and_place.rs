@@ -2,10 +2,20 @@
 
 use cranelift_codegen::entity::EntityRef;
 use cranelift_codegen::ir::immediates::Offset32;
+use cranelift_codegen::ir::ValueLabel;
 use rustc_middle::ty::FnSig;
 
+use crate::debuginfo::VariableLocation;
 use crate::prelude::*;
 
+/// Attach the value label of `var` to `val` so that the location of the variable can be tracked
+/// through register allocation for debuginfo.
+fn set_var_label(fx: &mut FunctionCx<'_, '_, '_>, var: Variable, val: Value) {
+    if fx.func_debug_cx.is_some() {
+        fx.bcx.set_val_label(val, ValueLabel::new(var.index()));
+    }
+}
+
 fn codegen_field<'tcx>(
     fx: &mut FunctionCx<'_, '_, 'tcx>,
     base: Pointer,
@@ -416,14 +426,14 @@ pub(crate) fn to_cvalue(self, fx: &mut FunctionCx<'_, '_, 'tcx>) -> CValue<'tcx>
         match self.inner {
             CPlaceInner::Var(_local, var) => {
                 let val = fx.bcx.use_var(var);
-                //fx.bcx.set_val_label(val, cranelift_codegen::ir::ValueLabel::new(var.index()));
+                set_var_label(fx, var, val);
                 CValue::by_val(val, layout)
             }
             CPlaceInner::VarPair(_local, var1, var2) => {
                 let val1 = fx.bcx.use_var(var1);
-                //fx.bcx.set_val_label(val1, cranelift_codegen::ir::ValueLabel::new(var1.index()));
+                set_var_label(fx, var1, val1);
                 let val2 = fx.bcx.use_var(var2);
-                //fx.bcx.set_val_label(val2, cranelift_codegen::ir::ValueLabel::new(var2.index()));
+                set_var_label(fx, var2, val2);
                 CValue::by_val_pair(val1, val2, layout)
             }
             CPlaceInner::Addr(ptr, extra) => {
@@ -436,6 +446,36 @@ pub(crate) fn to_cvalue(self, fx: &mut FunctionCx<'_, '_, 'tcx>) -> CValue<'tcx>
         }
     }
 
+    /// Where the value of this place can be found for variable debuginfo, if it can be described.
+    pub(crate) fn debuginfo_location(self, tcx: TyCtxt<'tcx>) -> Option<VariableLocation> {
+        match self.inner {
+            CPlaceInner::Var(_local, var) => {
+                Some(VariableLocation::Ssa(ValueLabel::new(var.index())))
+            }
+            CPlaceInner::VarPair(_local, var1, var2) => {
+                let Abi::ScalarPair(a_scalar, b_scalar) = self.layout.abi else {
+                    unreachable!("VarPair with non-ScalarPair layout {:?}", self.layout)
+                };
+                // The first piece extends up to the second scalar, see
+                // `scalar_pair_calculate_b_offset`.
+                let a_size = a_scalar.size(&tcx).align_to(b_scalar.align(&tcx).abi).bytes();
+                let b_size = b_scalar.size(&tcx).bytes();
+                Some(VariableLocation::SsaPair(
+                    (ValueLabel::new(var1.index()), a_size),
+                    (ValueLabel::new(var2.index()), b_size),
+                ))
+            }
+            CPlaceInner::Addr(ptr, None) => match ptr.debug_base_and_offset() {
+                (crate::pointer::PointerBase::Stack(stack_slot), offset) => {
+                    Some(VariableLocation::Stack(stack_slot, i64::from(i32::from(offset))))
+                }
+                (crate::pointer::PointerBase::Addr(_), _)
+                | (crate::pointer::PointerBase::Dangling(_), _) => None,
+            },
+            CPlaceInner::Addr(_ptr, Some(_meta)) => None,
+        }
+    }
+
     pub(crate) fn debug_comment(self) -> (&'static str, String) {
         match self.inner {
             CPlaceInner::Var(_local, var) => ("ssa", format!("var={}", var.index())),
@@ -544,7 +584,7 @@ fn transmute_scalar<'tcx>(
                 // calling `write_cvalue` you need to add a `bint` instruction.
                 _ => unreachable!("write_cvalue_transmute: {:?} -> {:?}", src_ty, dst_ty),
             };
-            //fx.bcx.set_val_label(data, cranelift_codegen::ir::ValueLabel::new(var.index()));
+            set_var_label(fx, var, data);
             fx.bcx.def_var(var, data);
         }
 
@@ -30,6 +30,10 @@ fn insert_reference_to_gdb_debug_scripts_section_global(&mut self) {
         // TODO(antoyo): insert reference to gdb debug scripts section global.
     }
 
+    fn insert_reference_to_lldb_formatters_section_global(&mut self) {
+        // TODO: insert reference to lldb formatters section global.
+    }
+
     fn set_var_name(&mut self, _value: RValue<'gcc>, _name: &str) {
         unimplemented!();
     }
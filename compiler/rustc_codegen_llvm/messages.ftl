@@ -57,6 +57,8 @@ codegen_llvm_prepare_thin_lto_context_with_llvm_err = failed to prepare thin LTO
 codegen_llvm_prepare_thin_lto_module = failed to prepare thin LTO module
 codegen_llvm_prepare_thin_lto_module_with_llvm_err = failed to prepare thin LTO module: {$llvm_err}
 
+codegen_llvm_read_profile = failed to read the profile {$path}: {$llvm_err}
+
 codegen_llvm_run_passes = failed to run LLVM passes
 codegen_llvm_run_passes_with_llvm_err = failed to run LLVM passes: {$llvm_err}
 
@@ -87,6 +89,8 @@ codegen_llvm_unknown_ctarget_feature_prefix =
 
 codegen_llvm_unknown_debuginfo_compression = unknown debuginfo compression algorithm {$algorithm} - will fall back to uncompressed debuginfo
 
+codegen_llvm_write_annotated_asm = failed to write annotated assembly to {$path}: {$err}
+
 codegen_llvm_write_bytecode = failed to write bytecode to {$path}: {$err}
 
 codegen_llvm_write_ir = failed to write LLVM IR to {$path}
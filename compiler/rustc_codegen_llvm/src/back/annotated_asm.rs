@@ -0,0 +1,266 @@
+//! Turns the assembly printed by LLVM into the output of `--emit=asm-annotated`.
+//!
+//! The assembly is meant to be read rather than assembled: assembler directives are removed,
+//! labels that no instruction refers to are dropped, symbol names are demangled and the source
+//! lines recorded in the `.loc` directives of the line table are interleaved with the
+//! instructions generated for them. With `-Z asm-filter`, only the functions whose demangled
+//! name contains the filter are kept.
+
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Sections that hold metadata about the code, like debuginfo and unwind tables, rather than
+/// code or data of the program. Their contents are left out.
+const METADATA_SECTIONS: &[&str] = &[
+    ".debug_",
+    ".note",
+    ".comment",
+    ".gcc_except_table",
+    ".eh_frame",
+    ".llvm",
+    "__DWARF,",
+    "__LD,__compact_unwind",
+    "__TEXT,__eh_frame",
+    "__TEXT,__gcc_except_tab",
+];
+
+/// Directives that declare the symbol named by their first argument.
+const SYMBOL_DIRECTIVES: &[&str] = &[
+    ".type",
+    ".globl",
+    ".global",
+    ".weak",
+    ".hidden",
+    ".protected",
+    ".private_extern",
+    ".weak_definition",
+];
+
+/// Directives that emit data, and are kept as they are part of the contents of the output.
+const DATA_DIRECTIVES: &[&str] = &[
+    ".byte", ".2byte", ".4byte", ".8byte", ".short", ".hword", ".word", ".long", ".int", ".quad",
+    ".xword", ".ascii", ".asciz", ".string", ".zero", ".space",
+];
+
+pub fn annotate_asm(asm: &str, filter: Option<&str>) -> String {
+    let mut files = FxHashMap::default();
+    let mut sources = SourceCache::default();
+    let mut symbols = FxHashSet::default();
+
+    // Split the assembly into the chunks following each symbol, and resolve the `.loc`
+    // directives into source lines while we still see all of them.
+    let mut chunks = vec![Chunk { name: None, lines: Vec::new() }];
+    let mut last_loc = None;
+    let mut in_metadata_section = false;
+    for line in asm.lines() {
+        let trimmed = line.trim();
+        if let Some(section) = section_name(trimmed) {
+            in_metadata_section =
+                METADATA_SECTIONS.iter().any(|prefix| section.starts_with(prefix));
+            continue;
+        }
+        if in_metadata_section {
+            continue;
+        }
+        if let Some(args) = trimmed.strip_prefix(".file") {
+            if let Some((index, path)) = parse_file_directive(args) {
+                files.insert(index, path);
+            }
+            continue;
+        }
+        if let Some(args) = trimmed.strip_prefix(".loc") {
+            let mut args = args.split_whitespace();
+            let file = args.next().and_then(|f| f.parse::<u32>().ok());
+            let line = args.next().and_then(|l| l.parse::<usize>().ok());
+            // Line 0 is used for code that doesn't belong to any source line.
+            if let (Some(file), Some(line @ 1..)) = (file, line)
+                && last_loc != Some((file, line))
+                && let Some(path) = files.get(&file)
+            {
+                last_loc = Some((file, line));
+                chunks.last_mut().unwrap().lines.push(Line::Source(path.clone(), line));
+            }
+            continue;
+        }
+        if let Some((directive, args)) = trimmed.split_once(char::is_whitespace)
+            && SYMBOL_DIRECTIVES.contains(&directive)
+        {
+            let symbol = args.split(',').next().unwrap().trim();
+            symbols.insert(symbol.to_owned());
+        }
+
+        if let Some(label) = label(trimmed) {
+            if !is_local_label(label, &symbols) {
+                last_loc = None;
+                chunks.push(Chunk { name: Some(label.to_owned()), lines: Vec::new() });
+            }
+            chunks.last_mut().unwrap().lines.push(Line::Label(label.to_owned()));
+        } else if is_kept_line(trimmed) {
+            chunks.last_mut().unwrap().lines.push(Line::Asm(line.to_owned()));
+        }
+    }
+
+    if let Some(filter) = filter {
+        chunks.retain(|chunk| {
+            chunk.name.as_deref().is_some_and(|name| demangle_symbol(name).contains(filter))
+        });
+    }
+
+    // Local labels are kept only when an instruction or data directive refers to them.
+    let referenced: FxHashSet<&str> = chunks
+        .iter()
+        .flat_map(|chunk| &chunk.lines)
+        .filter_map(|line| if let Line::Asm(asm) = line { Some(asm) } else { None })
+        .flat_map(|asm| identifiers(asm).map(|(_, ident)| ident))
+        .collect();
+
+    let mut out = String::new();
+    for chunk in &chunks {
+        for line in &chunk.lines {
+            match line {
+                Line::Label(label) => {
+                    let is_local = is_local_label(label, &symbols);
+                    if is_local && !referenced.contains(label.as_str()) {
+                        continue;
+                    }
+                    if !is_local && !out.is_empty() {
+                        out.push('\n');
+                    }
+                    writeln!(out, "{}:", demangle_symbol(label)).unwrap();
+                }
+                Line::Source(path, line) => match sources.line(path, *line) {
+                    Some(source) => {
+                        writeln!(out, "    // {}:{line}: {}", path.display(), source.trim())
+                            .unwrap()
+                    }
+                    None => writeln!(out, "    // {}:{line}", path.display()).unwrap(),
+                },
+                Line::Asm(asm) => writeln!(out, "{}", demangle_line(asm)).unwrap(),
+            }
+        }
+    }
+    out
+}
+
+struct Chunk {
+    /// The symbol the chunk starts with, `None` for the lines preceding the first symbol.
+    name: Option<String>,
+    lines: Vec<Line>,
+}
+
+enum Line {
+    Label(String),
+    Source(PathBuf, usize),
+    Asm(String),
+}
+
+#[derive(Default)]
+struct SourceCache {
+    files: FxHashMap<PathBuf, Option<Vec<String>>>,
+}
+
+impl SourceCache {
+    /// Returns the text of a line of a source file, if the file can still be read.
+    fn line(&mut self, path: &Path, line: usize) -> Option<&str> {
+        let lines = self.files.entry(path.to_owned()).or_insert_with(|| {
+            let source = fs::read_to_string(path).ok()?;
+            Some(source.lines().map(str::to_owned).collect())
+        });
+        lines.as_ref()?.get(line - 1).map(String::as_str)
+    }
+}
+
+/// Parses the arguments of a `.file <index> "<directory>" "<file>" [md5 ...]` directive, in
+/// which the directory is optional.
+fn parse_file_directive(args: &str) -> Option<(u32, PathBuf)> {
+    let (index, mut rest) = args.trim_start().split_once(char::is_whitespace)?;
+    let index = index.parse().ok()?;
+
+    let mut strings = Vec::new();
+    while let Some(start) = rest.find('"') {
+        let end = start + 1 + rest[start + 1..].find('"')?;
+        strings.push(&rest[start + 1..end]);
+        rest = &rest[end + 1..];
+    }
+    let path = match strings[..] {
+        [file] => PathBuf::from(file),
+        [directory, file, ..] => Path::new(directory).join(file),
+        [] => return None,
+    };
+    Some((index, path))
+}
+
+/// Returns the name of the section switched to by a line, if any.
+fn section_name(line: &str) -> Option<&str> {
+    let mut words = line.split_whitespace();
+    match words.next()? {
+        ".section" => Some(words.next()?.trim_matches('"')),
+        directive @ (".text" | ".data" | ".bss") => Some(directive),
+        _ => None,
+    }
+}
+
+/// Returns the name of the label defined by a line, if any. LLVM may follow the label with a
+/// comment.
+fn label(line: &str) -> Option<&str> {
+    let label = line.split_whitespace().next()?.strip_suffix(':')?;
+    let is_label = !label.is_empty()
+        && !label.starts_with('"')
+        && label.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '$'));
+    is_label.then_some(label)
+}
+
+/// Whether the label is private to the object file. LLVM names them `.L...` on ELF and `L...` or
+/// `l...` on Mach-O, where symbols that aren't declared by a directive start with `_`.
+fn is_local_label(label: &str, symbols: &FxHashSet<String>) -> bool {
+    label.starts_with(".L")
+        || (label.starts_with(|c| c == 'L' || c == 'l') && !symbols.contains(label))
+}
+
+/// Whether a line other than a label is kept in the output: instructions and data directives.
+fn is_kept_line(line: &str) -> bool {
+    if line.is_empty() || line.contains("-- Begin function") || line.contains("-- End function") {
+        return false;
+    }
+    match line.split_whitespace().next() {
+        Some(directive) if directive.starts_with('.') => DATA_DIRECTIVES.contains(&directive),
+        _ => true,
+    }
+}
+
+/// Iterates over the identifiers of a line, along with their offset in the line.
+fn identifiers(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    let is_ident_char = |c: char| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '$');
+    // Identifiers start at the beginning of the line or right after a separator.
+    [0].into_iter()
+        .chain(line.match_indices(move |c: char| !is_ident_char(c)).map(|(i, sep)| i + sep.len()))
+        .filter_map(move |start| {
+            let rest = &line[start..];
+            let len = rest.find(|c: char| !is_ident_char(c)).unwrap_or(rest.len());
+            (len > 0).then(|| (start, &rest[..len]))
+        })
+}
+
+fn demangle_symbol(symbol: &str) -> String {
+    match rustc_demangle::try_demangle(symbol) {
+        Ok(demangled) => format!("{demangled:#}"),
+        Err(_) => symbol.to_owned(),
+    }
+}
+
+/// Replaces the Rust symbols referred to by a line with their demangled names.
+fn demangle_line(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut last = 0;
+    for (start, ident) in identifiers(line) {
+        if let Ok(demangled) = rustc_demangle::try_demangle(ident) {
+            out.push_str(&line[last..start]);
+            write!(out, "{demangled:#}").unwrap();
+            last = start + ident.len();
+        }
+    }
+    out.push_str(&line[last..]);
+    out
+}
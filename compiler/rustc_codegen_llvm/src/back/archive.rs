@@ -15,8 +15,9 @@
 use crate::llvm::archive_ro::{ArchiveRO, Child};
 use crate::llvm::{self, ArchiveKind, LLVMMachineType, LLVMRustCOFFShortExport};
 use rustc_codegen_ssa::back::archive::{
-    get_native_object_symbols, try_extract_macho_fat_archive, ArArchiveBuilder,
-    ArchiveBuildFailure, ArchiveBuilder, ArchiveBuilderBuilder, UnknownArchiveKind,
+    get_native_object_symbols, sort_members_by_name, try_extract_macho_fat_archive,
+    ArArchiveBuilder, ArchiveBuildFailure, ArchiveBuilder, ArchiveBuilderBuilder,
+    UnknownArchiveKind,
 };
 
 use rustc_session::cstore::DllImport;
@@ -359,6 +360,7 @@ fn build_with_llvm(&mut self, output: &Path) -> io::Result<bool> {
         let mut additions = mem::take(&mut self.additions);
         let mut strings = Vec::new();
         let mut members = Vec::new();
+        let mut member_names = Vec::new();
 
         let dst = CString::new(output.to_str().unwrap())?;
 
@@ -373,6 +375,7 @@ fn build_with_llvm(&mut self, output: &Path) -> io::Result<bool> {
                             name.as_ptr(),
                             None,
                         ));
+                        member_names.push(name_in_archive.as_bytes().to_vec());
                         strings.push(path);
                         strings.push(name);
                     }
@@ -402,12 +405,19 @@ fn build_with_llvm(&mut self, output: &Path) -> io::Result<bool> {
                                 Some(child.raw),
                             );
                             members.push(m);
+                            member_names.push(child_name.as_bytes().to_vec());
                             strings.push(name);
                         }
                     }
                 }
             }
 
+            if self.sess.opts.unstable_opts.reproducible {
+                let mut named_members: Vec<_> = member_names.into_iter().zip(members).collect();
+                sort_members_by_name(&mut named_members, |(name, _)| &name[..]);
+                members = named_members.into_iter().map(|(_, member)| member).collect();
+            }
+
             let r = llvm::LLVMRustWriteArchive(
                 dst.as_ptr(),
                 members.len() as libc::size_t,
@@ -0,0 +1,38 @@
+use std::ffi::{c_char, c_void};
+use std::path::Path;
+use std::{slice, str};
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_fs_util::path_to_c_string;
+use rustc_session::Session;
+
+use crate::errors::ReadProfile;
+use crate::llvm;
+
+/// Reads the indexed profile passed to `-Z emit-symbol-order` into the sum of the counters of
+/// each function, keyed by the function names of the profile.
+pub fn read_function_counts(sess: &Session, path: &Path) -> FxHashMap<String, u64> {
+    unsafe extern "C" fn callback(state: *mut c_void, name: *const c_char, len: usize, count: u64) {
+        let counts = unsafe { &mut *(state as *mut FxHashMap<String, u64>) };
+        let name = unsafe { slice::from_raw_parts(name as *const u8, len) };
+        if let Ok(name) = str::from_utf8(name) {
+            // A function may have several records, one for each hash of its control flow.
+            *counts.entry(name.to_owned()).or_default() += count;
+        }
+    }
+
+    let mut counts = FxHashMap::default();
+    let path_c = path_to_c_string(path);
+    let ok = unsafe {
+        llvm::LLVMRustReadInstrProfFunctionCounts(
+            path_c.as_ptr(),
+            &mut counts as *mut FxHashMap<String, u64> as *mut c_void,
+            callback,
+        )
+    };
+    if !ok {
+        let llvm_err = llvm::last_error().unwrap_or_else(|| "unknown error".to_owned());
+        sess.emit_fatal(ReadProfile { path, llvm_err });
+    }
+    counts
+}
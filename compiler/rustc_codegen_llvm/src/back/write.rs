@@ -1,3 +1,4 @@
+use crate::back::annotated_asm::annotate_asm;
 use crate::back::lto::ThinBuffer;
 use crate::back::owned_target_machine::OwnedTargetMachine;
 use crate::back::profiling::{
@@ -7,7 +8,7 @@
 use crate::common;
 use crate::errors::{
     CopyBitcode, FromLlvmDiag, FromLlvmOptimizationDiag, LlvmError, UnknownCompression,
-    WithLlvmError, WriteBytecode,
+    WithLlvmError, WriteAnnotatedAsm, WriteBytecode,
 };
 use crate::llvm::{self, DiagnosticInfo, PassManager};
 use crate::llvm_util;
@@ -212,7 +213,8 @@ pub fn target_machine_factory(
     let abi = SmallCStr::new(&sess.target.llvm_abiname);
     let trap_unreachable =
         sess.opts.unstable_opts.trap_unreachable.unwrap_or(sess.target.trap_unreachable);
-    let emit_stack_size_section = sess.opts.unstable_opts.emit_stack_sizes;
+    let emit_stack_size_section =
+        sess.opts.unstable_opts.emit_stack_sizes || sess.opts.unstable_opts.stack_depth_report;
 
     let asm_comments = sess.opts.unstable_opts.asm_comments;
     let relax_elf_relocations =
@@ -783,7 +785,7 @@ extern "C" fn demangle_callback(
                 .map_err(|()| llvm_err(diag_handler, LlvmError::WriteIr { path: &out }))?;
         }
 
-        if config.emit_asm {
+        if config.emit_asm || config.emit_annotated_asm {
             let _timer =
                 cgcx.prof.generic_activity_with_arg("LLVM_module_codegen_emit_asm", &*module.name);
             let path = cgcx.output_filenames.temp_path(OutputType::Assembly, module_name);
@@ -809,6 +811,22 @@ extern "C" fn demangle_callback(
                     &cgcx.prof,
                 )
             })?;
+
+            // The annotated assembly is made from the assembly printed by LLVM, which is only
+            // kept if it was requested as well.
+            if config.emit_annotated_asm {
+                let annotated_path =
+                    cgcx.output_filenames.temp_path(OutputType::AssemblyAnnotated, module_name);
+                let result = fs::read_to_string(&path).and_then(|asm| {
+                    fs::write(&annotated_path, annotate_asm(&asm, config.asm_filter.as_deref()))
+                });
+                if let Err(err) = result {
+                    diag_handler.emit_err(WriteAnnotatedAsm { path: &annotated_path, err });
+                }
+                if !config.emit_asm {
+                    ensure_removed(diag_handler, &path);
+                }
+            }
         }
 
         match config.emit_obj {
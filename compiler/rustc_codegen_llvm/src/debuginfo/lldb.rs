@@ -0,0 +1,108 @@
+// .lldbformatters binary section.
+
+use crate::llvm;
+
+use crate::builder::Builder;
+use crate::common::CodegenCx;
+use crate::value::Value;
+use rustc_codegen_ssa::base::collect_debugger_visualizers_transitive;
+use rustc_codegen_ssa::traits::*;
+use rustc_hir::def_id::LOCAL_CRATE;
+use rustc_middle::{bug, middle::debugger_visualizer::DebuggerVisualizerType};
+use rustc_session::config::{CrateType, DebugInfo};
+
+/// Inserts a side-effect free instruction sequence that makes sure that the
+/// .lldbformatters global is referenced, so it isn't removed by the linker.
+pub fn insert_reference_to_lldb_formatters_section_global(bx: &mut Builder<'_, '_, '_>) {
+    if needs_lldb_formatters_section(bx) {
+        let lldb_formatters_section = get_or_insert_lldb_formatters_section_global(bx);
+        // Load just the first byte as that's all that's necessary to force
+        // LLVM to keep around the reference to the global.
+        let volatile_load_instruction = bx.volatile_load(bx.type_i8(), lldb_formatters_section);
+        unsafe {
+            llvm::LLVMSetAlignment(volatile_load_instruction, 1);
+        }
+    }
+}
+
+/// Allocates the global variable responsible for the .lldbformatters binary
+/// section. It is read by the `rust-lldb` wrapper through
+/// `src/etc/lldb_embedded_formatters.py`.
+pub fn get_or_insert_lldb_formatters_section_global<'ll>(cx: &CodegenCx<'ll, '_>) -> &'ll Value {
+    let c_section_var_name = "__rustc_lldb_formatters_section__\0";
+    let section_var_name = &c_section_var_name[..c_section_var_name.len() - 1];
+
+    let section_var =
+        unsafe { llvm::LLVMGetNamedGlobal(cx.llmod, c_section_var_name.as_ptr().cast()) };
+
+    section_var.unwrap_or_else(|| {
+        let section_name: &[u8] = if cx.sess().target.is_like_osx {
+            b"__TEXT,__lldbformatters\0"
+        } else {
+            b".lldbformatters\0"
+        };
+        let mut section_contents = Vec::new();
+
+        let crate_name = cx.tcx.crate_name(LOCAL_CRATE);
+        for (kind, visualizer_type) in [
+            (b"\x01", DebuggerVisualizerType::LldbPythonScript),
+            (b"\x02", DebuggerVisualizerType::LldbTypeSummary),
+        ] {
+            let visualizers = collect_debugger_visualizers_transitive(cx.tcx, visualizer_type);
+            for (index, visualizer) in visualizers.iter().enumerate() {
+                // The initial byte tells whether the formatter is a Python script (`1`)
+                // or a list of LLDB commands (`2`). It is followed by a name for the
+                // formatter and its source.
+                section_contents.extend_from_slice(kind);
+                let vis_name = format!("lldb-formatter-{crate_name}-{index}\n");
+                section_contents.extend_from_slice(vis_name.as_bytes());
+                section_contents.extend_from_slice(&visualizer.src);
+
+                // The final byte `0` terminates the formatter.
+                section_contents.extend_from_slice(b"\0");
+            }
+        }
+
+        unsafe {
+            let section_contents = section_contents.as_slice();
+            let llvm_type = cx.type_array(cx.type_i8(), section_contents.len() as u64);
+
+            let section_var = cx
+                .define_global(section_var_name, llvm_type)
+                .unwrap_or_else(|| bug!("symbol `{}` is already defined", section_var_name));
+            llvm::LLVMSetSection(section_var, section_name.as_ptr().cast());
+            llvm::LLVMSetInitializer(section_var, cx.const_bytes(section_contents));
+            llvm::LLVMSetGlobalConstant(section_var, llvm::True);
+            llvm::LLVMSetUnnamedAddress(section_var, llvm::UnnamedAddr::Global);
+            llvm::LLVMRustSetLinkage(section_var, llvm::Linkage::LinkOnceODRLinkage);
+            llvm::LLVMSetAlignment(section_var, 1);
+            section_var
+        }
+    })
+}
+
+pub fn needs_lldb_formatters_section(cx: &CodegenCx<'_, '_>) -> bool {
+    // Like the `.debug_gdb_scripts` section, the formatters are only embedded into
+    // leaf crates to avoid ODR violations between rlibs embedding different sets.
+    let embed_visualizers = cx.tcx.crate_types().iter().any(|&crate_type| match crate_type {
+        CrateType::Executable | CrateType::Dylib | CrateType::Cdylib | CrateType::Staticlib => true,
+        CrateType::ProcMacro | CrateType::Rlib => false,
+    });
+
+    // LLDB reads the section from ELF and Mach-O binaries.
+    let target = &cx.sess().target;
+    let supported_target = !target.is_like_windows && !target.is_like_wasm && !target.is_like_aix;
+
+    // Unlike GDB, LLDB has no formatters to load for the standard library from the
+    // section, so it is only emitted when there are formatters to embed.
+    let has_visualizers = || {
+        [DebuggerVisualizerType::LldbPythonScript, DebuggerVisualizerType::LldbTypeSummary]
+            .into_iter()
+            .any(|ty| !collect_debugger_visualizers_transitive(cx.tcx, ty).is_empty())
+    };
+
+    cx.sess().opts.debuginfo != DebugInfo::None
+        && supported_target
+        && embed_visualizers
+        && has_visualizers()
+}
@@ -44,6 +44,7 @@
 
 mod create_scope_map;
 pub mod gdb;
+pub mod lldb;
 pub mod metadata;
 mod namespace;
 mod utils;
@@ -150,6 +151,12 @@ pub fn finalize(cx: &CodegenCx<'_, '_>) {
             gdb::get_or_insert_gdb_debug_scripts_section_global(cx);
         }
 
+        if lldb::needs_lldb_formatters_section(cx) {
+            // Add a .lldbformatters section with the formatters embedded via
+            // `#[debugger_visualizer]`, which the `rust-lldb` wrapper loads.
+            lldb::get_or_insert_lldb_formatters_section_global(cx);
+        }
+
         dbg_cx.finalize(cx.sess());
     }
 }
@@ -217,6 +224,10 @@ fn insert_reference_to_gdb_debug_scripts_section_global(&mut self) {
         gdb::insert_reference_to_gdb_debug_scripts_section_global(self)
     }
 
+    fn insert_reference_to_lldb_formatters_section_global(&mut self) {
+        lldb::insert_reference_to_lldb_formatters_section_global(self)
+    }
+
     fn set_var_name(&mut self, value: &'ll Value, name: &str) {
         // Avoid wasting time if LLVM value names aren't even enabled.
         if self.sess().fewer_names() {
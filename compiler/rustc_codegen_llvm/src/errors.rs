@@ -218,6 +218,20 @@ pub(crate) struct FromLlvmDiag {
     pub message: String,
 }
 
+#[derive(Diagnostic)]
+#[diag(codegen_llvm_read_profile)]
+pub(crate) struct ReadProfile<'a> {
+    pub path: &'a Path,
+    pub llvm_err: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(codegen_llvm_write_annotated_asm)]
+pub(crate) struct WriteAnnotatedAsm<'a> {
+    pub path: &'a Path,
+    pub err: std::io::Error,
+}
+
 #[derive(Diagnostic)]
 #[diag(codegen_llvm_write_bytecode)]
 pub(crate) struct WriteBytecode<'a> {
@@ -55,10 +55,12 @@
 use std::io::Write;
 
 mod back {
+    mod annotated_asm;
     pub mod archive;
     pub mod lto;
     pub mod owned_target_machine;
     mod profiling;
+    pub mod symbol_order;
     pub mod write;
 }
 
@@ -397,6 +399,17 @@ fn link(
     ) -> Result<(), ErrorGuaranteed> {
         use crate::back::archive::LlvmArchiveBuilderBuilder;
         use rustc_codegen_ssa::back::link::link_binary;
+        use rustc_codegen_ssa::back::symbol_order::emit_symbol_order;
+
+        // The ordering file has to exist before the linker is passed it.
+        if let Some(symbol_order) = &codegen_results.crate_info.symbol_order
+            && let Some(profile) = &sess.opts.unstable_opts.emit_symbol_order
+        {
+            sess.time("emit_symbol_order", || {
+                let function_counts = back::symbol_order::read_function_counts(sess, profile);
+                emit_symbol_order(sess, symbol_order, &function_counts)
+            });
+        }
 
         // Run the linker on any artifacts that resulted from the LLVM run.
         // This should produce either a finished executable or library.
@@ -821,6 +821,7 @@ pub struct AllocKindFlags : u64 {
 
 pub type GetSymbolsCallback = unsafe extern "C" fn(*mut c_void, *const c_char) -> *mut c_void;
 pub type GetSymbolsErrorCallback = unsafe extern "C" fn(*const c_char) -> *mut c_void;
+pub type InstrProfFunctionCallback = unsafe extern "C" fn(*mut c_void, *const c_char, usize, u64);
 
 extern "C" {
     pub fn LLVMRustInstallFatalErrorHandler();
@@ -2380,4 +2381,10 @@ pub fn LLVMRustGetSymbols(
         callback: GetSymbolsCallback,
         error_callback: GetSymbolsErrorCallback,
     ) -> *mut c_void;
+
+    pub fn LLVMRustReadInstrProfFunctionCounts(
+        path: *const c_char,
+        state: *mut c_void,
+        callback: InstrProfFunctionCallback,
+    ) -> bool;
 }
@@ -28,6 +28,8 @@ codegen_ssa_expected_coverage_symbol = expected `coverage(off)` or `coverage(on)
 
 codegen_ssa_expected_used_symbol = expected `used`, `used(compiler)` or `used(linker)`
 
+codegen_ssa_export_symbol_versions_ignored = symbol versions in the export list of `-C export-symbols` are ignored on this target
+
 codegen_ssa_extern_funcs_not_found = some `extern` functions couldn't be found; some native libraries may need to be installed or have their path specified
 
 codegen_ssa_extract_bundled_libs_archive_member = failed to get data from archive member '{$rlib}': {$error}
@@ -217,11 +219,18 @@ codegen_ssa_static_library_native_artifacts = Link against the following native
 
 codegen_ssa_static_library_native_artifacts_to_file = Native artifacts to link against have been written to {$path}. The order and any duplication can be significant on some platforms.
 
+codegen_ssa_stack_sizes_read_failure = failed to read stack sizes from `{$path}`: {$error}
+
 codegen_ssa_stripping_debug_info_failed = stripping debug info with `{$util}` failed: {$status}
     .note = {$output}
 
 codegen_ssa_symbol_file_write_failure = failed to write symbols file: {$error}
 
+codegen_ssa_symbol_ordering_file_ignored = the symbol ordering file of `-Z emit-symbol-order` is not passed to the linker, as GNU ld doesn't support it
+    .help = select lld or gold with `-C link-arg=-fuse-ld=lld` or `-C link-arg=-fuse-ld=gold`
+
+codegen_ssa_symbol_sizes_read_failure = failed to read symbol sizes from `{$path}`: {$error}
+
 codegen_ssa_target_feature_safe_trait = `#[target_feature(..)]` cannot be applied to safe trait method
     .label = cannot be applied to safe trait method
     .label_def = not an `unsafe` function
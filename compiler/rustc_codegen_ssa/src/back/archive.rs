@@ -1,5 +1,8 @@
+use rustc_data_structures::fingerprint::Fingerprint;
 use rustc_data_structures::fx::FxHashSet;
 use rustc_data_structures::memmap::Mmap;
+use rustc_data_structures::stable_hasher::StableHasher;
+use rustc_metadata::fs::METADATA_FILENAME;
 use rustc_session::cstore::DllImport;
 use rustc_session::Session;
 use rustc_span::symbol::Symbol;
@@ -13,7 +16,8 @@
 use tempfile::Builder as TempFileBuilder;
 
 use std::error::Error;
-use std::fs::File;
+use std::fs::{self, File};
+use std::hash::Hash;
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 
@@ -239,9 +243,14 @@ fn build_inner(self, output: &Path) -> io::Result<bool> {
             }
         };
 
+        let mut source_entries = self.entries;
+        if self.sess.opts.unstable_opts.reproducible {
+            sort_members_by_name(&mut source_entries, |(name, _)| &name[..]);
+        }
+
         let mut entries = Vec::new();
 
-        for (entry_name, entry) in self.entries {
+        for (entry_name, entry) in source_entries {
             let data =
                 match entry {
                     ArchiveEntry::FromArchive { archive_index, file_range } => {
@@ -305,6 +314,50 @@ fn build_inner(self, output: &Path) -> io::Result<bool> {
     }
 }
 
+/// Sorts archive members by name for `-Z reproducible`, so that the archive doesn't depend on the
+/// order in which they were added, e.g. on which codegen unit finished first.
+///
+/// The metadata member is left in place, since some linkers require it to be the first or the
+/// last member of an rlib (see `link_rlib`).
+pub fn sort_members_by_name<T>(members: &mut Vec<T>, name: impl Fn(&T) -> &[u8]) {
+    let metadata_pos = members.iter().position(|m| name(m) == METADATA_FILENAME.as_bytes());
+    let metadata = metadata_pos.map(|pos| (pos == 0, members.remove(pos)));
+
+    // Use a stable sort, members of native libraries can have duplicate names.
+    members.sort_by(|a, b| name(a).cmp(name(b)));
+
+    match metadata {
+        Some((true, member)) => members.insert(0, member),
+        Some((false, member)) => members.push(member),
+        None => {}
+    }
+}
+
+/// Name of the rlib member holding the content build-id written with `-Z reproducible`.
+///
+/// Unlike executables and shared libraries, rlibs aren't produced by the linker, so they don't get
+/// a build-id note from `--build-id=sha1`. Instead, `link_rlib` adds this member, which is an
+/// object file for the target with a single `.rbuildid` section that linkers discard.
+pub const BUILD_ID_FILENAME: &str = "lib.rbuildid";
+
+/// Computes the build-id of an rlib from the names and contents of the files it is built from.
+///
+/// The inputs are hashed in the order `sort_members_by_name` puts them in, so that the build-id
+/// doesn't depend on the order in which codegen units finished.
+pub fn content_build_id(inputs: &[&Path]) -> io::Result<String> {
+    let mut inputs = inputs.to_vec();
+    inputs.sort_by_key(|path| path.file_name());
+
+    let mut hasher = StableHasher::new();
+    for path in inputs {
+        path.file_name().map(|name| name.as_encoded_bytes()).hash(&mut hasher);
+        fs::read(path)
+            .map_err(|err| io_error_context("failed to read archive member", err))?
+            .hash(&mut hasher);
+    }
+    Ok(hasher.finish::<Fingerprint>().to_hex())
+}
+
 fn io_error_context(context: &str, err: io::Error) -> io::Error {
     io::Error::new(io::ErrorKind::Other, format!("{context}: {err}"))
 }
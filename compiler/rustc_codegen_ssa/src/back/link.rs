@@ -28,11 +28,13 @@
 use rustc_target::spec::{Cc, LinkOutputKind, LinkerFlavor, Lld, PanicStrategy};
 use rustc_target::spec::{RelocModel, RelroLevel, SanitizerSet, SplitDebuginfo};
 
-use super::archive::{ArchiveBuilder, ArchiveBuilderBuilder};
+use super::archive::{content_build_id, ArchiveBuilder, ArchiveBuilderBuilder, BUILD_ID_FILENAME};
 use super::command::Command;
 use super::linker::{self, Linker};
 use super::metadata::{create_wrapper_file, MetadataPosition};
 use super::rpath::{self, RPathConfig};
+use super::size_report::emit_size_report;
+use super::stack_depth::emit_stack_depth_report;
 use crate::{
     errors, looks_like_rust_object_file, CodegenResults, CompiledModule, CrateInfo, NativeLib,
 };
@@ -71,6 +73,18 @@ pub fn link_binary<'a>(
     let _timer = sess.timer("link_binary");
     let output_metadata = sess.opts.output_types.contains_key(&OutputType::Metadata);
     let mut tempfiles_for_stdout_output: Vec<PathBuf> = Vec::new();
+
+    // The reports are built from the object files, so they have to be emitted before those are
+    // linked and removed.
+    if let Some(call_graph) = &codegen_results.crate_info.stack_depth_call_graph {
+        sess.time("emit_stack_depth_report", || {
+            emit_stack_depth_report(sess, codegen_results, call_graph, outputs)
+        });
+    }
+    if let Some(items) = &codegen_results.crate_info.size_report_items {
+        sess.time("emit_size_report", || emit_size_report(sess, codegen_results, items, outputs));
+    }
+
     for &crate_type in &codegen_results.crate_info.crate_types {
         // Ignore executable crates if we have -Z no-codegen, as they will error.
         if (sess.opts.unstable_opts.no_codegen || !sess.opts.output_types.should_codegen())
@@ -305,11 +319,15 @@ fn link_rlib<'a>(
 
     let mut ab = archive_builder_builder.new_archive_builder(sess);
 
+    // Everything that ends up in the rlib, used to compute its build-id with `-Z reproducible`.
+    let mut build_id_inputs = Vec::new();
+
     let trailing_metadata = match flavor {
         RlibFlavor::Normal => {
             let (metadata, metadata_position) =
                 create_wrapper_file(sess, b".rmeta".to_vec(), codegen_results.metadata.raw_data());
             let metadata = emit_wrapper_file(sess, &metadata, tmpdir, METADATA_FILENAME);
+            build_id_inputs.push(metadata.clone());
             match metadata_position {
                 MetadataPosition::First => {
                     // Most of the time metadata in rlib files is wrapped in a "dummy" object
@@ -330,10 +348,12 @@ fn link_rlib<'a>(
     for m in &codegen_results.modules {
         if let Some(obj) = m.object.as_ref() {
             ab.add_file(obj);
+            build_id_inputs.push(obj.clone());
         }
 
         if let Some(dwarf_obj) = m.dwarf_object.as_ref() {
             ab.add_file(dwarf_obj);
+            build_id_inputs.push(dwarf_obj.clone());
         }
     }
 
@@ -378,6 +398,7 @@ fn link_rlib<'a>(
                 read(path).map_err(|e| sess.emit_fatal(errors::ReadFileError { message: e }))?;
             let (data, _) = create_wrapper_file(sess, b".bundled_lib".to_vec(), &src);
             let wrapper_file = emit_wrapper_file(sess, &data, tmpdir, filename.as_str());
+            build_id_inputs.push(wrapper_file.clone());
             packed_bundled_libs.push(wrapper_file);
         } else {
             let path = find_native_static_library(
@@ -387,8 +408,9 @@ fn link_rlib<'a>(
                 sess,
             );
             ab.add_archive(&path, Box::new(|_| false)).unwrap_or_else(|error| {
-                sess.emit_fatal(errors::AddNativeLibrary { library_path: path, error })
+                sess.emit_fatal(errors::AddNativeLibrary { library_path: path.clone(), error })
             });
+            build_id_inputs.push(path);
         }
     }
 
@@ -404,8 +426,9 @@ fn link_rlib<'a>(
         );
 
         ab.add_archive(&output_path, Box::new(|_| false)).unwrap_or_else(|error| {
-            sess.emit_fatal(errors::AddNativeLibrary { library_path: output_path, error });
+            sess.emit_fatal(errors::AddNativeLibrary { library_path: output_path.clone(), error });
         });
+        build_id_inputs.push(output_path);
     }
 
     if let Some(trailing_metadata) = trailing_metadata {
@@ -443,6 +466,16 @@ fn link_rlib<'a>(
         ab.add_file(&lib)
     }
 
+    // The linker gives executables and shared libraries a build-id derived from their contents
+    // with `-Z reproducible` (see `add_order_independent_options`), do the same for rlibs.
+    if flavor == RlibFlavor::Normal && sess.opts.unstable_opts.reproducible {
+        let inputs: Vec<&Path> = build_id_inputs.iter().map(|path| path.as_path()).collect();
+        let build_id = content_build_id(&inputs)
+            .map_err(|e| sess.emit_fatal(errors::ReadFileError { message: e }))?;
+        let (data, _) = create_wrapper_file(sess, b".rbuildid".to_vec(), build_id.as_bytes());
+        ab.add_file(&emit_wrapper_file(sess, &data, tmpdir, BUILD_ID_FILENAME));
+    }
+
     return Ok(ab);
 }
 
@@ -531,8 +564,8 @@ fn link_staticlib<'a>(
             ab.add_archive(
                 path,
                 Box::new(move |fname: &str| {
-                    // Ignore metadata files, no matter the name.
-                    if fname == METADATA_FILENAME {
+                    // Ignore metadata and build-id files, no matter the name.
+                    if fname == METADATA_FILENAME || fname == BUILD_ID_FILENAME {
                         return true;
                     }
 
@@ -2296,6 +2329,10 @@ fn add_order_independent_options(
     // Make the binary compatible with data execution prevention schemes.
     cmd.add_no_exec();
 
+    if sess.opts.unstable_opts.reproducible {
+        cmd.add_build_id();
+    }
+
     if self_contained_components.is_crt_objects_enabled() {
         cmd.no_crt_objects();
     }
@@ -2327,6 +2364,14 @@ fn add_order_independent_options(
 
     cmd.linker_plugin_lto();
 
+    // The ordering file is written by the codegen backend, if it can read the profile. A file from
+    // an earlier compilation is removed before writing the new one.
+    if let Some(symbol_order) = &codegen_results.crate_info.symbol_order
+        && symbol_order.path.exists()
+    {
+        cmd.symbol_ordering_file(&symbol_order.path);
+    }
+
     add_library_search_dirs(cmd, sess, self_contained_components.are_any_components_enabled());
 
     cmd.output_filename(out_filename);
@@ -2794,7 +2839,7 @@ fn add_static_crate<'a>(
         if let Err(error) = archive.add_archive(
             cratepath,
             Box::new(move |f| {
-                if f == METADATA_FILENAME {
+                if f == METADATA_FILENAME || f == BUILD_ID_FILENAME {
                     return true;
                 }
 
@@ -10,6 +10,7 @@
 use std::path::{Path, PathBuf};
 use std::{env, mem, str};
 
+use rustc_data_structures::fx::FxIndexMap;
 use rustc_hir::def_id::{CrateNum, LOCAL_CRATE};
 use rustc_metadata::find_native_static_library;
 use rustc_middle::middle::dependency_format::Linkage;
@@ -148,6 +149,8 @@ pub fn get_linker<'a>(
             hinted_static: None,
             is_ld: cc == Cc::No,
             is_gnu: flavor.is_gnu(),
+            uses_lld: flavor.uses_lld(),
+            flavor,
         }) as Box<dyn Linker>,
         LinkerFlavor::Msvc(..) => Box::new(MsvcLinker { cmd, sess }) as Box<dyn Linker>,
         LinkerFlavor::EmCc => Box::new(EmLinker { cmd, sess }) as Box<dyn Linker>,
@@ -194,6 +197,8 @@ pub trait Linker {
     fn add_eh_frame_header(&mut self) {}
     fn add_no_exec(&mut self) {}
     fn add_as_needed(&mut self) {}
+    fn add_build_id(&mut self) {}
+    fn symbol_ordering_file(&mut self, _path: &Path) {}
     fn reset_per_library_state(&mut self) {}
 }
 
@@ -219,6 +224,8 @@ pub struct GccLinker<'a> {
     // Link as ld
     is_ld: bool,
     is_gnu: bool,
+    uses_lld: bool,
+    flavor: LinkerFlavor,
 }
 
 impl<'a> GccLinker<'a> {
@@ -672,6 +679,17 @@ fn export_symbols(&mut self, tmpdir: &Path, crate_type: CrateType, symbols: &[St
         let is_windows = self.sess.target.is_like_windows;
         let path = tmpdir.join(if is_windows { "list.def" } else { "list" });
 
+        // Symbol versions only exist in version scripts.
+        let versioned_exports = self
+            .sess
+            .opts
+            .export_symbols
+            .as_ref()
+            .filter(|list| crate_type == CrateType::Cdylib && list.has_versions());
+        if versioned_exports.is_some() && (self.sess.target.is_like_osx || is_windows) {
+            self.sess.emit_warning(errors::ExportSymbolVersionsIgnored);
+        }
+
         debug!("EXPORTED SYMBOLS:");
 
         if self.sess.target.is_like_osx {
@@ -701,6 +719,32 @@ fn export_symbols(&mut self, tmpdir: &Path, crate_type: CrateType, symbols: &[St
             if let Err(error) = res {
                 self.sess.emit_fatal(errors::LibDefWriteFailure { error });
             }
+        } else if let Some(list) = versioned_exports {
+            // Write an LD version script with a node for each version. Every symbol must belong
+            // to a named node, and the first one makes the symbols that aren't exported local.
+            let mut nodes: FxIndexMap<&str, Vec<&str>> = FxIndexMap::default();
+            for sym in symbols {
+                let version = list.version(sym).expect("exported symbol without a version");
+                nodes.entry(version).or_default().push(sym);
+            }
+            let res: io::Result<()> = try {
+                let mut f = BufWriter::new(File::create(&path)?);
+                for (i, (version, syms)) in nodes.iter().enumerate() {
+                    writeln!(f, "{version} {{")?;
+                    writeln!(f, "  global:")?;
+                    for sym in syms {
+                        debug!("    {sym}@{version};");
+                        writeln!(f, "    {sym};")?;
+                    }
+                    if i == 0 {
+                        writeln!(f, "\n  local:\n    *;")?;
+                    }
+                    writeln!(f, "}};")?;
+                }
+            };
+            if let Err(error) = res {
+                self.sess.emit_fatal(errors::VersionScriptWriteFailure { error });
+            }
         } else {
             // Write an LD version script
             let res: io::Result<()> = try {
@@ -782,6 +826,39 @@ fn add_as_needed(&mut self) {
             self.linker_args(&["-z", "ignore"]);
         }
     }
+
+    // A build-id derived from the output contents, rather than a random or time-based one.
+    fn add_build_id(&mut self) {
+        if self.is_gnu && !self.sess.target.is_like_windows {
+            self.linker_arg("--build-id=sha1");
+        }
+    }
+
+    fn symbol_ordering_file(&mut self, path: &Path) {
+        if self.sess.target.is_like_osx {
+            self.linker_args(&[OsString::from("-order_file"), path.into()]);
+        } else if self.is_gnu && !self.sess.target.is_like_windows {
+            // Supported by lld and gold, but GNU ld fails on the unknown option. Either of them
+            // can be selected by the linker flavor, or by `-fuse-ld` in the link args of the
+            // target or of the command line.
+            let sess = self.sess;
+            let target_uses_lld =
+                sess.opts.cg.linker_flavor.is_none() && sess.target.linker_flavor.uses_lld();
+            let target_args = sess.target.pre_link_args.get(&self.flavor).into_iter().flatten();
+            let selects_lld_or_gold = target_args
+                .map(|arg| &**arg)
+                .chain(sess.opts.cg.link_args.iter().map(String::as_str))
+                .chain(sess.opts.unstable_opts.pre_link_args.iter().map(String::as_str))
+                .any(|arg| matches!(arg, "-fuse-ld=lld" | "-fuse-ld=gold"));
+            if self.uses_lld || target_uses_lld || selects_lld_or_gold {
+                let mut arg = OsString::from("--symbol-ordering-file=");
+                arg.push(path);
+                self.linker_arg(arg);
+            } else {
+                sess.emit_warning(errors::SymbolOrderingFileIgnored);
+            }
+        }
+    }
 }
 
 pub struct MsvcLinker<'a> {
@@ -976,6 +1053,13 @@ fn export_symbols(&mut self, tmpdir: &Path, crate_type: CrateType, symbols: &[St
             }
         }
 
+        // Symbol versions only exist in version scripts.
+        if crate_type == CrateType::Cdylib
+            && self.sess.opts.export_symbols.as_ref().is_some_and(|list| list.has_versions())
+        {
+            self.sess.emit_warning(errors::ExportSymbolVersionsIgnored);
+        }
+
         let path = tmpdir.join("lib.def");
         let res: io::Result<()> = try {
             let mut f = BufWriter::new(File::create(&path)?);
@@ -1028,6 +1112,17 @@ fn linker_plugin_lto(&mut self) {
     fn add_no_exec(&mut self) {
         self.cmd.arg("/NXCOMPAT");
     }
+
+    // Replaces the PE timestamp with a hash of the output contents.
+    fn add_build_id(&mut self) {
+        self.cmd.arg("/Brepro");
+    }
+
+    fn symbol_ordering_file(&mut self, path: &Path) {
+        let mut arg = OsString::from("/ORDER:@");
+        arg.push(path);
+        self.cmd.arg(arg);
+    }
 }
 
 pub struct EmLinker<'a> {
@@ -1687,10 +1782,15 @@ pub(crate) fn exported_symbols(tcx: TyCtxt<'_>, crate_type: CrateType) -> Vec<St
         return exports.iter().map(ToString::to_string).collect();
     }
 
-    if let CrateType::ProcMacro = crate_type {
+    let symbols = if let CrateType::ProcMacro = crate_type {
         exported_symbols_for_proc_macro_crate(tcx)
     } else {
         exported_symbols_for_non_proc_macro(tcx, crate_type)
+    };
+
+    match &tcx.sess.opts.export_symbols {
+        Some(list) if crate_type == CrateType::Cdylib => list.exports(symbols),
+        _ => symbols,
     }
 }
 
@@ -5,5 +5,8 @@
 pub mod lto;
 pub mod metadata;
 pub mod rpath;
+pub mod size_report;
+pub mod stack_depth;
 pub mod symbol_export;
+pub mod symbol_order;
 pub mod write;
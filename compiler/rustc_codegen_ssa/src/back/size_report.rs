@@ -0,0 +1,248 @@
+//! The binary size attribution report of `-Z size-report`.
+//!
+//! The size of every symbol defined in the object files of the crate is joined with the mono
+//! item it was generated for, which gives the crate defining the function or static, its generic
+//! arguments and the codegen unit it ended up in. The sizes are then rolled up by defining crate
+//! and by generic function to show which crates and monomorphizations make the output large.
+//!
+//! Only the objects produced for the current crate are covered, which is the `crate_name` of the
+//! report, so every item was instantiated by it. Combining the reports of all crates of a build
+//! attributes the size of the whole program.
+
+use std::cmp;
+use std::fs;
+use std::io::BufWriter;
+use std::path::Path;
+
+use object::{Object, ObjectSection, ObjectSymbol, SymbolKind};
+use rustc_data_structures::fx::{FxHashMap, FxIndexMap};
+use rustc_middle::mir::mono::MonoItem;
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::{GenericArgKind, Instance, TyCtxt};
+use rustc_session::config::OutputFilenames;
+use rustc_session::Session;
+use serde::Serialize;
+
+use crate::{errors, CodegenResults};
+
+/// A mono item of the local crate, as described in the size report.
+#[derive(Clone, Debug, Encodable, Decodable)]
+pub struct SizeReportItem {
+    pub symbol_name: String,
+    /// The instance, including its generic arguments.
+    pub instance: String,
+    /// The path of the function or static the item was instantiated from.
+    pub def_path: String,
+    pub defining_crate: String,
+    pub generic_args: Vec<String>,
+    pub cgu: String,
+}
+
+pub fn size_report_items(tcx: TyCtxt<'_>) -> Vec<SizeReportItem> {
+    let (_, codegen_units) = tcx.collect_and_partition_mono_items(());
+
+    let mut items = Vec::new();
+    for cgu in codegen_units {
+        for &mono_item in cgu.items().keys() {
+            let instance = match mono_item {
+                MonoItem::Fn(instance) => instance,
+                MonoItem::Static(def_id) => Instance::mono(tcx, def_id),
+                MonoItem::GlobalAsm(_) => continue,
+            };
+            let def_id = instance.def_id();
+            let generic_args = instance
+                .args
+                .iter()
+                .filter(|arg| !matches!(arg.unpack(), GenericArgKind::Lifetime(_)))
+                .map(|arg| with_no_trimmed_paths!(arg.to_string()))
+                .collect();
+
+            items.push(SizeReportItem {
+                symbol_name: mono_item.symbol_name(tcx).name.to_string(),
+                instance: with_no_trimmed_paths!(instance.to_string()),
+                def_path: with_no_trimmed_paths!(tcx.def_path_str(def_id)),
+                defining_crate: tcx.crate_name(def_id.krate).to_string(),
+                generic_args,
+                cgu: cgu.name().to_string(),
+            });
+        }
+    }
+    items.sort_by(|a, b| a.symbol_name.cmp(&b.symbol_name));
+    items
+}
+
+#[derive(Serialize)]
+struct SizeReport<'a> {
+    crate_name: &'a str,
+    total_size: u64,
+    by_crate: Vec<CrateSize<'a>>,
+    by_generic_function: Vec<GenericFunctionSize<'a>>,
+    symbols: Vec<SymbolSize<'a>>,
+    /// Symbols that don't belong to a mono item, like allocator shims, vtables and constants.
+    unattributed: Vec<UnattributedSymbol<'a>>,
+}
+
+#[derive(Serialize)]
+struct CrateSize<'a> {
+    #[serde(rename = "crate")]
+    crate_name: &'a str,
+    size: u64,
+    symbols: usize,
+}
+
+#[derive(Serialize)]
+struct GenericFunctionSize<'a> {
+    function: &'a str,
+    defining_crate: &'a str,
+    size: u64,
+    instances: usize,
+}
+
+#[derive(Serialize)]
+struct SymbolSize<'a> {
+    symbol: &'a str,
+    size: u64,
+    instance: &'a str,
+    function: &'a str,
+    defining_crate: &'a str,
+    generic_args: &'a [String],
+    cgu: &'a str,
+}
+
+#[derive(Serialize)]
+struct UnattributedSymbol<'a> {
+    symbol: &'a str,
+    size: u64,
+}
+
+pub fn emit_size_report(
+    sess: &Session,
+    codegen_results: &CodegenResults,
+    items: &[SizeReportItem],
+    outputs: &OutputFilenames,
+) {
+    let mut symbol_sizes = FxIndexMap::default();
+    for object in codegen_results.modules.iter().filter_map(|m| m.object.as_ref()) {
+        if let Err(error) = read_symbol_sizes(sess, object, &mut symbol_sizes) {
+            sess.emit_err(errors::SymbolSizesReadFailure { path: object.clone(), error });
+            return;
+        }
+    }
+
+    let crate_name = codegen_results.crate_info.local_crate_name.as_str();
+    let items_by_symbol: FxHashMap<_, _> =
+        items.iter().map(|item| (item.symbol_name.as_str(), item)).collect();
+
+    let mut symbols = Vec::new();
+    let mut unattributed = Vec::new();
+    let mut by_crate: FxIndexMap<&str, CrateSize<'_>> = FxIndexMap::default();
+    let mut by_generic_function: FxIndexMap<(&str, &str), GenericFunctionSize<'_>> =
+        FxIndexMap::default();
+    for (symbol, &size) in &symbol_sizes {
+        let Some(item) = items_by_symbol.get(symbol.as_str()) else {
+            unattributed.push(UnattributedSymbol { symbol, size });
+            continue;
+        };
+
+        let crate_size = by_crate.entry(&item.defining_crate).or_insert(CrateSize {
+            crate_name: &item.defining_crate,
+            size: 0,
+            symbols: 0,
+        });
+        crate_size.size += size;
+        crate_size.symbols += 1;
+
+        if !item.generic_args.is_empty() {
+            let function_size = by_generic_function
+                .entry((item.defining_crate.as_str(), item.def_path.as_str()))
+                .or_insert(GenericFunctionSize {
+                    function: &item.def_path,
+                    defining_crate: &item.defining_crate,
+                    size: 0,
+                    instances: 0,
+                });
+            function_size.size += size;
+            function_size.instances += 1;
+        }
+
+        symbols.push(SymbolSize {
+            symbol,
+            size,
+            instance: &item.instance,
+            function: &item.def_path,
+            defining_crate: &item.defining_crate,
+            generic_args: &item.generic_args,
+            cgu: &item.cgu,
+        });
+    }
+
+    let mut by_crate: Vec<_> = by_crate.into_values().collect();
+    by_crate.sort_by_key(|c| (cmp::Reverse(c.size), c.crate_name));
+    let mut by_generic_function: Vec<_> = by_generic_function.into_values().collect();
+    by_generic_function.sort_by_key(|f| (cmp::Reverse(f.size), f.function));
+    symbols.sort_by_key(|s| (cmp::Reverse(s.size), s.symbol));
+    unattributed.sort_by_key(|s| (cmp::Reverse(s.size), s.symbol));
+
+    let report = SizeReport {
+        crate_name,
+        total_size: symbol_sizes.values().sum(),
+        by_crate,
+        by_generic_function,
+        symbols,
+        unattributed,
+    };
+
+    let path = outputs.with_extension("size-report.json");
+    let result = fs::File::create(&path).and_then(|file| {
+        serde_json::to_writer_pretty(BufWriter::new(file), &report).map_err(Into::into)
+    });
+    if let Err(error) = result {
+        sess.emit_err(errors::FailedToWrite { path, error });
+    }
+}
+
+/// Adds the size of every function and data symbol defined in an object file to
+/// `symbol_sizes`. Symbols defined in several objects, like inlined functions, are counted once
+/// per object as every copy takes up space in the output.
+fn read_symbol_sizes(
+    sess: &Session,
+    path: &Path,
+    symbol_sizes: &mut FxIndexMap<String, u64>,
+) -> Result<(), String> {
+    let data = fs::read(path).map_err(|error| error.to_string())?;
+    let file = object::File::parse(&*data).map_err(|error| error.to_string())?;
+
+    let mut symbols_by_section: FxIndexMap<_, Vec<_>> = FxIndexMap::default();
+    for symbol in file.symbols() {
+        if symbol.is_undefined() || !matches!(symbol.kind(), SymbolKind::Text | SymbolKind::Data) {
+            continue;
+        }
+        let (Some(section), Ok(name)) = (symbol.section_index(), symbol.name()) else { continue };
+        symbols_by_section.entry(section).or_default().push((
+            symbol.address(),
+            symbol.size(),
+            name,
+        ));
+    }
+
+    for (section_index, mut symbols) in symbols_by_section {
+        let section = file.section_by_index(section_index).map_err(|error| error.to_string())?;
+        let section_end = section.address() + section.size();
+        symbols.sort_by_key(|&(address, _, _)| address);
+
+        for (i, &(address, size, name)) in symbols.iter().enumerate() {
+            // Not all object formats record symbol sizes. Assume that a symbol extends up to the
+            // next symbol in the same section in that case.
+            let size = if size != 0 {
+                size
+            } else {
+                symbols.get(i + 1).map_or(section_end, |&(next, _, _)| next) - address
+            };
+            let name =
+                if sess.target.is_like_osx { name.strip_prefix('_').unwrap_or(name) } else { name };
+            *symbol_sizes.entry(name.to_owned()).or_default() += size;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,303 @@
+//! The worst-case stack depth report of `-Z stack-depth-report`.
+//!
+//! The per-function stack sizes are read from the `.stack_sizes` sections LLVM emits into the
+//! object files and combined with the call graph built from the mono items of the crate (see
+//! `rustc_monomorphize::call_graph`). For every entry point the deepest path through the call
+//! graph is reported. Recursion and calls through `dyn Trait` vtables or function pointers make
+//! the depth unbounded, in which case the offending path is reported instead.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use object::{Object, ObjectSection, ObjectSymbol, RelocationTarget, SymbolKind};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::stack::ensure_sufficient_stack;
+use rustc_middle::mir::mono::{MonoCallGraph, MonoCallee, StackDepthEntryKind};
+use rustc_session::config::OutputFilenames;
+use rustc_session::Session;
+
+use crate::{errors, CodegenResults};
+
+pub fn emit_stack_depth_report(
+    sess: &Session,
+    codegen_results: &CodegenResults,
+    call_graph: &MonoCallGraph,
+    outputs: &OutputFilenames,
+) {
+    let mut stack_sizes = FxHashMap::default();
+    for object in codegen_results.modules.iter().filter_map(|m| m.object.as_ref()) {
+        if let Err(error) = read_stack_sizes(object, &mut stack_sizes) {
+            sess.emit_err(errors::StackSizesReadFailure { path: object.clone(), error });
+            return;
+        }
+    }
+
+    let report = StackDepthAnalysis::new(call_graph, &stack_sizes)
+        .report(codegen_results.crate_info.local_crate_name.as_str());
+
+    let path = outputs.with_extension("stack-depth");
+    if let Err(error) = fs::write(&path, report) {
+        sess.emit_err(errors::FailedToWrite { path, error });
+    }
+}
+
+/// Reads the `.stack_sizes` sections of an object file into `stack_sizes`, keyed by symbol
+/// name. Each entry of such a section is the address of a function, which is relocated against
+/// the function or the section containing it, followed by its stack size as ULEB128.
+fn read_stack_sizes(path: &Path, stack_sizes: &mut FxHashMap<String, u64>) -> Result<(), String> {
+    let data = fs::read(path).map_err(|error| error.to_string())?;
+    let file = object::File::parse(&*data).map_err(|error| error.to_string())?;
+
+    let mut functions = FxHashMap::default();
+    for symbol in file.symbols() {
+        if let (SymbolKind::Text, Some(section), Ok(name)) =
+            (symbol.kind(), symbol.section_index(), symbol.name())
+        {
+            functions.insert((section, symbol.address()), name);
+        }
+    }
+
+    let address_size = if file.is_64() { 8 } else { 4 };
+    for section in file.sections() {
+        if section.name().map_or(true, |name| name != ".stack_sizes") {
+            continue;
+        }
+        let data = section.data().map_err(|error| error.to_string())?;
+
+        for (offset, relocation) in section.relocations() {
+            let RelocationTarget::Symbol(symbol_index) = relocation.target() else { continue };
+            let symbol = file.symbol_by_index(symbol_index).map_err(|error| error.to_string())?;
+            let offset = offset as usize;
+
+            let Some(address) = data.get(offset..offset + address_size) else { continue };
+            let mut addend = relocation.addend();
+            if relocation.has_implicit_addend() {
+                addend += read_address(address, file.is_little_endian());
+            }
+
+            let name = match symbol.kind() {
+                SymbolKind::Section => symbol
+                    .section_index()
+                    .and_then(|section| functions.get(&(section, addend as u64)).copied()),
+                _ => symbol.name().ok(),
+            };
+            let (Some(name), Some(size)) = (name, read_uleb128(&data[offset + address_size..]))
+            else {
+                continue;
+            };
+
+            // Functions instantiated in several codegen units may have different frame sizes.
+            let max_size = stack_sizes.entry(name.to_owned()).or_default();
+            *max_size = (*max_size).max(size);
+        }
+    }
+
+    Ok(())
+}
+
+fn read_address(bytes: &[u8], little_endian: bool) -> i64 {
+    let mut buf = [0; 8];
+    if little_endian {
+        buf[..bytes.len()].copy_from_slice(bytes);
+        i64::from_le_bytes(buf)
+    } else {
+        buf[8 - bytes.len()..].copy_from_slice(bytes);
+        i64::from_be_bytes(buf)
+    }
+}
+
+fn read_uleb128(bytes: &[u8]) -> Option<u64> {
+    let mut result = 0;
+    for (i, &byte) in bytes.iter().take(10).enumerate() {
+        result |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some(result);
+        }
+    }
+    None
+}
+
+#[derive(Clone, Debug)]
+enum Unbounded {
+    Recursion,
+    Virtual(String),
+    FnPtr(String),
+}
+
+/// The worst-case stack depth of calling a function.
+#[derive(Clone, Debug)]
+struct Depth {
+    bytes: u64,
+    /// The deepest path starting at the function, or the path to the reason the depth is
+    /// unbounded.
+    path: Vec<usize>,
+    unbounded: Option<Unbounded>,
+    /// Whether a function defined in another crate is reachable, whose stack usage is unknown.
+    calls_external: bool,
+}
+
+#[derive(Clone)]
+enum State {
+    Unvisited,
+    InProgress,
+    Done(Depth),
+}
+
+struct StackDepthAnalysis<'a> {
+    call_graph: &'a MonoCallGraph,
+    stack_sizes: &'a FxHashMap<String, u64>,
+    indices: FxHashMap<&'a str, usize>,
+    states: Vec<State>,
+}
+
+impl<'a> StackDepthAnalysis<'a> {
+    fn new(call_graph: &'a MonoCallGraph, stack_sizes: &'a FxHashMap<String, u64>) -> Self {
+        let indices = call_graph
+            .functions
+            .iter()
+            .enumerate()
+            .map(|(i, function)| (function.symbol_name.as_str(), i))
+            .collect();
+        let states = vec![State::Unvisited; call_graph.functions.len()];
+        StackDepthAnalysis { call_graph, stack_sizes, indices, states }
+    }
+
+    fn frame_size(&self, function: usize) -> Option<u64> {
+        self.stack_sizes.get(&self.call_graph.functions[function].symbol_name).copied()
+    }
+
+    fn depth(&mut self, function: usize) -> Depth {
+        match &self.states[function] {
+            State::Done(depth) => return depth.clone(),
+            State::InProgress => {
+                return Depth {
+                    bytes: 0,
+                    path: vec![function],
+                    unbounded: Some(Unbounded::Recursion),
+                    calls_external: false,
+                };
+            }
+            State::Unvisited => {}
+        }
+        self.states[function] = State::InProgress;
+
+        let call_graph = self.call_graph;
+        let mut deepest: Option<Depth> = None;
+        let mut calls_external = false;
+        for callee in &call_graph.functions[function].callees {
+            let unbounded = |reason| Depth {
+                bytes: 0,
+                path: vec![],
+                unbounded: Some(reason),
+                calls_external: false,
+            };
+            let depth = match callee {
+                MonoCallee::Direct(symbol_name) => match self.indices.get(symbol_name.as_str()) {
+                    Some(&callee) => ensure_sufficient_stack(|| self.depth(callee)),
+                    None => {
+                        calls_external = true;
+                        continue;
+                    }
+                },
+                MonoCallee::Virtual(method) => unbounded(Unbounded::Virtual(method.clone())),
+                MonoCallee::FnPtr(fn_ty) => unbounded(Unbounded::FnPtr(fn_ty.clone())),
+            };
+            calls_external |= depth.calls_external;
+
+            // The first unbounded path found is the one that gets reported.
+            let is_deeper = match &deepest {
+                None => true,
+                Some(deepest) => {
+                    deepest.unbounded.is_none()
+                        && (depth.unbounded.is_some() || depth.bytes > deepest.bytes)
+                }
+            };
+            if is_deeper {
+                deepest = Some(depth);
+            }
+        }
+
+        let mut depth = deepest.unwrap_or(Depth {
+            bytes: 0,
+            path: vec![],
+            unbounded: None,
+            calls_external: false,
+        });
+        // Functions without a recorded stack size have been inlined into all of their callers
+        // or don't need a stack frame at all.
+        depth.bytes += self.frame_size(function).unwrap_or(0);
+        depth.path.insert(0, function);
+        depth.calls_external = calls_external;
+
+        self.states[function] = State::Done(depth.clone());
+        depth
+    }
+
+    fn report(mut self, crate_name: &str) -> String {
+        let call_graph = self.call_graph;
+        let mut entries: Vec<_> = call_graph
+            .functions
+            .iter()
+            .enumerate()
+            .filter_map(|(i, function)| Some((function.entry_kind?, i)))
+            .collect();
+        entries.sort_by_key(|&(kind, i)| (kind as u8, &call_graph.functions[i].name));
+
+        let mut report = format!("worst-case stack depth of the entry points of `{crate_name}`\n");
+        if entries.is_empty() {
+            report.push_str("\nno entry points found\n");
+        }
+
+        for (kind, entry) in entries {
+            let depth = self.depth(entry);
+            let kind = match kind {
+                StackDepthEntryKind::Main => "main function",
+                StackDepthEntryKind::Interrupt => "interrupt handler",
+                StackDepthEntryKind::Export => "exported function",
+            };
+            let name = &call_graph.functions[entry].name;
+            let _ = match &depth.unbounded {
+                None => writeln!(report, "\n{kind} `{name}`: {} bytes", depth.bytes),
+                Some(Unbounded::Recursion) => {
+                    writeln!(report, "\n{kind} `{name}`: unbounded (recursion)")
+                }
+                Some(Unbounded::Virtual(_)) => {
+                    writeln!(report, "\n{kind} `{name}`: unbounded (call through `dyn` vtable)")
+                }
+                Some(Unbounded::FnPtr(_)) => {
+                    writeln!(report, "\n{kind} `{name}`: unbounded (call through function pointer)")
+                }
+            };
+
+            let last = depth.path.len() - 1;
+            for (i, &function) in depth.path.iter().enumerate() {
+                let name = &call_graph.functions[function].name;
+                let _ = match self.frame_size(function) {
+                    _ if i == last && matches!(depth.unbounded, Some(Unbounded::Recursion)) => {
+                        writeln!(report, "    {name} (recursive call)")
+                    }
+                    Some(size) => writeln!(report, "    {name} ({size} bytes)"),
+                    None => writeln!(report, "    {name} (no stack size recorded)"),
+                };
+            }
+            let _ = match &depth.unbounded {
+                Some(Unbounded::Virtual(method)) => {
+                    writeln!(report, "    calls `{method}` through a `dyn` vtable")
+                }
+                Some(Unbounded::FnPtr(fn_ty)) => {
+                    writeln!(report, "    calls a function pointer of type `{fn_ty}`")
+                }
+                Some(Unbounded::Recursion) | None => Ok(()),
+            };
+            if depth.calls_external {
+                report.push_str(
+                    "    note: calls functions defined in other crates, \
+                     whose stack usage is not included\n",
+                );
+            }
+        }
+
+        report
+    }
+}
@@ -0,0 +1,86 @@
+//! The symbol ordering file of `-Z emit-symbol-order`.
+//!
+//! The functions executed the most in a profile collected with `-C profile-generate` are listed
+//! first, so that the linker places them next to each other and the hot code of the program
+//! spans as few pages and cache lines as possible. The functions are matched with the counters of
+//! the profile by their symbol names, which only works when the profiled program was built from
+//! the same sources with the same options.
+
+use std::cmp;
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_middle::ty::TyCtxt;
+use rustc_session::Session;
+
+use super::symbol_export;
+use crate::errors;
+
+/// The symbols that can be ordered, and where the ordering file goes.
+#[derive(Clone, Debug, Encodable, Decodable)]
+pub struct SymbolOrder {
+    pub path: PathBuf,
+    /// The symbols of the mono items of the crate, and the symbols exported by upstream crates.
+    pub symbols: Vec<String>,
+}
+
+pub fn symbol_order(tcx: TyCtxt<'_>) -> SymbolOrder {
+    let (_, codegen_units) = tcx.collect_and_partition_mono_items(());
+    let mut symbols: Vec<_> = codegen_units
+        .iter()
+        .flat_map(|cgu| cgu.items().keys())
+        .map(|mono_item| mono_item.symbol_name(tcx).name.to_string())
+        .collect();
+    for &cnum in tcx.crates(()) {
+        for &(symbol, _) in tcx.exported_symbols(cnum) {
+            symbols.push(symbol_export::symbol_name_for_instance_in_crate(tcx, symbol, cnum));
+        }
+    }
+    symbols.sort();
+    symbols.dedup();
+
+    let path = tcx.output_filenames(()).with_extension("symbol-order");
+    SymbolOrder { path, symbols }
+}
+
+/// Writes the symbols with a non-zero count in `function_counts` to the ordering file, from the
+/// most executed to the least. The counts are keyed by the names of the profile, in which
+/// functions with internal linkage are prefixed with their file name and a `:` or `;`.
+pub fn emit_symbol_order(
+    sess: &Session,
+    symbol_order: &SymbolOrder,
+    function_counts: &FxHashMap<String, u64>,
+) {
+    let mut counts: FxHashMap<&str, u64> = FxHashMap::default();
+    for (name, &count) in function_counts {
+        let symbol = name.rsplit([':', ';']).next().unwrap();
+        *counts.entry(symbol).or_default() += count;
+    }
+
+    let mut symbols: Vec<_> = symbol_order
+        .symbols
+        .iter()
+        .filter_map(|symbol| Some((symbol.as_str(), *counts.get(symbol.as_str())?)))
+        .filter(|&(_, count)| count > 0)
+        .collect();
+    symbols.sort_by_key(|&(symbol, count)| (cmp::Reverse(count), symbol));
+
+    // Mach-O symbols are prefixed with an underscore.
+    let prefix = if sess.target.is_like_osx { "_" } else { "" };
+    let path = &symbol_order.path;
+    // The file is passed to the linker whenever it exists, so the one of an earlier compilation
+    // must not be left behind if this one can't be written.
+    let _ = fs::remove_file(path);
+    let result: std::io::Result<()> = try {
+        let mut file = BufWriter::new(fs::File::create(path)?);
+        for (symbol, _) in symbols {
+            writeln!(file, "{prefix}{symbol}")?;
+        }
+        file.flush()?;
+    };
+    if let Err(error) = result {
+        sess.emit_fatal(errors::FailedToWrite { path: path.clone(), error });
+    }
+}
@@ -23,10 +23,12 @@
 };
 use rustc_metadata::fs::copy_to_stdout;
 use rustc_metadata::EncodedMetadata;
-use rustc_middle::dep_graph::{WorkProduct, WorkProductId};
+use rustc_middle::dep_graph::{CguTiming, WorkProduct, WorkProductId};
 use rustc_middle::middle::exported_symbols::SymbolExportInfo;
 use rustc_middle::ty::TyCtxt;
-use rustc_session::config::{self, CrateType, Lto, OutFileName, OutputFilenames, OutputType};
+use rustc_session::config::{
+    self, CguPartitioning, CrateType, Lto, OutFileName, OutputFilenames, OutputType,
+};
 use rustc_session::config::{Passes, SwitchWithOptPath};
 use rustc_session::Session;
 use rustc_span::source_map::SourceMap;
@@ -44,8 +46,9 @@
 use std::path::{Path, PathBuf};
 use std::str;
 use std::sync::mpsc::{channel, Receiver, Sender};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::{Duration, Instant};
 
 const PRE_LTO_BC_EXT: &str = "pre-lto.bc";
 
@@ -101,6 +104,10 @@ pub struct ModuleConfig {
     pub emit_bc: bool,
     pub emit_ir: bool,
     pub emit_asm: bool,
+    pub emit_annotated_asm: bool,
+    /// Only keep the functions whose demangled name contains this string in the annotated
+    /// assembly.
+    pub asm_filter: Option<String>,
     pub emit_obj: EmitObj,
     pub emit_thin_lto: bool,
     pub bc_cmdline: String,
@@ -221,6 +228,11 @@ macro_rules! if_regular {
                 sess.opts.output_types.contains_key(&OutputType::Assembly),
                 false
             ),
+            emit_annotated_asm: if_regular!(
+                sess.opts.output_types.contains_key(&OutputType::AssemblyAnnotated),
+                false
+            ),
+            asm_filter: sess.opts.unstable_opts.asm_filter.clone(),
             emit_obj,
             emit_thin_lto: sess.opts.unstable_opts.emit_thin_lto,
             bc_cmdline: sess.target.bitcode_llvm_cmdline.to_string(),
@@ -367,6 +379,9 @@ pub struct CodegenContext<B: WriteBackendMethods> {
     pub incr_comp_session_dir: Option<PathBuf>,
     /// Channel back to the main control thread to send messages to
     pub coordinator_send: Sender<Box<dyn Any + Send>>,
+    /// Time spent by the backend on each regular module, from generating its IR to emitting
+    /// code, in nanoseconds. Only recorded for `-Z cgu-partitioning=timing-guided`.
+    pub backend_times: Option<Arc<Mutex<FxHashMap<String, u64>>>>,
 }
 
 impl<B: WriteBackendMethods> CodegenContext<B> {
@@ -381,6 +396,12 @@ pub fn config(&self, kind: ModuleKind) -> &ModuleConfig {
             ModuleKind::Allocator => &self.allocator_module_config,
         }
     }
+
+    fn record_backend_time(&self, module_name: &str, nanos: u64) {
+        if let Some(backend_times) = &self.backend_times {
+            *backend_times.lock().unwrap().entry(module_name.to_string()).or_default() += nanos;
+        }
+    }
 }
 
 fn generate_lto_work<B: ExtraBackendMethods>(
@@ -423,6 +444,9 @@ fn generate_lto_work<B: ExtraBackendMethods>(
 pub struct CompiledModules {
     pub modules: Vec<CompiledModule>,
     pub allocator_module: Option<CompiledModule>,
+    /// The backend time of each regular module, recorded for
+    /// `-Z cgu-partitioning=timing-guided`.
+    pub cgu_timings: FxHashMap<String, CguTiming>,
 }
 
 fn need_bitcode_in_object(tcx: TyCtxt<'_>) -> bool {
@@ -523,9 +547,10 @@ fn copy_all_cgu_workproducts_to_incr_comp_cache_dir(
             files.push(("dwo", dwarf_object_file_path.as_path()));
         }
 
-        if let Some((id, product)) =
+        if let Some((id, mut product)) =
             copy_cgu_workproduct_to_incr_comp_cache_dir(sess, &module.name, files.as_slice())
         {
+            product.backend_timing = compiled_modules.cgu_timings.get(&module.name).cloned();
             work_products.insert(id, product);
         }
     }
@@ -614,11 +639,18 @@ fn produce_final_output_artifacts(
             OutputType::Assembly => {
                 copy_if_one_unit(OutputType::Assembly, false);
             }
+            OutputType::AssemblyAnnotated => {
+                copy_if_one_unit(OutputType::AssemblyAnnotated, false);
+            }
             OutputType::Object => {
                 user_wants_objects = true;
                 copy_if_one_unit(OutputType::Object, true);
             }
-            OutputType::Mir | OutputType::Metadata | OutputType::Exe | OutputType::DepInfo => {}
+            OutputType::Mir
+            | OutputType::Metadata
+            | OutputType::MetadataInterface
+            | OutputType::Exe
+            | OutputType::DepInfo => {}
         }
     }
 
@@ -927,6 +959,11 @@ fn execute_copy_from_cache_work_item<B: ExtraBackendMethods>(
             load_from_incr_comp_dir(dwarf_obj_out, &saved_dwarf_object_file)
         });
 
+    // The module was not recompiled, so the time it took last time is still our best estimate.
+    if let Some(timing) = &module.source.backend_timing {
+        cgcx.record_backend_time(&module.name, timing.nanos);
+    }
+
     WorkItemResult::Finished(CompiledModule {
         name: module.name,
         kind: ModuleKind::Regular,
@@ -975,8 +1012,9 @@ pub(crate) enum Message<B: WriteBackendMethods> {
 
     /// The frontend has finished generating something (backend IR or a
     /// post-LTO artifact) for a codegen unit, and it should be passed to the
-    /// backend. Sent from the main thread.
-    CodegenDone { llvm_work_item: WorkItem<B>, cost: u64 },
+    /// backend. Sent from the main thread. `codegen_time` is the time it took
+    /// to generate the backend IR, if any.
+    CodegenDone { llvm_work_item: WorkItem<B>, cost: u64, codegen_time: Duration },
 
     /// Similar to `CodegenDone`, but for reusing a pre-LTO artifact
     /// Sent from the main thread.
@@ -1103,6 +1141,24 @@ fn start_executing_work<B: ExtraBackendMethods>(
         None
     };
 
+    // Record how long each CGU takes, so that the next session can balance them by time. We
+    // also need to know which CGUs were merged into each of them, see `CguTiming`.
+    let record_backend_times = sess.opts.incremental.is_some()
+        && sess.opts.unstable_opts.cgu_partitioning == CguPartitioning::TimingGuided;
+    let initial_cgus: FxHashMap<String, Vec<(String, usize)>> = if record_backend_times {
+        tcx.collect_and_partition_mono_items(())
+            .1
+            .iter()
+            .map(|cgu| {
+                let initial_cgus =
+                    cgu.initial_cgus().iter().map(|&(name, size)| (name.to_string(), size));
+                (cgu.name().to_string(), initial_cgus.collect())
+            })
+            .collect()
+    } else {
+        FxHashMap::default()
+    };
+
     let cgcx = CodegenContext::<B> {
         crate_types: tcx.crate_types().to_vec(),
         each_linked_rlib_for_lto,
@@ -1130,6 +1186,7 @@ fn start_executing_work<B: ExtraBackendMethods>(
         target_arch: tcx.sess.target.arch.to_string(),
         split_debuginfo: tcx.sess.split_debuginfo(),
         split_dwarf_kind: tcx.sess.opts.unstable_opts.split_dwarf_kind,
+        backend_times: record_backend_times.then(Default::default),
     };
 
     // This is the "main loop" of parallel work happening for parallel codegen.
@@ -1508,7 +1565,11 @@ enum CodegenState {
                     }
                 }
 
-                Message::CodegenDone { llvm_work_item, cost } => {
+                Message::CodegenDone { llvm_work_item, cost, codegen_time } => {
+                    if let WorkItem::Optimize(m) = &llvm_work_item {
+                        cgcx.record_backend_time(&m.name, codegen_time.as_nanos() as u64);
+                    }
+
                     // We keep the queue sorted by estimated processing cost,
                     // so that more expensive items are processed earlier. This
                     // is good for throughput as it gives the main thread more
@@ -1626,9 +1687,24 @@ enum CodegenState {
         // out deterministic results.
         compiled_modules.sort_by(|a, b| a.name.cmp(&b.name));
 
+        let cgu_timings = match &cgcx.backend_times {
+            Some(backend_times) => {
+                let backend_times = backend_times.lock().unwrap();
+                initial_cgus
+                    .into_iter()
+                    .filter_map(|(name, initial_cgus)| {
+                        let nanos = *backend_times.get(&name)?;
+                        Some((name, CguTiming { nanos, initial_cgus }))
+                    })
+                    .collect()
+            }
+            None => FxHashMap::default(),
+        };
+
         Ok(CompiledModules {
             modules: compiled_modules,
             allocator_module: compiled_allocator_module,
+            cgu_timings,
         })
     })
     .expect("failed to spawn coordinator thread");
@@ -1745,7 +1821,11 @@ fn drop(&mut self) {
                 WorkItem::Optimize(m) => {
                     let _timer =
                         cgcx.prof.generic_activity_with_arg("codegen_module_optimize", &*m.name);
-                    execute_optimize_work_item(&cgcx, m, module_config)
+                    let name = m.name.clone();
+                    let start = Instant::now();
+                    let result = execute_optimize_work_item(&cgcx, m, module_config);
+                    cgcx.record_backend_time(&name, start.elapsed().as_nanos() as u64);
+                    result
                 }
                 WorkItem::CopyPostLtoArtifacts(m) => {
                     let _timer = cgcx.prof.generic_activity_with_arg(
@@ -1757,7 +1837,11 @@ fn drop(&mut self) {
                 WorkItem::LTO(m) => {
                     let _timer =
                         cgcx.prof.generic_activity_with_arg("codegen_module_perform_lto", m.name());
-                    execute_lto_work_item(&cgcx, m, module_config)
+                    let name = m.name().to_string();
+                    let start = Instant::now();
+                    let result = execute_lto_work_item(&cgcx, m, module_config);
+                    cgcx.record_backend_time(&name, start.elapsed().as_nanos() as u64);
+                    result
                 }
             })
         };
@@ -2022,9 +2106,14 @@ pub fn submit_codegened_module_to_llvm<B: ExtraBackendMethods>(
     tx_to_llvm_workers: &Sender<Box<dyn Any + Send>>,
     module: ModuleCodegen<B::Module>,
     cost: u64,
+    codegen_time: Duration,
 ) {
     let llvm_work_item = WorkItem::Optimize(module);
-    drop(tx_to_llvm_workers.send(Box::new(Message::CodegenDone::<B> { llvm_work_item, cost })));
+    drop(tx_to_llvm_workers.send(Box::new(Message::CodegenDone::<B> {
+        llvm_work_item,
+        cost,
+        codegen_time,
+    })));
 }
 
 pub fn submit_post_lto_module_to_llvm<B: ExtraBackendMethods>(
@@ -2033,7 +2122,11 @@ pub fn submit_post_lto_module_to_llvm<B: ExtraBackendMethods>(
     module: CachedModuleCodegen,
 ) {
     let llvm_work_item = WorkItem::CopyPostLtoArtifacts(module);
-    drop(tx_to_llvm_workers.send(Box::new(Message::CodegenDone::<B> { llvm_work_item, cost: 0 })));
+    drop(tx_to_llvm_workers.send(Box::new(Message::CodegenDone::<B> {
+        llvm_work_item,
+        cost: 0,
+        codegen_time: Duration::ZERO,
+    })));
 }
 
 pub fn submit_pre_lto_module_to_llvm<B: ExtraBackendMethods>(
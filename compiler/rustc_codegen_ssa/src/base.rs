@@ -1,6 +1,8 @@
 use crate::assert_module_sources::CguReuse;
 use crate::back::link::are_upstream_rust_objects_already_included;
 use crate::back::metadata::create_compressed_metadata_file;
+use crate::back::size_report;
+use crate::back::symbol_order;
 use crate::back::write::{
     compute_per_cgu_lto_type, start_async_codegen, submit_codegened_module_to_llvm,
     submit_post_lto_module_to_llvm, submit_pre_lto_module_to_llvm, ComputedLtoType, OngoingCodegen,
@@ -457,6 +459,7 @@ fn create_entry_fn<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
         let mut bx = Bx::build(&cx, llbb);
 
         bx.insert_reference_to_gdb_debug_scripts_section_global();
+        bx.insert_reference_to_lldb_formatters_section_global();
 
         let isize_ty = cx.type_isize();
         let ptr_ty = cx.type_ptr();
@@ -656,6 +659,7 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
             &ongoing_codegen.coordinator.sender,
             ModuleCodegen { name: llmod_id, module_llvm, kind: ModuleKind::Allocator },
             cost,
+            Duration::ZERO,
         );
     }
 
@@ -717,8 +721,9 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
             let start_time = Instant::now();
 
             let pre_compiled_cgus = par_map(cgus, |(i, _)| {
+                let cgu_start_time = Instant::now();
                 let module = backend.compile_codegen_unit(tcx, codegen_units[i].name());
-                (i, module)
+                (i, (module, cgu_start_time.elapsed()))
             });
 
             total_codegen_time += start_time.elapsed();
@@ -737,13 +742,15 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
 
         match cgu_reuse {
             CguReuse::No => {
-                let (module, cost) = if let Some(cgu) = pre_compiled_cgus.remove(&i) {
-                    cgu
-                } else {
-                    let start_time = Instant::now();
-                    let module = backend.compile_codegen_unit(tcx, cgu.name());
-                    total_codegen_time += start_time.elapsed();
-                    module
+                let ((module, cost), codegen_time) = match pre_compiled_cgus.remove(&i) {
+                    Some(cgu) => cgu,
+                    None => {
+                        let start_time = Instant::now();
+                        let module = backend.compile_codegen_unit(tcx, cgu.name());
+                        let codegen_time = start_time.elapsed();
+                        total_codegen_time += codegen_time;
+                        (module, codegen_time)
+                    }
                 };
                 // This will unwind if there are errors, which triggers our `AbortCodegenOnDrop`
                 // guard. Unfortunately, just skipping the `submit_codegened_module_to_llvm` makes
@@ -755,6 +762,7 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
                     &ongoing_codegen.coordinator.sender,
                     module,
                     cost,
+                    codegen_time,
                 );
             }
             CguReuse::PreLto => {
@@ -863,6 +871,26 @@ pub fn new(tcx: TyCtxt<'_>, target_cpu: String) -> CrateInfo {
             dependency_formats: tcx.dependency_formats(()).clone(),
             windows_subsystem,
             natvis_debugger_visualizers: Default::default(),
+            stack_depth_call_graph: tcx.sess.opts.unstable_opts.stack_depth_report.then(|| {
+                // The call graph is fed by the mono item collection, which hasn't run yet with
+                // `-Z no-codegen`.
+                tcx.collect_and_partition_mono_items(());
+                tcx.mono_call_graph(()).clone()
+            }),
+            size_report_items: tcx
+                .sess
+                .opts
+                .unstable_opts
+                .size_report
+                .is_some()
+                .then(|| size_report::size_report_items(tcx)),
+            symbol_order: tcx
+                .sess
+                .opts
+                .unstable_opts
+                .emit_symbol_order
+                .is_some()
+                .then(|| symbol_order::symbol_order(tcx)),
         };
         let crates = tcx.crates(());
 
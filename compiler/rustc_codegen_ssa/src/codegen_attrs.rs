@@ -9,6 +9,7 @@
 use rustc_middle::mir::mono::Linkage;
 use rustc_middle::query::Providers;
 use rustc_middle::ty::{self as ty, TyCtxt};
+use rustc_session::config::CrateType;
 use rustc_session::{lint, parse::feature_err};
 use rustc_span::symbol::Ident;
 use rustc_span::{sym, Span};
@@ -613,6 +614,22 @@ fn codegen_fn_attrs(tcx: TyCtxt<'_>, did: LocalDefId) -> CodegenFnAttrs {
         codegen_fn_attrs.flags |= CodegenFnAttrFlags::NO_MANGLE;
     }
 
+    // The symbols marked as weak in the export list of `-C export-symbols` are defined with weak
+    // linkage, so that a definition loaded earlier takes precedence over them. Like the rest of
+    // the list, this only applies to cdylibs.
+    if let Some(exports) = &tcx.sess.opts.export_symbols
+        && tcx.crate_types().contains(&CrateType::Cdylib)
+        && codegen_fn_attrs.linkage.is_none()
+        && !tcx.is_foreign_item(did)
+        && (codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::NO_MANGLE)
+            || codegen_fn_attrs.export_name.is_some())
+        && let Some(name) =
+            codegen_fn_attrs.export_name.or_else(|| tcx.opt_item_name(did.to_def_id()))
+        && exports.is_weak(name.as_str())
+    {
+        codegen_fn_attrs.linkage = Some(Linkage::WeakAny);
+    }
+
     // Any linkage to LLVM intrinsics for now forcibly marks them all as never
     // unwinds since LLVM sometimes can't handle codegen which `invoke`s
     // intrinsic functions.
@@ -111,6 +111,15 @@ pub struct SymbolFileWriteFailure {
 #[diag(codegen_ssa_linker_unsupported_modifier)]
 pub struct LinkerUnsupportedModifier;
 
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_export_symbol_versions_ignored)]
+pub struct ExportSymbolVersionsIgnored;
+
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_symbol_ordering_file_ignored)]
+#[help]
+pub struct SymbolOrderingFileIgnored;
+
 #[derive(Diagnostic)]
 #[diag(codegen_ssa_L4Bender_exporting_symbols_unimplemented)]
 pub struct L4BenderExportingSymbolsUnimplemented;
@@ -545,6 +554,20 @@ pub struct FailedToWrite {
     pub error: Error,
 }
 
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_stack_sizes_read_failure)]
+pub struct StackSizesReadFailure {
+    pub path: PathBuf,
+    pub error: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_symbol_sizes_read_failure)]
+pub struct SymbolSizesReadFailure {
+    pub path: PathBuf,
+    pub error: String,
+}
+
 #[derive(Diagnostic)]
 #[diag(codegen_ssa_unable_to_write_debugger_visualizer)]
 pub struct UnableToWriteDebuggerVisualizer {
@@ -24,6 +24,8 @@
 #[macro_use]
 extern crate rustc_middle;
 
+use crate::back::size_report::SizeReportItem;
+use crate::back::symbol_order::SymbolOrder;
 use rustc_ast as ast;
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::sync::Lrc;
@@ -34,6 +36,7 @@
 use rustc_middle::middle::debugger_visualizer::DebuggerVisualizerFile;
 use rustc_middle::middle::dependency_format::Dependencies;
 use rustc_middle::middle::exported_symbols::SymbolExportKind;
+use rustc_middle::mir::mono::MonoCallGraph;
 use rustc_middle::util::Providers;
 use rustc_serialize::opaque::{FileEncoder, MemDecoder};
 use rustc_serialize::{Decodable, Decoder, Encodable, Encoder};
@@ -168,6 +171,12 @@ pub struct CrateInfo {
     pub dependency_formats: Lrc<Dependencies>,
     pub windows_subsystem: Option<String>,
     pub natvis_debugger_visualizers: BTreeSet<DebuggerVisualizerFile>,
+    /// The call graph used for `-Z stack-depth-report`, if requested.
+    pub stack_depth_call_graph: Option<MonoCallGraph>,
+    /// The mono items of the crate used for `-Z size-report`, if requested.
+    pub size_report_items: Option<Vec<SizeReportItem>>,
+    /// The symbols ordered by `-Z emit-symbol-order`, if requested.
+    pub symbol_order: Option<SymbolOrder>,
 }
 
 #[derive(Encodable, Decodable)]
@@ -623,6 +623,24 @@ fn codegen_assert_terminator(
                 // and `#[track_caller]` adds an implicit third argument.
                 (LangItem::PanicMisalignedPointerDereference, vec![required, found, location])
             }
+            AssertKind::NullPointerDereference => {
+                // It's `fn panic_null_pointer_dereference()`,
+                // and `#[track_caller]` adds an implicit argument.
+                (LangItem::PanicNullPointerDereference, vec![location])
+            }
+            AssertKind::InvalidValue { ref value } => {
+                let value = self.codegen_operand(bx, value).immediate();
+                // It's `fn panic_invalid_value(value: u128)`,
+                // and `#[track_caller]` adds an implicit second argument.
+                (LangItem::PanicInvalidValue, vec![value, location])
+            }
+            AssertKind::SliceFromRawPartsOverflow { ref len, ref size } => {
+                let len = self.codegen_operand(bx, len).immediate();
+                let size = self.codegen_operand(bx, size).immediate();
+                // It's `fn panic_slice_from_raw_parts_overflow(len: usize, size: usize)`,
+                // and `#[track_caller]` adds an implicit third argument.
+                (LangItem::PanicSliceFromRawPartsOverflow, vec![len, size, location])
+            }
             _ => {
                 let msg = bx.const_str(msg.description());
                 // It's `pub fn panic(expr: &str)`, with the wide reference being passed
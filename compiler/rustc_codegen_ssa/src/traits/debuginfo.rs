@@ -80,5 +80,6 @@ fn dbg_var_addr(
     );
     fn set_dbg_loc(&mut self, dbg_loc: Self::DILocation);
     fn insert_reference_to_gdb_debug_scripts_section_global(&mut self);
+    fn insert_reference_to_lldb_formatters_section_global(&mut self);
     fn set_var_name(&mut self, value: Self::Value, name: &str);
 }
@@ -561,6 +561,11 @@ fn assert_panic(
                     found: eval_to_int(found)?,
                 }
             }
+            NullPointerDereference => NullPointerDereference,
+            InvalidValue { ref value } => InvalidValue { value: eval_to_int(value)? },
+            SliceFromRawPartsOverflow { ref len, ref size } => {
+                SliceFromRawPartsOverflow { len: eval_to_int(len)?, size: eval_to_int(size)? }
+            }
         };
         Err(ConstEvalErrKind::AssertFailure(err).into())
     }
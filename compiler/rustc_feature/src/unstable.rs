@@ -492,6 +492,8 @@ pub fn internal(&self, feature: Symbol) -> bool {
     (unstable, let_chains, "1.37.0", Some(53667), None),
     /// Allows using `reason` in lint attributes and the `#[expect(lint)]` lint check.
     (unstable, lint_reasons, "1.31.0", Some(54503), None),
+    /// Allows embedding LLDB formatters with `#[debugger_visualizer]`.
+    (unstable, lldb_debugger_visualizer, "CURRENT_RUSTC_VERSION", None, None),
     /// Give access to additional metadata about declarative macro meta-variables.
     (unstable, macro_metavar_expr, "1.61.0", Some(83527), None),
     /// Allows `#[marker]` on certain traits allowing overlapping implementations.
@@ -530,6 +532,8 @@ pub fn internal(&self, feature: Symbol) -> bool {
     (unstable, offset_of_enum, "CURRENT_RUSTC_VERSION", Some(106655), None),
     /// Allows using `#[optimize(X)]`.
     (unstable, optimize_attribute, "1.34.0", Some(54882), None),
+    /// Allows running a test once per `#[case(...)]` attribute with the given arguments.
+    (unstable, parameterized_tests, "CURRENT_RUSTC_VERSION", None, None),
     /// Allows exhaustive integer pattern matching on `usize` and `isize`.
     (unstable, precise_pointer_size_matching, "1.32.0", Some(56354), None),
     /// Allows macro attributes on expressions, statements and non-inline modules.
@@ -558,6 +562,8 @@ pub fn internal(&self, feature: Symbol) -> bool {
     (unstable, string_deref_patterns, "1.67.0", Some(87121), None),
     /// Allows the use of `#[target_feature]` on safe functions.
     (unstable, target_feature_11, "1.45.0", Some(69098), None),
+    /// Allows giving tests a hard timeout with `#[timeout = "..."]`.
+    (unstable, test_timeout, "CURRENT_RUSTC_VERSION", None, None),
     /// Allows using `#[thread_local]` on `static` items.
     (unstable, thread_local, "1.0.0", Some(29594), None),
     /// Allows defining `trait X = A + B;` alias items.
@@ -234,6 +234,9 @@ pub fn extract(attrs: &[ast::Attribute]) -> Option<(Symbol, Span)> {
     ConstPanicFmt,           sym::const_panic_fmt,     const_panic_fmt,            Target::Fn,             GenericRequirement::None;
     PanicBoundsCheck,        sym::panic_bounds_check,  panic_bounds_check_fn,      Target::Fn,             GenericRequirement::Exact(0);
     PanicMisalignedPointerDereference,        sym::panic_misaligned_pointer_dereference,  panic_misaligned_pointer_dereference_fn,      Target::Fn,             GenericRequirement::Exact(0);
+    PanicNullPointerDereference,              sym::panic_null_pointer_dereference,        panic_null_pointer_dereference_fn,            Target::Fn,             GenericRequirement::Exact(0);
+    PanicInvalidValue,                        sym::panic_invalid_value,                   panic_invalid_value_fn,                       Target::Fn,             GenericRequirement::Exact(0);
+    PanicSliceFromRawPartsOverflow,           sym::panic_slice_from_raw_parts_overflow,   panic_slice_from_raw_parts_overflow_fn,       Target::Fn,             GenericRequirement::Exact(0);
     PanicInfo,               sym::panic_info,          panic_info,                 Target::Struct,         GenericRequirement::None;
     PanicLocation,           sym::panic_location,      panic_location,             Target::Struct,         GenericRequirement::None;
     PanicImpl,               sym::panic_impl,          panic_impl,                 Target::Fn,             GenericRequirement::None;
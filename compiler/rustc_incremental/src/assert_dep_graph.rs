@@ -45,19 +45,22 @@
     dep_kinds, DepGraphQuery, DepKind, DepNode, DepNodeExt, DepNodeFilter, EdgeFilter,
 };
 use rustc_middle::hir::nested_filter;
+use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::TyCtxt;
+use rustc_session::config::DumpDepGraphFormat;
 use rustc_span::symbol::{sym, Symbol};
 use rustc_span::Span;
 
 use std::env;
 use std::fs::{self, File};
-use std::io::{BufWriter, Write};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
 
 #[allow(missing_docs)]
 pub fn assert_dep_graph(tcx: TyCtxt<'_>) {
     tcx.dep_graph.with_ignore(|| {
-        if tcx.sess.opts.unstable_opts.dump_dep_graph {
-            tcx.dep_graph.with_query(dump_graph);
+        if let Some(format) = tcx.sess.opts.unstable_opts.dump_dep_graph {
+            tcx.dep_graph.with_query(|query| dump_graph(tcx, query, format));
         }
 
         if !tcx.sess.opts.unstable_opts.query_dep_graph {
@@ -223,9 +226,21 @@ fn check_paths<'tcx>(tcx: TyCtxt<'tcx>, if_this_changed: &Sources, then_this_wou
     });
 }
 
-fn dump_graph(query: &DepGraphQuery) {
+fn dump_graph(tcx: TyCtxt<'_>, query: &DepGraphQuery, format: DumpDepGraphFormat) {
     let path: String = env::var("RUST_DEP_GRAPH").unwrap_or_else(|_| "dep_graph".to_string());
 
+    match format {
+        DumpDepGraphFormat::Dot => dump_graph_dot(tcx, query, &path),
+        DumpDepGraphFormat::Json => {
+            let json_path = PathBuf::from(format!("{path}.json"));
+            if let Err(err) = dump_graph_json(tcx, query, &json_path) {
+                tcx.sess.emit_err(errors::WriteDepGraph { path: &json_path, err });
+            }
+        }
+    }
+}
+
+fn dump_graph_dot(tcx: TyCtxt<'_>, query: &DepGraphQuery, path: &str) {
     let nodes = match env::var("RUST_DEP_GRAPH_FILTER") {
         Ok(string) => {
             // Expect one of: "-> target", "source -> target", or "source ->".
@@ -235,7 +250,15 @@ fn dump_graph(query: &DepGraphQuery) {
             let targets = node_set(&query, &edge_filter.target);
             filter_nodes(&query, &sources, &targets)
         }
-        Err(_) => query.nodes().into_iter().map(|n| n.kind).collect(),
+        Err(_) => {
+            let selected = selected_nodes(tcx, query);
+            query
+                .graph
+                .enumerated_nodes()
+                .filter(|(index, _)| selected[index.node_id()])
+                .map(|(_, node)| node.data.kind)
+                .collect()
+        }
     };
     let edges = filter_edges(&query, &nodes);
 
@@ -257,6 +280,87 @@ fn dump_graph(query: &DepGraphQuery) {
     }
 }
 
+/// Dumps every node of the graph with its fingerprint and timing, and every edge, as JSON.
+/// Nodes are identified by their position in the `nodes` array, and an edge `[a, b]` means
+/// that node `a` read node `b` while it was computed.
+fn dump_graph_json(tcx: TyCtxt<'_>, query: &DepGraphQuery, path: &Path) -> io::Result<()> {
+    #[derive(serde::Serialize)]
+    struct Node {
+        kind: String,
+        label: String,
+        def_path: Option<String>,
+        fingerprint: String,
+        /// Time spent computing the node, including its dependencies, if it was computed in
+        /// this session.
+        duration_ns: Option<u64>,
+    }
+
+    #[derive(serde::Serialize)]
+    struct Graph {
+        nodes: Vec<Node>,
+        edges: Vec<(usize, usize)>,
+    }
+
+    let selected = selected_nodes(tcx, query);
+
+    // Indices in the dumped `nodes` array, which skips the nodes that were filtered out.
+    let mut json_indices = vec![None; query.graph.len_nodes()];
+    let mut nodes = Vec::new();
+    for (index, node) in query.graph.enumerated_nodes() {
+        if !selected[index.node_id()] {
+            continue;
+        }
+        let node = &node.data;
+        json_indices[index.node_id()] = Some(nodes.len());
+        nodes.push(Node {
+            kind: format!("{:?}", node.kind),
+            label: format!("{node:?}"),
+            def_path: node
+                .extract_def_id(tcx)
+                .map(|def_id| with_no_trimmed_paths!(tcx.def_path_str(def_id))),
+            fingerprint: query.fingerprints[index.node_id()].to_hex(),
+            duration_ns: query.durations[index.node_id()].map(|d| d.as_nanos() as u64),
+        });
+    }
+
+    let edges = query
+        .graph
+        .all_edges()
+        .iter()
+        .filter_map(|edge| {
+            Some((json_indices[edge.source().node_id()]?, json_indices[edge.target().node_id()]?))
+        })
+        .collect();
+
+    let file = BufWriter::new(File::create(path)?);
+    serde_json::to_writer(file, &Graph { nodes, edges })?;
+    Ok(())
+}
+
+/// Returns, for each node of `query`, whether it should be dumped: either all nodes if there
+/// is no `-Z dump-dep-graph-filter`, or the nodes matching it along with their direct
+/// dependencies and dependents.
+fn selected_nodes(tcx: TyCtxt<'_>, query: &DepGraphQuery) -> Vec<bool> {
+    let Some(filter) = &tcx.sess.opts.unstable_opts.dump_dep_graph_filter else {
+        return vec![true; query.graph.len_nodes()];
+    };
+    let filter = DepNodeFilter::new(filter);
+
+    let mut selected = vec![false; query.graph.len_nodes()];
+    for (index, node) in query.graph.enumerated_nodes() {
+        if filter.test(&node.data) {
+            selected[index.node_id()] = true;
+            for (_, edge) in query.graph.adjacent_edges(index, OUTGOING) {
+                selected[edge.target().node_id()] = true;
+            }
+            for (_, edge) in query.graph.adjacent_edges(index, INCOMING) {
+                selected[edge.source().node_id()] = true;
+            }
+        }
+    }
+    selected
+}
+
 #[allow(missing_docs)]
 pub struct GraphvizDepGraph(FxIndexSet<DepKind>, Vec<(DepKind, DepKind)>);
 
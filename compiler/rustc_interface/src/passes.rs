@@ -13,7 +13,7 @@
 use rustc_expand::base::{ExtCtxt, LintStoreExpand};
 use rustc_feature::Features;
 use rustc_fs_util::try_canonicalize;
-use rustc_hir::def_id::{StableCrateId, LOCAL_CRATE};
+use rustc_hir::def_id::{LocalDefId, StableCrateId, LOCAL_CRATE};
 use rustc_lint::{unerased_lint_store, BufferedEarlyLint, EarlyCheckNode, LintStore};
 use rustc_metadata::creader::CStore;
 use rustc_middle::arena::Arena;
@@ -740,8 +740,19 @@ fn analysis(tcx: TyCtxt<'_>, (): ()) -> Result<()> {
     // passes are timed inside typeck
     rustc_hir_analysis::check_crate(tcx)?;
 
+    // Metadata interfaces only contain what downstream crates need for type checking, so the
+    // MIR of bodies which can't be evaluated at compile time is neither built nor checked there.
+    // Errors in those bodies are reported when the crate is built for real.
+    let needs_mir = |def_id: LocalDefId| {
+        !sess.opts.output_types.is_metadata_interface()
+            || tcx.hir().body_const_context(def_id).is_some()
+    };
+
     sess.time("MIR_borrow_checking", || {
         tcx.hir().par_body_owners(|def_id| {
+            if !needs_mir(def_id) {
+                return;
+            }
             // Run THIR unsafety check because it's responsible for stealing
             // and deallocating THIR when enabled.
             tcx.ensure().thir_check_unsafety(def_id);
@@ -751,6 +762,9 @@ fn analysis(tcx: TyCtxt<'_>, (): ()) -> Result<()> {
 
     sess.time("MIR_effect_checking", || {
         for def_id in tcx.hir().body_owners() {
+            if !needs_mir(def_id) {
+                continue;
+            }
             if !tcx.sess.opts.unstable_opts.thir_unsafeck {
                 rustc_mir_transform::check_unsafety::check_unsafety(tcx, def_id);
             }
@@ -298,13 +298,9 @@ pub fn link(self) -> Result<()> {
         // any more, we can finalize it (which involves renaming it)
         rustc_incremental::finalize_session_directory(&self.sess, self.crate_hash);
 
-        if !self
-            .sess
-            .opts
-            .output_types
-            .keys()
-            .any(|&i| i == OutputType::Exe || i == OutputType::Metadata)
-        {
+        if !self.sess.opts.output_types.keys().any(|&i| {
+            i == OutputType::Exe || i == OutputType::Metadata || i == OutputType::MetadataInterface
+        }) {
             return Ok(());
         }
 
@@ -19,6 +19,7 @@
 #include "llvm/MC/TargetRegistry.h"
 #include "llvm/Object/ObjectFile.h"
 #include "llvm/Object/IRObjectFile.h"
+#include "llvm/ProfileData/InstrProfReader.h"
 #include "llvm/Passes/PassBuilder.h"
 #include "llvm/Passes/PassPlugin.h"
 #include "llvm/Passes/StandardInstrumentations.h"
@@ -1652,3 +1653,36 @@ LLVMRustComputeLTOCacheKey(RustStringRef KeyOut, const char *ModId, LLVMRustThin
 
   LLVMRustStringWriteImpl(KeyOut, Key.c_str(), Key.size());
 }
+
+typedef void (*LLVMRustInstrProfFunctionCallback)(void *, const char *, size_t, uint64_t);
+
+// Calls `Callback` with the name of every function of an indexed instrumentation profile,
+// along with the sum of its counters. Returns false and sets the last error if the profile can't
+// be read.
+extern "C" bool
+LLVMRustReadInstrProfFunctionCounts(const char *Path, void *State,
+                                    LLVMRustInstrProfFunctionCallback Callback) {
+#if LLVM_VERSION_GE(17, 0)
+  auto FS = vfs::getRealFileSystem();
+  auto ReaderOrErr = IndexedInstrProfReader::create(Path, *FS);
+#else
+  auto ReaderOrErr = IndexedInstrProfReader::create(Path);
+#endif
+  if (!ReaderOrErr) {
+    LLVMRustSetLastError(toString(ReaderOrErr.takeError()).c_str());
+    return false;
+  }
+  std::unique_ptr<IndexedInstrProfReader> Reader = std::move(ReaderOrErr.get());
+
+  for (const NamedInstrProfRecord &Record : *Reader) {
+    uint64_t Count = 0;
+    for (uint64_t C : Record.Counts)
+      Count = SaturatingAdd(Count, C);
+    Callback(State, Record.Name.data(), Record.Name.size(), Count);
+  }
+  if (Error E = Reader->getError()) {
+    LLVMRustSetLastError(toString(std::move(E)).c_str());
+    return false;
+  }
+  return true;
+}
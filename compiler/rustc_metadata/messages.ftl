@@ -159,6 +159,10 @@ metadata_link_requires_name =
     `#[link]` attribute requires a `name = "string"` argument
     .label = missing `name` argument
 
+metadata_metadata_interface_not_linkable =
+    crate `{$crate_name}` was compiled with `--emit=metadata-interface` and cannot be used for code generation
+    .help = rebuild `{$path}` with `--emit=metadata` or `--emit=link` instead
+
 metadata_missing_native_library =
     could not find native static library `{$libname}`, perhaps an -L flag is missing?
 
@@ -380,6 +380,14 @@ fn register_crate(
         let crate_root = metadata.get_root();
         let host_hash = host_lib.as_ref().map(|lib| lib.metadata.get_root().hash());
 
+        // Metadata interfaces lack the MIR that codegen needs from upstream crates.
+        if crate_root.is_metadata_interface() && self.sess.opts.output_types.should_codegen() {
+            self.sess.emit_err(errors::MetadataInterfaceNotLinkable {
+                crate_name: crate_root.name(),
+                path: source.paths().next().cloned().unwrap_or_default(),
+            });
+        }
+
         let private_dep = self
             .sess
             .opts
@@ -380,6 +380,14 @@ pub struct ConflictingAllocErrorHandler {
 #[diag(metadata_global_alloc_required)]
 pub struct GlobalAllocRequired;
 
+#[derive(Diagnostic)]
+#[diag(metadata_metadata_interface_not_linkable)]
+#[help]
+pub struct MetadataInterfaceNotLinkable {
+    pub crate_name: Symbol,
+    pub path: PathBuf,
+}
+
 #[derive(Diagnostic)]
 #[diag(metadata_no_transitive_needs_dep)]
 pub struct NoTransitiveNeedsDep<'a> {
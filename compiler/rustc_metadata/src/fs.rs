@@ -71,7 +71,8 @@ pub fn encode_and_write_metadata(tcx: TyCtxt<'_>) -> (EncodedMetadata, bool) {
     // If the user requests metadata as output, rename `metadata_filename`
     // to the expected output `out_filename`. The match above should ensure
     // this file always exists.
-    let need_metadata_file = tcx.sess.opts.output_types.contains_key(&OutputType::Metadata);
+    let need_metadata_file = tcx.sess.opts.output_types.contains_key(&OutputType::Metadata)
+        || tcx.sess.opts.output_types.is_metadata_interface();
     let (metadata_filename, metadata_tmpdir) = if need_metadata_file {
         let filename = match out_filename {
             OutFileName::Real(ref path) => {
@@ -934,6 +934,10 @@ pub(crate) fn stable_crate_id(&self) -> StableCrateId {
         self.stable_crate_id
     }
 
+    pub(crate) fn is_metadata_interface(&self) -> bool {
+        self.is_metadata_interface
+    }
+
     pub(crate) fn decode_crate_deps<'a>(
         &self,
         metadata: &'a MetadataBlob,
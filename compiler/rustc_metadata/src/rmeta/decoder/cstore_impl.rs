@@ -219,6 +219,7 @@ fn into_args(self) -> (DefId, SimplifiedType) {
     object_lifetime_default => { table }
     thir_abstract_const => { table }
     optimized_mir => { table }
+    mir_summary => { table }
     mir_for_ctfe => { table }
     closure_saved_names_of_captured_variables => { table }
     mir_coroutine_witnesses => { table }
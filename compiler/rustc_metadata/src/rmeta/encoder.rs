@@ -658,8 +658,13 @@ macro_rules! stat {
 
         // Encode exported symbols info. This is prefetched in `encode_metadata` so we encode
         // this as late as possible to give the prefetching as much time as possible to complete.
+        // Metadata interfaces are never linked against, so they don't need them at all.
         let exported_symbols = stat!("exported-symbols", || {
-            self.encode_exported_symbols(&tcx.exported_symbols(LOCAL_CRATE))
+            if tcx.sess.opts.output_types.is_metadata_interface() {
+                LazyArray::default()
+            } else {
+                self.encode_exported_symbols(&tcx.exported_symbols(LOCAL_CRATE))
+            }
         });
 
         // Encode the hygiene data.
@@ -703,6 +708,7 @@ macro_rules! stat {
                 panic_runtime: attr::contains_name(&attrs, sym::panic_runtime),
                 profiler_runtime: attr::contains_name(&attrs, sym::profiler_runtime),
                 symbol_mangling_version: tcx.sess.opts.get_symbol_mangling_version(),
+                is_metadata_interface: tcx.sess.opts.output_types.is_metadata_interface(),
 
                 crate_deps,
                 dylib_dependency_formats,
@@ -1014,7 +1020,8 @@ fn should_encode_stability(def_kind: DefKind) -> bool {
 /// Computing, optimizing and encoding the MIR is a relatively expensive operation.
 /// We want to avoid this work when not required. Therefore:
 /// - we only compute `mir_for_ctfe` on items with const-eval semantics;
-/// - we skip `optimized_mir` for check runs.
+/// - we skip `optimized_mir` for check runs, and for metadata interfaces even if
+///   `-Zalways-encode-mir` is passed (coroutines are the exception, see below).
 /// - we only encode `optimized_mir` that could be generated in other crates, that is, a code that
 ///   is either generic or has inline hint, and is reachable from the other crates (contained
 ///   in reachable set).
@@ -1034,11 +1041,15 @@ fn should_encode_mir(
     reachable_set: &LocalDefIdSet,
     def_id: LocalDefId,
 ) -> (bool, bool) {
+    // Metadata interfaces are only used to type check downstream crates, which never looks at
+    // the optimized MIR of functions.
+    let is_metadata_interface = tcx.sess.opts.output_types.is_metadata_interface();
     match tcx.def_kind(def_id) {
         // Constructors
         DefKind::Ctor(_, _) => {
-            let mir_opt_base = tcx.sess.opts.output_types.should_codegen()
-                || tcx.sess.opts.unstable_opts.always_encode_mir;
+            let mir_opt_base = !is_metadata_interface
+                && (tcx.sess.opts.output_types.should_codegen()
+                    || tcx.sess.opts.unstable_opts.always_encode_mir);
             (true, mir_opt_base)
         }
         // Constants
@@ -1050,17 +1061,19 @@ fn should_encode_mir(
         // Full-fledged functions + closures
         DefKind::AssocFn | DefKind::Fn | DefKind::Closure => {
             let generics = tcx.generics_of(def_id);
-            let opt = tcx.sess.opts.unstable_opts.always_encode_mir
-                || (tcx.sess.opts.output_types.should_codegen()
-                    && reachable_set.contains(&def_id)
-                    && (generics.requires_monomorphization(tcx)
-                        || tcx.cross_crate_inlinable(def_id)));
+            let opt = !is_metadata_interface
+                && (tcx.sess.opts.unstable_opts.always_encode_mir
+                    || (tcx.sess.opts.output_types.should_codegen()
+                        && reachable_set.contains(&def_id)
+                        && (generics.requires_monomorphization(tcx)
+                            || tcx.cross_crate_inlinable(def_id))));
             // The function has a `const` modifier or is in a `#[const_trait]`.
             let is_const_fn = tcx.is_const_fn_raw(def_id.to_def_id())
                 || tcx.is_const_default_method(def_id.to_def_id());
             (is_const_fn, opt)
         }
-        // Coroutines require optimized MIR to compute layout.
+        // Coroutines require optimized MIR to compute layout, which downstream type checking
+        // may need as well, so this is kept even in metadata interfaces.
         DefKind::Coroutine => (false, true),
         // The others don't have MIR.
         _ => (false, false),
@@ -1626,6 +1639,12 @@ fn encode_mir(&mut self) {
                 self.tables
                     .cross_crate_inlinable
                     .set(def_id.to_def_id().index, Some(self.tcx.cross_crate_inlinable(def_id)));
+                if tcx.sess.opts.unstable_opts.encode_mir_summaries
+                    && let DefKind::Fn | DefKind::AssocFn | DefKind::Closure = tcx.def_kind(def_id)
+                    && let Some(summary) = tcx.mir_summary(def_id)
+                {
+                    record!(self.tables.mir_summary[def_id.to_def_id()] <- summary);
+                }
                 record!(self.tables.closure_saved_names_of_captured_variables[def_id.to_def_id()]
                     <- tcx.closure_saved_names_of_captured_variables(def_id));
 
@@ -2207,7 +2226,14 @@ pub fn encode_metadata(tcx: TyCtxt<'_>, path: &Path) {
             // Prefetch some queries used by metadata encoding.
             // This is not necessary for correctness, but is only done for performance reasons.
             // It can be removed if it turns out to cause trouble or be detrimental to performance.
-            join(|| prefetch_mir(tcx), || tcx.exported_symbols(LOCAL_CRATE));
+            join(
+                || prefetch_mir(tcx),
+                || {
+                    if !tcx.sess.opts.output_types.is_metadata_interface() {
+                        tcx.exported_symbols(LOCAL_CRATE);
+                    }
+                },
+            );
         },
     );
 }
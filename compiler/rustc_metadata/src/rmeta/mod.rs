@@ -297,6 +297,9 @@ pub(crate) struct CrateRoot {
     panic_runtime: bool,
     profiler_runtime: bool,
     symbol_mangling_version: SymbolManglingVersion,
+    /// Set if this was emitted with `--emit=metadata-interface`, in which case it can only be
+    /// used to type check other crates, not to link them.
+    is_metadata_interface: bool,
 }
 
 /// On-disk representation of `DefId`.
@@ -428,6 +431,7 @@ fn encode(&self, buf: &mut FileEncoder) -> LazyTables {
     optimized_mir: Table<DefIndex, LazyValue<mir::Body<'static>>>,
     mir_for_ctfe: Table<DefIndex, LazyValue<mir::Body<'static>>>,
     cross_crate_inlinable: Table<DefIndex, bool>,
+    mir_summary: Table<DefIndex, LazyValue<mir::MirSummary>>,
     closure_saved_names_of_captured_variables: Table<DefIndex, LazyValue<IndexVec<FieldIdx, Symbol>>>,
     mir_coroutine_witnesses: Table<DefIndex, LazyValue<mir::CoroutineLayout<'static>>>,
     promoted_mir: Table<DefIndex, LazyValue<IndexVec<mir::Promoted, mir::Body<'static>>>>,
@@ -14,9 +14,15 @@ middle_assert_divide_by_zero =
 
 middle_assert_gen_resume_after_panic = `gen` fn or block cannot be further iterated on after it panicked
 
+middle_assert_invalid_value =
+    invalid value {$value} read through a pointer or transmuted into a type that does not allow it
+
 middle_assert_misaligned_ptr_deref =
     misaligned pointer dereference: address must be a multiple of {$required} but is {$found}
 
+middle_assert_null_ptr_deref =
+    null pointer dereference occurred
+
 middle_assert_op_overflow =
     attempt to compute `{$left} {$op} {$right}`, which would overflow
 
@@ -32,6 +38,9 @@ middle_assert_shl_overflow =
 middle_assert_shr_overflow =
     attempt to shift right by `{$val}`, which would overflow
 
+middle_assert_slice_from_raw_parts_overflow =
+    slice::from_raw_parts called with {$len} elements of {$size} bytes, which is more than isize::MAX bytes
+
 middle_bounds_check =
     index out of bounds: the length is {$len} but the index is {$index}
 
@@ -8,9 +8,9 @@
 
 pub use rustc_query_system::dep_graph::debug::EdgeFilter;
 pub use rustc_query_system::dep_graph::{
-    debug::DepNodeFilter, hash_result, DepContext, DepGraphQuery, DepNodeColor, DepNodeIndex, Deps,
-    SerializedDepGraph, SerializedDepNodeIndex, TaskDeps, TaskDepsRef, WorkProduct, WorkProductId,
-    WorkProductMap,
+    debug::DepNodeFilter, hash_result, CguTiming, DepContext, DepGraphQuery, DepNodeColor,
+    DepNodeIndex, Deps, SerializedDepGraph, SerializedDepNodeIndex, TaskDeps, TaskDepsRef,
+    WorkProduct, WorkProductId, WorkProductMap,
 };
 
 pub use dep_node::{dep_kinds, label_strs, DepKind, DepNode, DepNodeExt};
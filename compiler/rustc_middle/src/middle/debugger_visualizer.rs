@@ -6,6 +6,10 @@
 pub enum DebuggerVisualizerType {
     Natvis,
     GdbPrettyPrinter,
+    /// A Python script that is imported into LLDB.
+    LldbPythonScript,
+    /// A list of LLDB commands, like `type summary add`, that is run by LLDB.
+    LldbTypeSummary,
 }
 
 /// A single debugger visualizer file.
@@ -64,6 +64,14 @@ pub fn size_estimate(&self, tcx: TyCtxt<'tcx>) -> usize {
                 match instance.def {
                     // "Normal" functions size estimate: the number of
                     // statements, plus one for the terminator.
+                    // Upstream items may have this precomputed in their crate metadata,
+                    // which saves decoding the MIR.
+                    InstanceDef::Item(def_id)
+                        if !def_id.is_local()
+                            && let Some(summary) = tcx.mir_summary(def_id) =>
+                    {
+                        summary.size_estimate
+                    }
                     InstanceDef::Item(..) | InstanceDef::DropGlue(..) => {
                         let mir = tcx.instance_mir(instance.def);
                         mir.basic_blocks.iter().map(|bb| bb.statements.len() + 1).sum()
@@ -247,6 +255,10 @@ pub struct CodegenUnit<'tcx> {
     /// True if this is CGU is used to hold code coverage information for dead code,
     /// false otherwise.
     is_code_coverage_dead_code_cgu: bool,
+    /// The CGUs this one was merged from, with their size estimates before merging. Only
+    /// recorded with `-Z cgu-partitioning=timing-guided`, to attribute the backend time of
+    /// this CGU to them in the next incremental session.
+    initial_cgus: Vec<(Symbol, usize)>,
 }
 
 /// Auxiliary info about a `MonoItem`.
@@ -288,6 +300,45 @@ pub enum Visibility {
     Protected,
 }
 
+/// The call graph between the functions codegened in the local crate, as used by
+/// `-Z stack-depth-report`. Functions are identified by their symbol names so that the
+/// graph can be matched against the stack sizes found in the object files after codegen.
+#[derive(Clone, Debug, Default, Encodable, Decodable)]
+pub struct MonoCallGraph {
+    pub functions: Vec<MonoCallGraphNode>,
+}
+
+#[derive(Clone, Debug, Encodable, Decodable)]
+pub struct MonoCallGraphNode {
+    pub symbol_name: String,
+    /// The human readable path of the function.
+    pub name: String,
+    /// Set if the function can be entered without being called from Rust code.
+    pub entry_kind: Option<StackDepthEntryKind>,
+    pub callees: Vec<MonoCallee>,
+}
+
+#[derive(Copy, Clone, Debug, Encodable, Decodable)]
+pub enum StackDepthEntryKind {
+    /// The `main` function of a binary.
+    Main,
+    /// A function using one of the interrupt ABIs.
+    Interrupt,
+    /// A `#[no_mangle]` or `#[export_name]` function.
+    Export,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Encodable, Decodable)]
+pub enum MonoCallee {
+    /// A direct call to the function with the given symbol name. The function may be
+    /// defined in another crate, in which case it is not part of the graph.
+    Direct(String),
+    /// A call through a `dyn Trait` vtable to the named method.
+    Virtual(String),
+    /// A call through a function pointer of the given type.
+    FnPtr(String),
+}
+
 impl<'tcx> CodegenUnit<'tcx> {
     #[inline]
     pub fn new(name: Symbol) -> CodegenUnit<'tcx> {
@@ -297,6 +348,7 @@ pub fn new(name: Symbol) -> CodegenUnit<'tcx> {
             size_estimate: 0,
             primary: false,
             is_code_coverage_dead_code_cgu: false,
+            initial_cgus: Vec::new(),
         }
     }
 
@@ -335,6 +387,14 @@ pub fn make_code_coverage_dead_code_cgu(&mut self) {
         self.is_code_coverage_dead_code_cgu = true;
     }
 
+    pub fn initial_cgus(&self) -> &[(Symbol, usize)] {
+        &self.initial_cgus
+    }
+
+    pub fn set_initial_cgus(&mut self, initial_cgus: Vec<(Symbol, usize)>) {
+        self.initial_cgus = initial_cgus;
+    }
+
     pub fn mangle_name(human_readable_name: &str) -> String {
         // We generate a 80 bit hash from the name. This should be enough to
         // avoid collisions and is still reasonably short for filenames.
@@ -434,6 +494,8 @@ fn hash_stable(&self, hcx: &mut StableHashingContext<'a>, hasher: &mut StableHas
             size_estimate: _,
             primary: _,
             is_code_coverage_dead_code_cgu,
+            // Only used to guide the partitioning of later sessions
+            initial_cgus: _,
         } = *self;
 
         name.hash_stable(hcx, hasher);
@@ -251,6 +251,25 @@ pub struct ConstQualifs {
     pub tainted_by_errors: Option<ErrorGuaranteed>,
 }
 
+/// The result of the `mir_summary` query.
+///
+/// A summary of an item's `optimized_mir` that is cheap to store in crate metadata, so that
+/// downstream crates can make MIR inlining and CGU partitioning decisions about the item without
+/// decoding its body. The numbers are computed on the generic body, so they do not depend on the
+/// generic arguments of any particular instance.
+#[derive(Clone, Copy, Debug, TyEncodable, TyDecodable, HashStable)]
+pub struct MirSummary {
+    /// The number of basic blocks in the body.
+    pub basic_blocks: usize,
+    /// The number of statements plus one per terminator, which is what
+    /// `MonoItem::size_estimate` computes for the body.
+    pub size_estimate: usize,
+    /// A lower bound of the inlining cost that the MIR inliner computes for any instance of
+    /// the body. A callsite can be rejected without looking at the body if this is already
+    /// above the inlining threshold.
+    pub min_inline_cost: usize,
+}
+
 /// After we borrow check a closure, we are left with various
 /// requirements that we have inferred between the free regions that
 /// appear in the closure's signature or on its field types. These
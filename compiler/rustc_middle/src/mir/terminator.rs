@@ -129,8 +129,9 @@ pub fn is_optional_overflow_check(&self) -> bool {
 
     /// Get the message that is printed at runtime when this assertion fails.
     ///
-    /// The caller is expected to handle `BoundsCheck` and `MisalignedPointerDereference` by
-    /// invoking the appropriate lang item (panic_bounds_check/panic_misaligned_pointer_dereference)
+    /// The caller is expected to handle `BoundsCheck`, `MisalignedPointerDereference`,
+    /// `NullPointerDereference`, `InvalidValue` and `SliceFromRawPartsOverflow` by invoking the
+    /// appropriate lang item (panic_bounds_check/panic_misaligned_pointer_dereference/...)
     /// instead of printing a static message.
     pub fn description(&self) -> &'static str {
         use AssertKind::*;
@@ -157,7 +158,11 @@ pub fn description(&self) -> &'static str {
                 "`gen fn` should just keep returning `None` after panicking"
             }
 
-            BoundsCheck { .. } | MisalignedPointerDereference { .. } => {
+            BoundsCheck { .. }
+            | MisalignedPointerDereference { .. }
+            | NullPointerDereference
+            | InvalidValue { .. }
+            | SliceFromRawPartsOverflow { .. } => {
                 bug!("Unexpected AssertKind")
             }
         }
@@ -220,6 +225,19 @@ pub fn fmt_assert_args<W: fmt::Write>(&self, f: &mut W) -> fmt::Result
                     "\"misaligned pointer dereference: address must be a multiple of {{}} but is {{}}\", {required:?}, {found:?}"
                 )
             }
+            NullPointerDereference => write!(f, "\"null pointer dereference occurred\""),
+            InvalidValue { value } => {
+                write!(
+                    f,
+                    "\"invalid value {{}} read through a pointer or transmuted into a type that does not allow it\", {value:?}"
+                )
+            }
+            SliceFromRawPartsOverflow { len, size } => {
+                write!(
+                    f,
+                    "\"slice::from_raw_parts called with {{}} elements of {{}} bytes, which is more than isize::MAX bytes\", {len:?}, {size:?}"
+                )
+            }
             _ => write!(f, "\"{}\"", self.description()),
         }
     }
@@ -256,6 +274,9 @@ pub fn diagnostic_message(&self) -> DiagnosticMessage {
             }
 
             MisalignedPointerDereference { .. } => middle_assert_misaligned_ptr_deref,
+            NullPointerDereference => middle_assert_null_ptr_deref,
+            InvalidValue { .. } => middle_assert_invalid_value,
+            SliceFromRawPartsOverflow { .. } => middle_assert_slice_from_raw_parts_overflow,
         }
     }
 
@@ -287,11 +308,18 @@ macro_rules! add {
                 add!("left", format!("{left:#?}"));
                 add!("right", format!("{right:#?}"));
             }
-            ResumedAfterReturn(_) | ResumedAfterPanic(_) => {}
+            ResumedAfterReturn(_) | ResumedAfterPanic(_) | NullPointerDereference => {}
             MisalignedPointerDereference { required, found } => {
                 add!("required", format!("{required:#?}"));
                 add!("found", format!("{found:#?}"));
             }
+            InvalidValue { value } => {
+                add!("value", format!("{value:#?}"));
+            }
+            SliceFromRawPartsOverflow { len, size } => {
+                add!("len", format!("{len:#?}"));
+                add!("size", format!("{size:#?}"));
+            }
         }
     }
 }
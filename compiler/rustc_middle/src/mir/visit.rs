@@ -618,13 +618,20 @@ fn super_assert_message(&mut self,
                     OverflowNeg(op) | DivisionByZero(op) | RemainderByZero(op) => {
                         self.visit_operand(op, location);
                     }
-                    ResumedAfterReturn(_) | ResumedAfterPanic(_) => {
+                    ResumedAfterReturn(_) | ResumedAfterPanic(_) | NullPointerDereference => {
                         // Nothing to visit
                     }
                     MisalignedPointerDereference { required, found } => {
                         self.visit_operand(required, location);
                         self.visit_operand(found, location);
                     }
+                    InvalidValue { value } => {
+                        self.visit_operand(value, location);
+                    }
+                    SliceFromRawPartsOverflow { len, size } => {
+                        self.visit_operand(len, location);
+                        self.visit_operand(size, location);
+                    }
                 }
             }
 
@@ -23,7 +23,7 @@
     EvalToAllocationRawResult, EvalToConstValueResult, EvalToValTreeResult,
 };
 use crate::mir::interpret::{LitToConstError, LitToConstInput};
-use crate::mir::mono::CodegenUnit;
+use crate::mir::mono::{CodegenUnit, MonoCallGraph};
 use crate::query::erase::{erase, restore, Erase};
 use crate::query::plumbing::{
     query_ensure, query_ensure_error_guaranteed, query_get_at, CyclePlaceholder, DynamicQuery,
@@ -576,6 +576,15 @@
         separate_provide_extern
     }
 
+    /// Size and inlining cost summary of the `optimized_mir` of an item.
+    ///
+    /// For upstream items this is only available if the defining crate was compiled with
+    /// `-Zencode-mir-summaries`, which lets callers avoid decoding the MIR itself.
+    query mir_summary(key: DefId) -> Option<mir::MirSummary> {
+        desc { |tcx| "summarizing the optimized MIR of `{}`", tcx.def_path_str(key) }
+        separate_provide_extern
+    }
+
     /// Summarizes coverage IDs inserted by the `InstrumentCoverage` MIR pass
     /// (for compiler option `-Cinstrument-coverage`), after MIR optimizations
     /// have had a chance to potentially remove some of them.
@@ -1887,6 +1896,17 @@
         desc { "getting codegen unit `{sym}`" }
     }
 
+    /// The call graph between all functions codegened in the local crate, used by
+    /// `-Z stack-depth-report`. It is only fed by `collect_and_partition_mono_items`, from the
+    /// output of the mono item collector, so it must not be called before that query.
+    query mono_call_graph(_: ()) -> &'tcx MonoCallGraph {
+        feedable
+        eval_always
+        arena_cache
+        no_hash
+        desc { "building the call graph of monomorphized functions" }
+    }
+
     query unused_generic_params(key: ty::InstanceDef<'tcx>) -> UnusedGenericParams {
         cache_on_disk_if { key.def_id().is_local() }
         desc {
@@ -61,6 +61,7 @@ impl $crate::ty::ParameterizedOverTcx for $ty {
     crate::middle::exported_symbols::SymbolExportInfo,
     crate::middle::resolve_bound_vars::ObjectLifetimeDefault,
     crate::mir::ConstQualifs,
+    crate::mir::MirSummary,
     ty::AssocItemContainer,
     ty::Asyncness,
     ty::DeducedParamAttrs,
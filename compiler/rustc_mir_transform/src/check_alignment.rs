@@ -132,7 +132,7 @@ fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, location:
     }
 }
 
-fn split_block(
+pub(super) fn split_block(
     basic_blocks: &mut IndexVec<BasicBlock, BasicBlockData<'_>>,
     location: Location,
 ) -> BasicBlock {
@@ -0,0 +1,517 @@
+//! Inserts the runtime checks of `-Z ub-checks`.
+//!
+//! Like `CheckAlignment`, every check splits the block right before the statement or terminator
+//! it guards and ends the first half with an `Assert` that calls a `#[rustc_nounwind]` panic
+//! function when the precondition doesn't hold.
+
+use crate::check_alignment::split_block;
+use crate::MirPass;
+use rustc_const_eval::util;
+use rustc_hir::lang_items::LangItem;
+use rustc_middle::mir::*;
+use rustc_middle::mir::{
+    interpret::Scalar,
+    visit::{MutatingUseContext, NonMutatingUseContext, PlaceContext, Visitor},
+};
+use rustc_middle::ty::layout::IntegerExt;
+use rustc_middle::ty::{ParamEnv, Ty, TyCtxt, TypeAndMut};
+use rustc_session::config::UbChecks;
+use rustc_session::Session;
+use rustc_span::sym;
+use rustc_target::abi::{Abi, FieldIdx, Primitive, Scalar as ScalarAbi};
+
+pub struct CheckUb;
+
+impl<'tcx> MirPass<'tcx> for CheckUb {
+    fn is_enabled(&self, sess: &Session) -> bool {
+        sess.opts.unstable_opts.ub_checks.any()
+    }
+
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        // This pass emits new panics. If for whatever reason we do not have a panic
+        // implementation, running this pass may cause otherwise-valid code to not compile.
+        if tcx.lang_items().get(LangItem::PanicImpl).is_none() {
+            return;
+        }
+
+        let checks = tcx.sess.opts.unstable_opts.ub_checks;
+        let basic_blocks = body.basic_blocks.as_mut();
+        let local_decls = &mut body.local_decls;
+        let param_env = tcx.param_env_reveal_all_normalized(body.source.def_id());
+
+        // Like `CheckAlignment`, we iterate backwards so that inserting new blocks never changes
+        // the Location of a statement that we still have to visit.
+        for block in (0..basic_blocks.len()).rev() {
+            let block = block.into();
+
+            if checks.from_raw_parts
+                && let Some((len, elem_ty, source_info)) =
+                    slice_from_raw_parts_call(tcx, basic_blocks[block].terminator())
+            {
+                let location =
+                    Location { block, statement_index: basic_blocks[block].statements.len() };
+                debug!("Inserting from_raw_parts check for {:?}", elem_ty);
+                let new_block = split_block(basic_blocks, location);
+                insert_from_raw_parts_check(
+                    tcx,
+                    local_decls,
+                    &mut basic_blocks[block],
+                    len,
+                    elem_ty,
+                    source_info,
+                    new_block,
+                );
+            }
+
+            for statement_index in (0..basic_blocks[block].statements.len()).rev() {
+                let location = Location { block, statement_index };
+                let statement = &basic_blocks[block].statements[statement_index];
+                let source_info = statement.source_info;
+
+                let mut finder =
+                    UbFinder { tcx, local_decls, param_env, checks, found: Vec::new() };
+                finder.visit_statement(statement, location);
+
+                // The checks that are inserted last are executed first, so the finder pushes the
+                // null check of a place after the check of the value loaded from it.
+                for check in finder.found {
+                    debug!("Inserting UB check {:?}", check);
+                    let new_block = split_block(basic_blocks, location);
+                    let block_data = &mut basic_blocks[block];
+                    match check {
+                        UbCheck::NonNull(pointer) => insert_null_check(
+                            tcx,
+                            local_decls,
+                            block_data,
+                            pointer,
+                            source_info,
+                            new_block,
+                        ),
+                        UbCheck::ValidLoad(place, ty, scalar) => {
+                            let bits =
+                                load_bits(tcx, local_decls, block_data, place, scalar, source_info);
+                            insert_validity_check(
+                                tcx,
+                                local_decls,
+                                block_data,
+                                bits,
+                                ty,
+                                scalar,
+                                source_info,
+                                new_block,
+                            );
+                        }
+                        UbCheck::ValidTransmute(operand, ty, scalar) => {
+                            let bits = transmute_bits(
+                                tcx,
+                                local_decls,
+                                block_data,
+                                operand,
+                                scalar,
+                                source_info,
+                            );
+                            insert_validity_check(
+                                tcx,
+                                local_decls,
+                                block_data,
+                                bits,
+                                ty,
+                                scalar,
+                                source_info,
+                                new_block,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+enum UbCheck<'tcx> {
+    /// The pointer the place is based on must not be null.
+    NonNull(Place<'tcx>),
+    /// The value read from the place must be valid for its type.
+    ValidLoad(Place<'tcx>, Ty<'tcx>, ScalarAbi),
+    /// The operand must be a valid value of the type it is transmuted into.
+    ValidTransmute(Operand<'tcx>, Ty<'tcx>, ScalarAbi),
+}
+
+struct UbFinder<'tcx, 'a> {
+    tcx: TyCtxt<'tcx>,
+    local_decls: &'a LocalDecls<'tcx>,
+    param_env: ParamEnv<'tcx>,
+    checks: UbChecks,
+    found: Vec<UbCheck<'tcx>>,
+}
+
+impl<'tcx, 'a> UbFinder<'tcx, 'a> {
+    /// Returns the scalar of the layout of `ty` if only some of its bit patterns are valid, like
+    /// for `bool`, `char` and field-less enums.
+    fn restricted_scalar(&self, ty: Ty<'tcx>) -> Option<ScalarAbi> {
+        let layout = self.tcx.layout_of(self.param_env.and(ty)).ok()?;
+        let Abi::Scalar(scalar) = layout.abi else { return None };
+        // We can only check integers, and only if the whole value is the scalar.
+        if !matches!(scalar.primitive(), Primitive::Int(..))
+            || layout.size != scalar.size(&self.tcx)
+        {
+            return None;
+        }
+        if scalar.is_always_valid(&self.tcx) {
+            return None;
+        }
+        Some(scalar)
+    }
+
+    /// Returns the raw pointer an indirect place is based on.
+    fn unsafe_pointer(&self, place: &Place<'tcx>) -> Option<(Place<'tcx>, Ty<'tcx>)> {
+        if !place.is_indirect() {
+            return None;
+        }
+
+        // Since Deref projections must come first and only once, the pointer for an indirect place
+        // is the Local that the Place is based on.
+        let pointer_ty = self.local_decls[place.local].ty;
+        if !pointer_ty.is_unsafe_ptr() {
+            trace!("Indirect, but not based on an unsafe ptr, not checking {:?}", place);
+            return None;
+        }
+        let pointee_ty =
+            pointer_ty.builtin_deref(true).expect("no builtin_deref for an unsafe pointer").ty;
+        Some((Place::from(place.local), pointee_ty))
+    }
+
+    /// Checks that the value read from `place` is valid for its type, if the type restricts it.
+    fn check_load(&mut self, place: &Place<'tcx>) {
+        let ty = place.ty(self.local_decls, self.tcx).ty;
+        let Some(scalar) = self.restricted_scalar(ty) else { return };
+
+        // The check reads the place again as an integer, with an aligned load. That load would
+        // itself be UB for the field of a packed struct, so those aren't checked.
+        if util::is_disaligned(self.tcx, self.local_decls, self.param_env, *place) {
+            debug!("Place may be unaligned, not checking the load from {:?}", place);
+            return;
+        }
+
+        self.found.push(UbCheck::ValidLoad(*place, ty, scalar));
+    }
+}
+
+impl<'tcx, 'a> Visitor<'tcx> for UbFinder<'tcx, 'a> {
+    fn visit_rvalue(&mut self, rvalue: &Rvalue<'tcx>, location: Location) {
+        match rvalue {
+            // Reading the discriminant of a field-less enum loads the whole value.
+            Rvalue::Discriminant(place) if self.checks.loads => {
+                if self.unsafe_pointer(place).is_some() {
+                    self.check_load(place);
+                }
+            }
+            Rvalue::Cast(CastKind::Transmute, operand, ty) if self.checks.transmute => {
+                if let Some(scalar) = self.restricted_scalar(*ty) {
+                    self.found.push(UbCheck::ValidTransmute(operand.to_copy(), *ty, scalar));
+                }
+            }
+            _ => {}
+        }
+
+        self.super_rvalue(rvalue, location);
+    }
+
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, location: Location) {
+        let Some((pointer, pointee_ty)) = self.unsafe_pointer(place) else { return };
+
+        let is_read = matches!(
+            context,
+            PlaceContext::NonMutatingUse(NonMutatingUseContext::Copy | NonMutatingUseContext::Move)
+        );
+        if self.checks.loads && is_read {
+            self.check_load(place);
+        }
+
+        // Unlike `CheckAlignment`, creating a reference to a null place is also checked, as it is
+        // just as immediately UB. `addr_of!` is still allowed.
+        let is_deref = match context {
+            PlaceContext::MutatingUse(
+                MutatingUseContext::Store
+                | MutatingUseContext::AsmOutput
+                | MutatingUseContext::Call
+                | MutatingUseContext::Yield
+                | MutatingUseContext::Drop
+                | MutatingUseContext::Borrow,
+            ) => true,
+            PlaceContext::NonMutatingUse(
+                NonMutatingUseContext::Copy
+                | NonMutatingUseContext::Move
+                | NonMutatingUseContext::Inspect
+                | NonMutatingUseContext::SharedBorrow
+                | NonMutatingUseContext::ShallowBorrow,
+            ) => true,
+            _ => false,
+        };
+        if self.checks.null && is_deref {
+            // Accessing a zero-sized place through a null pointer is never a memory access. Places
+            // whose layout depends on generic parameters are not checked for the same reason.
+            match self.tcx.layout_of(self.param_env.and(pointee_ty)) {
+                Ok(layout) if !layout.is_zst() => self.found.push(UbCheck::NonNull(pointer)),
+                _ => debug!("Unsafe pointer, but pointee may be zero-sized: {:?}", pointee_ty),
+            }
+        }
+
+        self.super_place(place, context, location);
+    }
+}
+
+/// Returns the length operand and the element type of a call to `slice::from_raw_parts` or
+/// `slice::from_raw_parts_mut`.
+fn slice_from_raw_parts_call<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    terminator: &Terminator<'tcx>,
+) -> Option<(Operand<'tcx>, Ty<'tcx>, SourceInfo)> {
+    let TerminatorKind::Call { func, args, .. } = &terminator.kind else { return None };
+    let (def_id, generic_args) = func.const_fn_def()?;
+    let name = tcx.get_diagnostic_name(def_id)?;
+    if name != sym::slice_from_raw_parts && name != sym::slice_from_raw_parts_mut {
+        return None;
+    }
+    let [_data, len] = &args[..] else { return None };
+    Some((len.to_copy(), generic_args.type_at(0), terminator.source_info))
+}
+
+fn new_temp<'tcx>(
+    local_decls: &mut LocalDecls<'tcx>,
+    ty: Ty<'tcx>,
+    source_info: SourceInfo,
+) -> Place<'tcx> {
+    local_decls.push(LocalDecl::with_source_info(ty, source_info)).into()
+}
+
+fn push_assign<'tcx>(
+    block_data: &mut BasicBlockData<'tcx>,
+    source_info: SourceInfo,
+    place: Place<'tcx>,
+    rvalue: Rvalue<'tcx>,
+) {
+    block_data
+        .statements
+        .push(Statement { source_info, kind: StatementKind::Assign(Box::new((place, rvalue))) });
+}
+
+fn uint_const<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    source_info: SourceInfo,
+    value: u128,
+    ty: Ty<'tcx>,
+) -> Operand<'tcx> {
+    let size = tcx.layout_of(ParamEnv::reveal_all().and(ty)).unwrap().size;
+    Operand::Constant(Box::new(ConstOperand {
+        span: source_info.span,
+        user_ty: None,
+        const_: Const::Val(ConstValue::Scalar(Scalar::from_uint(value, size)), ty),
+    }))
+}
+
+/// Ends `block_data` with an assert that continues to `new_block` if `is_ok` is true.
+fn assert<'tcx>(
+    block_data: &mut BasicBlockData<'tcx>,
+    is_ok: Place<'tcx>,
+    msg: AssertKind<Operand<'tcx>>,
+    source_info: SourceInfo,
+    new_block: BasicBlock,
+) {
+    block_data.terminator = Some(Terminator {
+        source_info,
+        kind: TerminatorKind::Assert {
+            cond: Operand::Copy(is_ok),
+            expected: true,
+            target: new_block,
+            msg: Box::new(msg),
+            // The panic functions of these checks are #[rustc_nounwind]: like for
+            // `CheckAlignment`, we never want to insert an unwind into unsafe code.
+            unwind: UnwindAction::Unreachable,
+        },
+    });
+}
+
+fn insert_null_check<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    local_decls: &mut LocalDecls<'tcx>,
+    block_data: &mut BasicBlockData<'tcx>,
+    pointer: Place<'tcx>,
+    source_info: SourceInfo,
+    new_block: BasicBlock,
+) {
+    // Cast the pointer to a *const ()
+    let const_raw_ptr = Ty::new_ptr(tcx, TypeAndMut { ty: tcx.types.unit, mutbl: Mutability::Not });
+    let thin_ptr = new_temp(local_decls, const_raw_ptr, source_info);
+    let rvalue = Rvalue::Cast(CastKind::PtrToPtr, Operand::Copy(pointer), const_raw_ptr);
+    push_assign(block_data, source_info, thin_ptr, rvalue);
+
+    // Transmute the pointer to a usize (equivalent to `ptr.addr()`)
+    let addr = new_temp(local_decls, tcx.types.usize, source_info);
+    let rvalue = Rvalue::Cast(CastKind::Transmute, Operand::Copy(thin_ptr), tcx.types.usize);
+    push_assign(block_data, source_info, addr, rvalue);
+
+    // Check that the address isn't zero
+    let is_ok = new_temp(local_decls, tcx.types.bool, source_info);
+    let zero = uint_const(tcx, source_info, 0, tcx.types.usize);
+    let rvalue = Rvalue::BinaryOp(BinOp::Ne, Box::new((Operand::Copy(addr), zero)));
+    push_assign(block_data, source_info, is_ok, rvalue);
+
+    assert(block_data, is_ok, AssertKind::NullPointerDereference, source_info, new_block);
+}
+
+/// Reads the bits of the scalar behind `place` as an unsigned integer, without asserting that
+/// they are valid for the type of the place.
+fn load_bits<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    local_decls: &mut LocalDecls<'tcx>,
+    block_data: &mut BasicBlockData<'tcx>,
+    place: Place<'tcx>,
+    scalar: ScalarAbi,
+    source_info: SourceInfo,
+) -> Place<'tcx> {
+    let Primitive::Int(integer, _) = scalar.primitive() else { bug!() };
+    let bits_ty = integer.to_ty(tcx, false);
+    let place_ty = place.ty(&*local_decls, tcx).ty;
+
+    // Take the address of the place and cast it to a pointer to an unsigned integer
+    let place_ptr_ty = Ty::new_ptr(tcx, TypeAndMut { ty: place_ty, mutbl: Mutability::Not });
+    let place_ptr = new_temp(local_decls, place_ptr_ty, source_info);
+    push_assign(block_data, source_info, place_ptr, Rvalue::AddressOf(Mutability::Not, place));
+
+    let bits_ptr_ty = Ty::new_ptr(tcx, TypeAndMut { ty: bits_ty, mutbl: Mutability::Not });
+    let bits_ptr = new_temp(local_decls, bits_ptr_ty, source_info);
+    let rvalue = Rvalue::Cast(CastKind::PtrToPtr, Operand::Copy(place_ptr), bits_ptr_ty);
+    push_assign(block_data, source_info, bits_ptr, rvalue);
+
+    // Read the bits through the cast pointer
+    let bits = new_temp(local_decls, bits_ty, source_info);
+    let rvalue = Rvalue::Use(Operand::Copy(tcx.mk_place_deref(bits_ptr)));
+    push_assign(block_data, source_info, bits, rvalue);
+    bits
+}
+
+/// Transmutes `operand` into an unsigned integer of the size of `scalar`.
+fn transmute_bits<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    local_decls: &mut LocalDecls<'tcx>,
+    block_data: &mut BasicBlockData<'tcx>,
+    operand: Operand<'tcx>,
+    scalar: ScalarAbi,
+    source_info: SourceInfo,
+) -> Place<'tcx> {
+    let Primitive::Int(integer, _) = scalar.primitive() else { bug!() };
+    let bits_ty = integer.to_ty(tcx, false);
+
+    let bits = new_temp(local_decls, bits_ty, source_info);
+    push_assign(block_data, source_info, bits, Rvalue::Cast(CastKind::Transmute, operand, bits_ty));
+    bits
+}
+
+/// Asserts that `bits`, an unsigned integer of the size of `scalar`, is a valid value of `ty`.
+fn insert_validity_check<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    local_decls: &mut LocalDecls<'tcx>,
+    block_data: &mut BasicBlockData<'tcx>,
+    bits: Place<'tcx>,
+    ty: Ty<'tcx>,
+    scalar: ScalarAbi,
+    source_info: SourceInfo,
+    new_block: BasicBlock,
+) {
+    let bits_ty = bits.ty(&*local_decls, tcx).ty;
+    let size = scalar.size(&tcx);
+    let valid_range = scalar.valid_range(&tcx);
+
+    // The valid range may wrap around, so check `bits.wrapping_sub(start) <= end - start`
+    let offset = new_temp(local_decls, bits_ty, source_info);
+    let start = uint_const(tcx, source_info, valid_range.start, bits_ty);
+    let rvalue = Rvalue::BinaryOp(BinOp::Sub, Box::new((Operand::Copy(bits), start)));
+    push_assign(block_data, source_info, offset, rvalue);
+
+    let mut is_ok = new_temp(local_decls, tcx.types.bool, source_info);
+    let range_len = size.truncate(valid_range.end.wrapping_sub(valid_range.start));
+    let range_len = uint_const(tcx, source_info, range_len, bits_ty);
+    let rvalue = Rvalue::BinaryOp(BinOp::Le, Box::new((Operand::Copy(offset), range_len)));
+    push_assign(block_data, source_info, is_ok, rvalue);
+
+    // The surrogate code points are not part of the valid range of `char`, but aren't valid
+    // either: check `bits & 0xFFFF_F800 != 0xD800`
+    if ty == tcx.types.char {
+        let masked = new_temp(local_decls, bits_ty, source_info);
+        let mask = uint_const(tcx, source_info, 0xFFFF_F800, bits_ty);
+        let rvalue = Rvalue::BinaryOp(BinOp::BitAnd, Box::new((Operand::Copy(bits), mask)));
+        push_assign(block_data, source_info, masked, rvalue);
+
+        let not_surrogate = new_temp(local_decls, tcx.types.bool, source_info);
+        let surrogates = uint_const(tcx, source_info, 0xD800, bits_ty);
+        let rvalue = Rvalue::BinaryOp(BinOp::Ne, Box::new((Operand::Copy(masked), surrogates)));
+        push_assign(block_data, source_info, not_surrogate, rvalue);
+
+        let is_valid_char = new_temp(local_decls, tcx.types.bool, source_info);
+        let rvalue = Rvalue::BinaryOp(
+            BinOp::BitAnd,
+            Box::new((Operand::Copy(is_ok), Operand::Copy(not_surrogate))),
+        );
+        push_assign(block_data, source_info, is_valid_char, rvalue);
+        is_ok = is_valid_char;
+    }
+
+    // The panic function takes the value as a u128
+    let value = new_temp(local_decls, tcx.types.u128, source_info);
+    let rvalue = Rvalue::Cast(CastKind::IntToInt, Operand::Copy(bits), tcx.types.u128);
+    push_assign(block_data, source_info, value, rvalue);
+
+    let msg = AssertKind::InvalidValue { value: Operand::Copy(value) };
+    assert(block_data, is_ok, msg, source_info, new_block);
+}
+
+fn insert_from_raw_parts_check<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    local_decls: &mut LocalDecls<'tcx>,
+    block_data: &mut BasicBlockData<'tcx>,
+    len: Operand<'tcx>,
+    elem_ty: Ty<'tcx>,
+    source_info: SourceInfo,
+    new_block: BasicBlock,
+) {
+    let usize_ty = tcx.types.usize;
+
+    // Get the size of the elements
+    let size = new_temp(local_decls, usize_ty, source_info);
+    push_assign(block_data, source_info, size, Rvalue::NullaryOp(NullOp::SizeOf, elem_ty));
+
+    // Multiply the length by the size, keeping track of overflows
+    let checked_ty = Ty::new_tup(tcx, &[usize_ty, tcx.types.bool]);
+    let checked = new_temp(local_decls, checked_ty, source_info);
+    let rvalue = Rvalue::CheckedBinaryOp(BinOp::Mul, Box::new((len.clone(), Operand::Copy(size))));
+    push_assign(block_data, source_info, checked, rvalue);
+    let bytes = tcx.mk_place_field(checked, FieldIdx::from_u32(0), usize_ty);
+    let overflow = tcx.mk_place_field(checked, FieldIdx::from_u32(1), tcx.types.bool);
+
+    // The slice must not be larger than isize::MAX bytes
+    let fits = new_temp(local_decls, tcx.types.bool, source_info);
+    let isize_max = tcx.data_layout.pointer_size.signed_int_max() as u128;
+    let isize_max = uint_const(tcx, source_info, isize_max, usize_ty);
+    let rvalue = Rvalue::BinaryOp(BinOp::Le, Box::new((Operand::Copy(bytes), isize_max)));
+    push_assign(block_data, source_info, fits, rvalue);
+
+    let not_overflow = new_temp(local_decls, tcx.types.bool, source_info);
+    push_assign(
+        block_data,
+        source_info,
+        not_overflow,
+        Rvalue::UnaryOp(UnOp::Not, Operand::Copy(overflow)),
+    );
+
+    let is_ok = new_temp(local_decls, tcx.types.bool, source_info);
+    let rvalue = Rvalue::BinaryOp(
+        BinOp::BitAnd,
+        Box::new((Operand::Copy(not_overflow), Operand::Copy(fits))),
+    );
+    push_assign(block_data, source_info, is_ok, rvalue);
+
+    let msg = AssertKind::SliceFromRawPartsOverflow { len, size: Operand::Copy(size) };
+    assert(block_data, is_ok, msg, source_info, new_block);
+}
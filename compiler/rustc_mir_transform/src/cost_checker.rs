@@ -2,10 +2,10 @@
 use rustc_middle::mir::*;
 use rustc_middle::ty::{self, ParamEnv, Ty, TyCtxt};
 
-const INSTR_COST: usize = 5;
-const CALL_PENALTY: usize = 25;
-const LANDINGPAD_PENALTY: usize = 50;
-const RESUME_PENALTY: usize = 45;
+pub(crate) const INSTR_COST: usize = 5;
+pub(crate) const CALL_PENALTY: usize = 25;
+pub(crate) const LANDINGPAD_PENALTY: usize = 50;
+pub(crate) const RESUME_PENALTY: usize = 45;
 
 /// Verify that the callee body is compatible with the caller.
 #[derive(Clone)]
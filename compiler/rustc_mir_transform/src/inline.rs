@@ -182,6 +182,7 @@ fn try_inlining(
             }
         }
 
+        self.check_mir_summary(callsite, cross_crate_inlinable)?;
         let callee_body = try_instance_mir(self.tcx, callsite.callee.def)?;
         self.check_mir_body(callsite, callee_body, callee_attrs, cross_crate_inlinable)?;
 
@@ -450,6 +451,52 @@ fn check_codegen_attributes(
         Ok(())
     }
 
+    /// Computes the cost threshold below which a callee with `basic_blocks` blocks is inlined.
+    fn inline_threshold(&self, cross_crate_inlinable: bool, basic_blocks: usize) -> usize {
+        let mut threshold = if cross_crate_inlinable {
+            self.tcx.sess.opts.unstable_opts.inline_mir_hint_threshold.unwrap_or(100)
+        } else {
+            self.tcx.sess.opts.unstable_opts.inline_mir_threshold.unwrap_or(50)
+        };
+
+        // Give a bonus functions with a small number of blocks,
+        // We normally have two or three blocks for even
+        // very small functions.
+        if basic_blocks <= 3 {
+            threshold += threshold / 4;
+        }
+        threshold
+    }
+
+    /// Returns an early inlining decision for upstream callees whose crate encoded a
+    /// `MirSummary`, without decoding the callee MIR body.
+    ///
+    /// The summary only provides a lower bound of the cost, so this can only reject callsites.
+    /// Everything else is left to `check_mir_body`.
+    #[instrument(level = "debug", skip(self))]
+    fn check_mir_summary(
+        &self,
+        callsite: &CallSite<'tcx>,
+        cross_crate_inlinable: bool,
+    ) -> Result<(), &'static str> {
+        let InstanceDef::Item(def_id) = callsite.callee.def else { return Ok(()) };
+        if def_id.is_local() {
+            return Ok(());
+        }
+        let Some(summary) = self.tcx.mir_summary(def_id) else { return Ok(()) };
+
+        let threshold = self.inline_threshold(cross_crate_inlinable, summary.basic_blocks);
+        if summary.min_inline_cost > threshold {
+            debug!(
+                "NOT inlining {:?} [min cost={} > threshold={}]",
+                callsite, summary.min_inline_cost, threshold
+            );
+            Err("summarized cost above threshold")
+        } else {
+            Ok(())
+        }
+    }
+
     /// Returns inlining decision that is based on the examination of callee MIR body.
     /// Assumes that codegen attributes have been checked for compatibility already.
     #[instrument(level = "debug", skip(self, callee_body))]
@@ -462,18 +509,8 @@ fn check_mir_body(
     ) -> Result<(), &'static str> {
         let tcx = self.tcx;
 
-        let mut threshold = if cross_crate_inlinable {
-            self.tcx.sess.opts.unstable_opts.inline_mir_hint_threshold.unwrap_or(100)
-        } else {
-            self.tcx.sess.opts.unstable_opts.inline_mir_threshold.unwrap_or(50)
-        };
-
-        // Give a bonus functions with a small number of blocks,
-        // We normally have two or three blocks for even
-        // very small functions.
-        if callee_body.basic_blocks.len() <= 3 {
-            threshold += threshold / 4;
-        }
+        let threshold =
+            self.inline_threshold(cross_crate_inlinable, callee_body.basic_blocks.len());
         debug!("    final inline threshold = {}", threshold);
 
         // FIXME: Give a bonus to functions with only a single caller
@@ -89,6 +89,7 @@
 mod lower_intrinsics;
 mod lower_slice_len;
 mod match_branches;
+mod mir_summary;
 mod multiple_return_terminators;
 mod normalize_array_len;
 mod nrvo;
@@ -106,6 +107,7 @@
 mod ssa;
 // This pass is public to allow external drivers to perform MIR cleanup
 mod check_alignment;
+mod check_ub;
 pub mod simplify;
 mod simplify_branches;
 mod simplify_comparison_integral;
@@ -129,6 +131,7 @@ pub fn provide(providers: &mut Providers) {
     ffi_unwind_calls::provide(providers);
     shim::provide(providers);
     cross_crate_inline::provide(providers);
+    mir_summary::provide(providers);
     *providers = Providers {
         mir_keys,
         mir_const,
@@ -567,6 +570,7 @@ fn o1<T>(x: T) -> WithMinOptLevel<T> {
         body,
         &[
             &check_alignment::CheckAlignment,
+            &check_ub::CheckUb,
             &lower_slice_len::LowerSliceLenCalls, // has to be done before inlining, otherwise actual call will be almost always inlined. Also simple, so can just do first
             &inline::Inline,
             // Substitutions during inlining may introduce switch on enums with uninhabited branches.
@@ -0,0 +1,85 @@
+//! Computes the `mir_summary` of an item, a small digest of its `optimized_mir` that can be
+//! stored in crate metadata with `-Zencode-mir-summaries`.
+//!
+//! Downstream crates use the summary to reject MIR inlining candidates and to estimate the size
+//! of mono items without decoding the callee body.
+
+use crate::cost_checker::{CALL_PENALTY, INSTR_COST, LANDINGPAD_PENALTY, RESUME_PENALTY};
+use rustc_hir::def_id::LocalDefId;
+use rustc_index::bit_set::BitSet;
+use rustc_middle::mir::*;
+use rustc_middle::query::Providers;
+use rustc_middle::ty::{self, TyCtxt};
+
+pub fn provide(providers: &mut Providers) {
+    providers.mir_summary = mir_summary;
+}
+
+fn mir_summary(tcx: TyCtxt<'_>, def_id: LocalDefId) -> Option<MirSummary> {
+    if !tcx.is_mir_available(def_id) {
+        return None;
+    }
+
+    let body = tcx.optimized_mir(def_id);
+    Some(MirSummary {
+        basic_blocks: body.basic_blocks.len(),
+        size_estimate: body.basic_blocks.iter().map(|bb| bb.statements.len() + 1).sum(),
+        min_inline_cost: min_inline_cost(tcx, body),
+    })
+}
+
+/// Computes a lower bound of the cost that `Inliner::check_mir_body` assigns to any instance of
+/// `body`.
+///
+/// This walks the blocks the same way the inliner does. The only parts of the inliner's cost that
+/// depend on the generic arguments are whether a dropped place needs dropping and whether a
+/// callee resolves to an intrinsic, so those are counted at their cheapest here.
+fn min_inline_cost<'tcx>(tcx: TyCtxt<'tcx>, body: &Body<'tcx>) -> usize {
+    let mut cost = 0;
+    let mut work_list = vec![START_BLOCK];
+    let mut visited = BitSet::new_empty(body.basic_blocks.len());
+    while let Some(bb) = work_list.pop() {
+        if !visited.insert(bb.index()) {
+            continue;
+        }
+
+        let blk = &body.basic_blocks[bb];
+        for statement in &blk.statements {
+            match statement.kind {
+                StatementKind::StorageLive(_)
+                | StatementKind::StorageDead(_)
+                | StatementKind::Deinit(_)
+                | StatementKind::Nop => {}
+                _ => cost += INSTR_COST,
+            }
+        }
+
+        let term = blk.terminator();
+        if let TerminatorKind::Drop { target, .. } = term.kind {
+            // The place might not need dropping once instantiated, in which case the inliner
+            // treats this as a goto and never visits the cleanup block.
+            work_list.push(target);
+        } else {
+            work_list.extend(term.successors());
+        }
+
+        cost += match term.kind {
+            TerminatorKind::Call { func: Operand::Constant(ref f), unwind, .. } => {
+                let call_cost = match *f.const_.ty().kind() {
+                    ty::FnDef(def_id, _) if !tcx.is_intrinsic(def_id) => CALL_PENALTY,
+                    _ => INSTR_COST,
+                };
+                call_cost + landing_pad_cost(unwind)
+            }
+            TerminatorKind::Assert { unwind, .. } => CALL_PENALTY + landing_pad_cost(unwind),
+            TerminatorKind::UnwindResume => RESUME_PENALTY,
+            TerminatorKind::InlineAsm { unwind, .. } => INSTR_COST + landing_pad_cost(unwind),
+            _ => INSTR_COST,
+        };
+    }
+    cost
+}
+
+fn landing_pad_cost(unwind: UnwindAction) -> usize {
+    if let UnwindAction::Cleanup(_) = unwind { LANDINGPAD_PENALTY } else { 0 }
+}
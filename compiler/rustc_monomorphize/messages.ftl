@@ -4,6 +4,9 @@ monomorphize_consider_type_length_limit =
 monomorphize_couldnt_dump_mono_stats =
     unexpected error occurred while dumping monomorphization stats: {$error}
 
+monomorphize_couldnt_read_cgu_timings =
+    failed to read the CGU timings from `{$path}`: {$error}
+
 monomorphize_encountered_error_while_instantiating =
     the above error was encountered while instantiating `{$formatted_item}`
 
@@ -29,4 +32,7 @@ monomorphize_unknown_cgu_collection_mode =
 
 monomorphize_unused_generic_params = item has unused generic parameters
 
+monomorphize_why_reachable_not_found =
+    no item used by this crate matches `-Z why-reachable={$target}`
+
 monomorphize_written_to_path = the full type name has been written to '{$path}'
@@ -0,0 +1,113 @@
+//! Builds the call graph between the functions codegened in the local crate for
+//! `-Z stack-depth-report`.
+//!
+//! The graph is taken from the output of the mono item collector rather than derived from the
+//! MIR again, so that it always agrees with what gets codegened. Its nodes are the collected
+//! functions and its edges the uses recorded in the `UsageMap`. A use isn't necessarily a call,
+//! taking the address of a function or putting it into a vtable counts as well, so the depths
+//! reported from the graph are upper bounds. Calls through a `dyn Trait` vtable or a function
+//! pointer don't use a mono item; the collector records them separately, so that the report
+//! can flag them as unbounded.
+
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::def_id::DefId;
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
+use rustc_middle::mir::mono::{
+    MonoCallGraph, MonoCallGraphNode, MonoCallee, MonoItem, StackDepthEntryKind,
+};
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::{self, Instance, TyCtxt};
+use rustc_target::spec::abi::Abi;
+
+use crate::collector::{IndirectCall, UsageMap};
+
+pub(crate) fn build_call_graph<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    items: &FxHashSet<MonoItem<'tcx>>,
+    usage_map: &UsageMap<'tcx>,
+) -> MonoCallGraph {
+    let entry_fn = tcx.entry_fn(()).map(|(def_id, _)| def_id);
+
+    let mut functions: Vec<_> = items
+        .iter()
+        .filter_map(|&item| {
+            let MonoItem::Fn(instance) = item else { return None };
+            Some(MonoCallGraphNode {
+                symbol_name: tcx.symbol_name(instance).name.to_string(),
+                name: with_no_trimmed_paths!(instance.to_string()),
+                entry_kind: entry_kind(tcx, instance, entry_fn),
+                callees: callees(tcx, item, usage_map),
+            })
+        })
+        .collect();
+    functions.sort_by(|a, b| a.symbol_name.cmp(&b.symbol_name));
+
+    MonoCallGraph { functions }
+}
+
+fn entry_kind<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+    entry_fn: Option<DefId>,
+) -> Option<StackDepthEntryKind> {
+    let ty::InstanceDef::Item(def_id) = instance.def else { return None };
+    if entry_fn == Some(def_id) {
+        return Some(StackDepthEntryKind::Main);
+    }
+
+    let fn_ty = instance.ty(tcx, ty::ParamEnv::reveal_all());
+    if let ty::FnDef(..) = fn_ty.kind() {
+        match fn_ty.fn_sig(tcx).abi() {
+            Abi::Msp430Interrupt
+            | Abi::X86Interrupt
+            | Abi::AvrInterrupt
+            | Abi::AvrNonBlockingInterrupt
+            | Abi::RiscvInterruptM
+            | Abi::RiscvInterruptS => return Some(StackDepthEntryKind::Interrupt),
+            _ => {}
+        }
+    }
+
+    let attrs = tcx.codegen_fn_attrs(def_id);
+    if attrs.flags.contains(CodegenFnAttrFlags::NO_MANGLE) || attrs.export_name.is_some() {
+        return Some(StackDepthEntryKind::Export);
+    }
+
+    None
+}
+
+fn callees<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    item: MonoItem<'tcx>,
+    usage_map: &UsageMap<'tcx>,
+) -> Vec<MonoCallee> {
+    let mut callees = Vec::new();
+    let mut push = |callee: MonoCallee| {
+        if !callees.contains(&callee) {
+            callees.push(callee);
+        }
+    };
+
+    let used_items = usage_map.get_used_items(item).iter();
+    for &used_item in used_items.chain(usage_map.get_upstream_used_items(item)) {
+        let MonoItem::Fn(instance) = used_item else { continue };
+        match instance.def {
+            // Dropping a `dyn Trait` calls the drop glue in its vtable.
+            ty::InstanceDef::DropGlue(_, Some(ty)) if matches!(ty.kind(), ty::Dynamic(..)) => {
+                push(MonoCallee::Virtual(with_no_trimmed_paths!(format!("drop_in_place::<{ty}>"))))
+            }
+            _ => push(MonoCallee::Direct(tcx.symbol_name(instance).name.to_string())),
+        }
+    }
+
+    for &call in usage_map.get_indirect_calls(item) {
+        push(match call {
+            IndirectCall::Virtual(def_id) => {
+                MonoCallee::Virtual(with_no_trimmed_paths!(tcx.def_path_str(def_id)))
+            }
+            IndirectCall::FnPtr(ty) => MonoCallee::FnPtr(with_no_trimmed_paths!(ty.to_string())),
+        });
+    }
+
+    callees
+}
@@ -190,6 +190,8 @@
 use rustc_span::symbol::{sym, Ident};
 use rustc_span::{Span, DUMMY_SP};
 use rustc_target::abi::Size;
+use std::collections::hash_map::Entry;
+use std::collections::VecDeque;
 use std::path::PathBuf;
 
 use crate::errors::{
@@ -207,15 +209,43 @@ pub struct UsageMap<'tcx> {
     // Maps every mono item to the mono items used by it.
     used_map: FxHashMap<MonoItem<'tcx>, Vec<MonoItem<'tcx>>>,
 
-    // Maps every mono item to the mono items that use it.
+    // Maps every mono item to the mono items that use it. With `-Z why-reachable` and
+    // `-Z stack-depth-report`, this also includes the items codegened upstream, which aren't
+    // collected.
     user_map: FxHashMap<MonoItem<'tcx>, Vec<MonoItem<'tcx>>>,
+
+    // Maps every mono item to the items codegened upstream used by it. Only recorded with
+    // `-Z why-reachable` and `-Z stack-depth-report`.
+    upstream_used_map: FxHashMap<MonoItem<'tcx>, Vec<MonoItem<'tcx>>>,
+
+    // Maps every function to the calls it makes that don't go to a mono item. Only recorded
+    // with `-Z stack-depth-report`.
+    indirect_calls: FxHashMap<MonoItem<'tcx>, Vec<IndirectCall<'tcx>>>,
+
+    // The mono items the collection started from.
+    roots: FxHashSet<MonoItem<'tcx>>,
+}
+
+/// A call that can't be resolved to a mono item.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum IndirectCall<'tcx> {
+    /// A call through a `dyn Trait` vtable to the given trait method.
+    Virtual(DefId),
+    /// A call through a function pointer of the given type.
+    FnPtr(Ty<'tcx>),
 }
 
 type MonoItems<'tcx> = Vec<Spanned<MonoItem<'tcx>>>;
 
 impl<'tcx> UsageMap<'tcx> {
-    fn new() -> UsageMap<'tcx> {
-        UsageMap { used_map: FxHashMap::default(), user_map: FxHashMap::default() }
+    fn new(roots: FxHashSet<MonoItem<'tcx>>) -> UsageMap<'tcx> {
+        UsageMap {
+            used_map: FxHashMap::default(),
+            user_map: FxHashMap::default(),
+            upstream_used_map: FxHashMap::default(),
+            indirect_calls: FxHashMap::default(),
+            roots,
+        }
     }
 
     fn record_used<'a>(
@@ -233,10 +263,46 @@ fn record_used<'a>(
         assert!(self.used_map.insert(user_item, used_items).is_none());
     }
 
+    fn record_upstream_used(
+        &mut self,
+        user_item: MonoItem<'tcx>,
+        upstream_items: &[Spanned<MonoItem<'tcx>>],
+    ) {
+        for upstream_item in upstream_items {
+            self.user_map.entry(upstream_item.node).or_default().push(user_item);
+        }
+
+        let upstream_items = upstream_items.iter().map(|item| item.node).collect();
+        assert!(self.upstream_used_map.insert(user_item, upstream_items).is_none());
+    }
+
+    fn record_indirect_calls(&mut self, user_item: MonoItem<'tcx>, calls: Vec<IndirectCall<'tcx>>) {
+        if !calls.is_empty() {
+            assert!(self.indirect_calls.insert(user_item, calls).is_none());
+        }
+    }
+
     pub fn get_user_items(&self, item: MonoItem<'tcx>) -> &[MonoItem<'tcx>] {
         self.user_map.get(&item).map(|items| items.as_slice()).unwrap_or(&[])
     }
 
+    /// Returns the collected mono items used by `item`.
+    pub fn get_used_items(&self, item: MonoItem<'tcx>) -> &[MonoItem<'tcx>] {
+        self.used_map.get(&item).map(|items| items.as_slice()).unwrap_or(&[])
+    }
+
+    /// Returns the items codegened upstream used by `item`, which are only recorded with
+    /// `-Z why-reachable` and `-Z stack-depth-report`.
+    pub fn get_upstream_used_items(&self, item: MonoItem<'tcx>) -> &[MonoItem<'tcx>] {
+        self.upstream_used_map.get(&item).map(|items| items.as_slice()).unwrap_or(&[])
+    }
+
+    /// Returns the calls made by `item` that don't go to a mono item, which are only recorded
+    /// with `-Z stack-depth-report`.
+    pub fn get_indirect_calls(&self, item: MonoItem<'tcx>) -> &[IndirectCall<'tcx>] {
+        self.indirect_calls.get(&item).map(|calls| calls.as_slice()).unwrap_or(&[])
+    }
+
     /// Internally iterate over all inlined items used by `item`.
     pub fn for_each_inlined_used_item<F>(&self, tcx: TyCtxt<'tcx>, item: MonoItem<'tcx>, mut f: F)
     where
@@ -250,6 +316,41 @@ pub fn for_each_inlined_used_item<F>(&self, tcx: TyCtxt<'tcx>, item: MonoItem<'t
             }
         }
     }
+
+    /// Iterates over the roots and the used items, which includes items codegened upstream with
+    /// `-Z why-reachable` and `-Z stack-depth-report`. Items may be visited more than once.
+    pub fn items(&self) -> impl Iterator<Item = MonoItem<'tcx>> + '_ {
+        self.roots.iter().chain(self.user_map.keys()).copied()
+    }
+
+    pub fn is_root(&self, item: MonoItem<'tcx>) -> bool {
+        self.roots.contains(&item)
+    }
+
+    /// Returns the shortest chain of uses leading from a root to `item`, starting with the root
+    /// and ending with `item`.
+    pub fn shortest_use_chain(&self, item: MonoItem<'tcx>) -> Option<Vec<MonoItem<'tcx>>> {
+        // Walk the uses backwards from `item`, recording the item each user leads to.
+        let mut leads_to = FxHashMap::default();
+        leads_to.insert(item, None);
+        let mut queue = VecDeque::from([item]);
+        while let Some(current) = queue.pop_front() {
+            if self.is_root(current) {
+                let mut chain = vec![current];
+                while let Some(&Some(next)) = leads_to.get(chain.last().unwrap()) {
+                    chain.push(next);
+                }
+                return Some(chain);
+            }
+            for &user in self.get_user_items(current) {
+                if let Entry::Vacant(entry) = leads_to.entry(user) {
+                    entry.insert(Some(current));
+                    queue.push_back(user);
+                }
+            }
+        }
+        None
+    }
 }
 
 #[instrument(skip(tcx, mode), level = "debug")]
@@ -265,7 +366,7 @@ pub fn collect_crate_mono_items(
     debug!("building mono item graph, beginning at roots");
 
     let mut visited = MTLock::new(FxHashSet::default());
-    let mut usage_map = MTLock::new(UsageMap::new());
+    let mut usage_map = MTLock::new(UsageMap::new(roots.iter().copied().collect()));
     let recursion_limit = tcx.recursion_limit();
 
     {
@@ -345,6 +446,7 @@ fn collect_items_rec<'tcx>(
     }
 
     let mut used_items = Vec::new();
+    let mut indirect_calls = Vec::new();
     let recursion_depth_reset;
 
     // Post-monomorphization errors MVP
@@ -415,7 +517,7 @@ fn collect_items_rec<'tcx>(
             check_type_length_limit(tcx, instance);
 
             rustc_data_structures::stack::ensure_sufficient_stack(|| {
-                collect_used_items(tcx, instance, &mut used_items);
+                collect_used_items(tcx, instance, &mut used_items, &mut indirect_calls);
             });
         }
         MonoItem::GlobalAsm(item_id) => {
@@ -468,7 +570,18 @@ fn collect_items_rec<'tcx>(
             formatted_item,
         });
     }
+    if records_upstream_uses(tcx) {
+        // Uses of the items codegened upstream are recorded for `-Z why-reachable` and
+        // `-Z stack-depth-report`, but the items themselves aren't collected.
+        let upstream_items;
+        (used_items, upstream_items) = used_items.into_iter().partition(|item| match item.node {
+            MonoItem::Fn(instance) => should_codegen_locally(tcx, &instance),
+            MonoItem::Static(..) | MonoItem::GlobalAsm(..) => true,
+        });
+        usage_map.lock_mut().record_upstream_used(starting_item.node, &upstream_items);
+    }
     usage_map.lock_mut().record_used(starting_item.node, &used_items);
+    usage_map.lock_mut().record_indirect_calls(starting_item.node, indirect_calls);
 
     for used_item in used_items {
         collect_items_rec(tcx, used_item, visited, recursion_depths, recursion_limit, usage_map);
@@ -591,6 +704,8 @@ struct MirUsedCollector<'a, 'tcx> {
     tcx: TyCtxt<'tcx>,
     body: &'a mir::Body<'tcx>,
     output: &'a mut MonoItems<'tcx>,
+    /// Calls that don't go to a mono item, only recorded with `-Z stack-depth-report`.
+    indirect_calls: &'a mut Vec<IndirectCall<'tcx>>,
     instance: Instance<'tcx>,
     /// Spans for move size lints already emitted. Helps avoid duplicate lints.
     move_size_spans: Vec<Span>,
@@ -612,6 +727,26 @@ pub fn monomorphize<T>(&self, value: T) -> T
         )
     }
 
+    /// Records a call through a `dyn Trait` vtable or a function pointer, which
+    /// `-Z stack-depth-report` can't follow.
+    fn record_indirect_call(&mut self, callee_ty: Ty<'tcx>) {
+        let call = match *callee_ty.kind() {
+            ty::FnDef(def_id, args) => {
+                let instance =
+                    Instance::expect_resolve(self.tcx, ty::ParamEnv::reveal_all(), def_id, args);
+                match instance.def {
+                    InstanceDef::Virtual(def_id, _) => IndirectCall::Virtual(def_id),
+                    _ => return,
+                }
+            }
+            ty::FnPtr(_) => IndirectCall::FnPtr(callee_ty),
+            _ => return,
+        };
+        if !self.indirect_calls.contains(&call) {
+            self.indirect_calls.push(call);
+        }
+    }
+
     fn check_operand_move_size(&mut self, operand: &mir::Operand<'tcx>, location: Location) {
         let limit = self.tcx.move_size_limit().0;
         if limit == 0 {
@@ -816,6 +951,9 @@ fn visit_terminator(&mut self, terminator: &mir::Terminator<'tcx>, location: Loc
                 let callee_ty = func.ty(self.body, tcx);
                 let callee_ty = self.monomorphize(callee_ty);
                 self.check_fn_args_move_size(callee_ty, args, location);
+                if tcx.sess.opts.unstable_opts.stack_depth_report {
+                    self.record_indirect_call(callee_ty);
+                }
                 visit_fn_use(self.tcx, callee_ty, true, source, &mut self.output)
             }
             mir::TerminatorKind::Drop { ref place, .. } => {
@@ -917,6 +1055,11 @@ fn visit_instance_use<'tcx>(
 ) {
     debug!("visit_item_use({:?}, is_direct_call={:?})", instance, is_direct_call);
     if !should_codegen_locally(tcx, &instance) {
+        // `-Z why-reachable` and `-Z stack-depth-report` also need the uses of functions
+        // codegened upstream, they are separated from the collected items in `collect_items_rec`.
+        if records_upstream_uses(tcx) && matches!(instance.def, ty::InstanceDef::Item(..)) {
+            output.push(create_fn_mono_item(tcx, instance, source));
+        }
         return;
     }
 
@@ -948,6 +1091,12 @@ fn visit_instance_use<'tcx>(
     }
 }
 
+/// Returns `true` if the uses of items codegened upstream are recorded in the `UsageMap`.
+fn records_upstream_uses(tcx: TyCtxt<'_>) -> bool {
+    tcx.sess.opts.unstable_opts.why_reachable.is_some()
+        || tcx.sess.opts.unstable_opts.stack_depth_report
+}
+
 /// Returns `true` if we should codegen an instance in the local crate, or returns `false` if we
 /// can just link to the upstream crate and therefore don't need a mono item.
 fn should_codegen_locally<'tcx>(tcx: TyCtxt<'tcx>, instance: &Instance<'tcx>) -> bool {
@@ -1410,11 +1559,12 @@ fn build_skip_move_check_fns(tcx: TyCtxt<'_>) -> Vec<DefId> {
 }
 
 /// Scans the MIR in order to find function calls, closures, and drop-glue.
-#[instrument(skip(tcx, output), level = "debug")]
+#[instrument(skip(tcx, output, indirect_calls), level = "debug")]
 fn collect_used_items<'tcx>(
     tcx: TyCtxt<'tcx>,
     instance: Instance<'tcx>,
     output: &mut MonoItems<'tcx>,
+    indirect_calls: &mut Vec<IndirectCall<'tcx>>,
 ) {
     let body = tcx.instance_mir(instance.def);
 
@@ -1424,6 +1574,7 @@ fn collect_used_items<'tcx>(
         tcx,
         body: &body,
         output,
+        indirect_calls,
         instance,
         move_size_spans: vec![],
         visiting_call_terminator: false,
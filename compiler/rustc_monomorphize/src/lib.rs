@@ -18,6 +18,7 @@
 use rustc_middle::ty::adjustment::CustomCoerceUnsized;
 use rustc_middle::ty::{self, Ty};
 
+mod call_graph;
 mod collector;
 mod errors;
 mod partitioning;
@@ -111,14 +111,18 @@
 };
 use rustc_middle::query::Providers;
 use rustc_middle::ty::print::{characteristic_def_id_of_type, with_no_trimmed_paths};
-use rustc_middle::ty::{self, visit::TypeVisitableExt, InstanceDef, TyCtxt};
-use rustc_session::config::{DumpMonoStatsFormat, SwitchWithOptPath};
+use rustc_middle::ty::{self, visit::TypeVisitableExt, Instance, InstanceDef, TyCtxt};
+use rustc_session::config::{CguPartitioning, DumpMonoStatsFormat, SwitchWithOptPath};
 use rustc_session::CodegenUnits;
 use rustc_span::symbol::Symbol;
 
+use crate::call_graph::build_call_graph;
 use crate::collector::UsageMap;
 use crate::collector::{self, MonoItemCollectionMode};
-use crate::errors::{CouldntDumpMonoStats, SymbolAlreadyDefined, UnknownCguCollectionMode};
+use crate::errors::{
+    CouldntDumpMonoStats, CouldntReadCguTimings, SymbolAlreadyDefined, UnknownCguCollectionMode,
+    WhyReachableNotFound,
+};
 
 struct PartitioningCx<'a, 'tcx> {
     tcx: TyCtxt<'tcx>,
@@ -317,6 +321,31 @@ fn merge_codegen_units<'tcx>(
     let mut cgu_contents: FxHashMap<Symbol, Vec<Symbol>> =
         codegen_units.iter().map(|cgu| (cgu.name(), vec![cgu.name()])).collect();
 
+    // With `-Z cgu-partitioning=timing-guided`, we balance CGUs by their expected backend time
+    // instead of by their size, using the timings recorded in the previous session. We also
+    // record which CGUs end up merged together, to attribute the time of this session to them.
+    let timings = PreviousCguTimings::load(cx.tcx, codegen_units);
+    let record_initial_cgus = cx.tcx.sess.opts.incremental.is_some()
+        && cx.tcx.sess.opts.unstable_opts.cgu_partitioning == CguPartitioning::TimingGuided;
+    let initial_sizes: FxHashMap<Symbol, usize> = if record_initial_cgus {
+        codegen_units.iter().map(|cgu| (cgu.name(), cgu.size_estimate())).collect()
+    } else {
+        FxHashMap::default()
+    };
+
+    // The cost of each CGU: its expected backend time in nanoseconds when we have timings, or
+    // its size estimate otherwise.
+    let mut costs: FxHashMap<Symbol, usize> = codegen_units
+        .iter()
+        .map(|cgu| {
+            let cost = match &timings {
+                Some(timings) => timings.estimate(cgu),
+                None => cgu.size_estimate(),
+            };
+            (cgu.name(), cost)
+        })
+        .collect();
+
     // If N is the maximum number of CGUs, and the CGUs are sorted from largest
     // to smallest, we repeatedly find which CGU in codegen_units[N..] has the
     // greatest overlap of inlined items with codegen_units[N-1], merge that
@@ -333,19 +362,19 @@ fn merge_codegen_units<'tcx>(
     // is the smallest of those, and so has the most room to grow.
     let max_codegen_units = cx.tcx.sess.codegen_units().as_usize();
     while codegen_units.len() > max_codegen_units {
-        // Sort small CGUs to the back.
-        codegen_units.sort_by_key(|cgu| cmp::Reverse(cgu.size_estimate()));
+        // Sort cheap CGUs to the back.
+        codegen_units.sort_by_key(|cgu| cmp::Reverse(costs[&cgu.name()]));
 
         let cgu_dst = &codegen_units[max_codegen_units - 1];
 
         // Find the CGU that overlaps the most with `cgu_dst`. In the case of a
-        // tie, favour the earlier (bigger) CGU.
+        // tie, favour the earlier (costlier) CGU.
         let mut max_overlap = 0;
         let mut max_overlap_i = max_codegen_units;
         for (i, cgu_src) in codegen_units.iter().enumerate().skip(max_codegen_units) {
-            if cgu_src.size_estimate() <= max_overlap {
+            if timings.is_none() && cgu_src.size_estimate() <= max_overlap {
                 // None of the remaining overlaps can exceed `max_overlap`, so
-                // stop looking.
+                // stop looking. This relies on the CGUs being sorted by size.
                 break;
             }
 
@@ -365,6 +394,12 @@ fn merge_codegen_units<'tcx>(
         cgu_dst.items_mut().extend(cgu_src.items_mut().drain());
         cgu_dst.compute_size_estimate();
 
+        // The expected time of the merged CGU is the sum of the expected times of its parts,
+        // which slightly overestimates it when they share inlined items.
+        let src_cost = costs.remove(&cgu_src.name()).unwrap();
+        let dst_cost = costs.get_mut(&cgu_dst.name()).unwrap();
+        *dst_cost = if timings.is_some() { *dst_cost + src_cost } else { cgu_dst.size_estimate() };
+
         // Record that `cgu_dst` now contains all the stuff that was in
         // `cgu_src` before.
         let mut consumed_cgu_names = cgu_contents.remove(&cgu_src.name()).unwrap();
@@ -407,6 +442,14 @@ fn merge_codegen_units<'tcx>(
         // Don't update `cgu_contents`, that's only for incremental builds.
     }
 
+    if record_initial_cgus {
+        for cgu in codegen_units.iter_mut() {
+            let initial_cgus =
+                cgu_contents[&cgu.name()].iter().map(|name| (*name, initial_sizes[name])).collect();
+            cgu.set_initial_cgus(initial_cgus);
+        }
+    }
+
     let cgu_name_builder = &mut CodegenUnitNameBuilder::new(cx.tcx);
 
     // Rename the newly merged CGUs.
@@ -483,6 +526,100 @@ fn merge_codegen_units<'tcx>(
     }
 }
 
+/// The backend timings recorded in the previous incremental session, or read from the file given
+/// to `-Z cgu-partitioning-timings`, used by `-Z cgu-partitioning=timing-guided` to estimate how
+/// long each CGU will take.
+struct PreviousCguTimings {
+    /// Backend nanoseconds per unit of size estimate, for each CGU as initially placed.
+    rates: FxHashMap<String, f64>,
+    /// The rate of CGUs without a recorded timing, e.g. those of new modules.
+    default_rate: f64,
+}
+
+impl PreviousCguTimings {
+    fn load(tcx: TyCtxt<'_>, codegen_units: &[CodegenUnit<'_>]) -> Option<Self> {
+        if tcx.sess.opts.unstable_opts.cgu_partitioning != CguPartitioning::TimingGuided {
+            return None;
+        }
+
+        // Each timing is the backend time of a CGU in nanoseconds, along with the CGUs it was
+        // merged from, as they were named before merging, and their size estimates.
+        let timings: Vec<(u64, Vec<(String, usize)>)> =
+            if let Some(path) = &tcx.sess.opts.unstable_opts.cgu_partitioning_timings {
+                read_cgu_timings(path, codegen_units).unwrap_or_else(|error| {
+                    tcx.sess.emit_fatal(CouldntReadCguTimings { path: path.clone(), error })
+                })
+            } else if tcx.sess.opts.incremental.is_some() {
+                // A sorted order here ensures the floating point rates are deterministic.
+                tcx.dep_graph
+                    .previous_work_products()
+                    .to_sorted_stable_ord()
+                    .into_iter()
+                    .filter_map(|(_, work_product)| work_product.backend_timing.as_ref())
+                    .map(|timing| (timing.nanos, timing.initial_cgus.clone()))
+                    .collect()
+            } else {
+                return None;
+            };
+
+        let mut rates = FxHashMap::default();
+        let mut total_nanos = 0;
+        let mut total_size = 0;
+        for (nanos, initial_cgus) in timings {
+            let size: usize = initial_cgus.iter().map(|&(_, size)| size).sum();
+            if size == 0 {
+                continue;
+            }
+            // We can't tell which of the merged CGUs took the most time, so we assume the
+            // time was spread evenly over their combined size.
+            let rate = nanos as f64 / size as f64;
+            for (name, _) in initial_cgus {
+                rates.insert(name, rate);
+            }
+            total_nanos += nanos;
+            total_size += size;
+        }
+
+        if total_size == 0 {
+            // This is the first session, or the previous one did not record timings.
+            return None;
+        }
+
+        Some(PreviousCguTimings { rates, default_rate: total_nanos as f64 / total_size as f64 })
+    }
+
+    /// Estimates the backend time of a CGU which has not been merged yet, in nanoseconds.
+    fn estimate(&self, cgu: &CodegenUnit<'_>) -> usize {
+        let rate = self.rates.get(cgu.name().as_str()).copied().unwrap_or(self.default_rate);
+        (cgu.size_estimate() as f64 * rate) as usize
+    }
+}
+
+/// Reads the file given to `-Z cgu-partitioning-timings`, in which each line is the name of a
+/// CGU as initially placed followed by its backend time in nanoseconds. Lines naming CGUs that
+/// don't exist are ignored, like the timings of modules removed since the previous session.
+fn read_cgu_timings(
+    path: &Path,
+    codegen_units: &[CodegenUnit<'_>],
+) -> Result<Vec<(u64, Vec<(String, usize)>)>, String> {
+    let contents = fs::read_to_string(path).map_err(|error| error.to_string())?;
+
+    let mut timings = Vec::new();
+    for line in contents.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let timing = line
+            .rsplit_once(char::is_whitespace)
+            .and_then(|(name, nanos)| Some((name.trim_end(), nanos.parse::<u64>().ok()?)));
+        let Some((name, nanos)) = timing else {
+            return Err(format!("invalid line `{line}`, expected `<CGU name> <nanoseconds>`"));
+        };
+        if let Some(cgu) = codegen_units.iter().find(|cgu| cgu.name().as_str() == name) {
+            timings.push((nanos, vec![(name.to_owned(), cgu.size_estimate())]));
+        }
+    }
+
+    Ok(timings)
+}
+
 /// Compute the combined size of all inlined items that appear in both `cgu1`
 /// and `cgu2`.
 fn compute_inlined_overlap<'tcx>(cgu1: &CodegenUnit<'tcx>, cgu2: &CodegenUnit<'tcx>) -> usize {
@@ -1197,9 +1334,77 @@ fn collect_and_partition_mono_items(tcx: TyCtxt<'_>, (): ()) -> (&DefIdSet, &[Co
         }
     }
 
+    if let Some(target) = &tcx.sess.opts.unstable_opts.why_reachable {
+        print_why_reachable(tcx, &usage_map, target);
+    }
+
+    if tcx.sess.opts.unstable_opts.stack_depth_report {
+        tcx.feed_unit_query().mono_call_graph(build_call_graph(tcx, &items, &usage_map));
+    }
+
     (tcx.arena.alloc(mono_items), codegen_units)
 }
 
+/// Prints the shortest chain of uses leading from a root of the collection to each mono item
+/// matching `-Z why-reachable`, by symbol name or path.
+fn print_why_reachable<'tcx>(tcx: TyCtxt<'tcx>, usage_map: &UsageMap<'tcx>, target: &str) {
+    let matches = |item: MonoItem<'tcx>| {
+        let def_id = match item {
+            MonoItem::Fn(instance) => instance.def_id(),
+            MonoItem::Static(def_id) => def_id,
+            MonoItem::GlobalAsm(..) => return false,
+        };
+        item.symbol_name(tcx).name == target
+            || with_no_trimmed_paths!(item.to_string()) == target
+            || with_no_trimmed_paths!(tcx.def_path_str(def_id)) == target
+    };
+    let items: FxHashSet<_> = usage_map.items().filter(|&item| matches(item)).collect();
+    if items.is_empty() {
+        tcx.sess.emit_warning(WhyReachableNotFound { target });
+        return;
+    }
+
+    let mut chains: Vec<_> =
+        items.into_iter().filter_map(|item| usage_map.shortest_use_chain(item)).collect();
+    chains.sort_by_cached_key(|chain| with_no_trimmed_paths!(chain.last().unwrap().to_string()));
+    for chain in chains {
+        let root = chain[0];
+        let root_kind = match root {
+            MonoItem::Fn(instance)
+                if tcx.entry_fn(()).is_some_and(|(def_id, _)| def_id == instance.def_id())
+                    || tcx.lang_items().start_fn() == Some(instance.def_id()) =>
+            {
+                "the entry function"
+            }
+            MonoItem::Static(def_id)
+                if tcx
+                    .codegen_fn_attrs(def_id)
+                    .flags
+                    .intersects(CodegenFnAttrFlags::USED | CodegenFnAttrFlags::USED_LINKER) =>
+            {
+                "the `#[used]` static"
+            }
+            MonoItem::Fn(Instance { def: InstanceDef::Item(def_id), .. })
+            | MonoItem::Static(def_id)
+                if tcx.is_reachable_non_generic(def_id) =>
+            {
+                "the exported item"
+            }
+            MonoItem::GlobalAsm(..) => "the `global_asm!`",
+            _ => "the root",
+        };
+
+        let item = chain.last().unwrap();
+        with_no_trimmed_paths!({
+            println!("`{item}` is reachable from {root_kind} `{root}`:");
+            println!("       {root}");
+            for item in &chain[1..] {
+                println!("    -> {item}");
+            }
+        });
+    }
+}
+
 /// Outputs stats about instantiation counts and estimated size, per `MonoItem`'s
 /// def, to a file in the given output directory.
 fn dump_mono_items_stats<'tcx>(
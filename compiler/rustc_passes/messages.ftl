@@ -133,9 +133,7 @@ passes_dead_codes =
 
 passes_debug_visualizer_invalid =
     invalid argument
-    .note_1 = expected: `natvis_file = "..."`
-    .note_2 = OR
-    .note_3 = expected: `gdb_script_file = "..."`
+    .note = expected one of: `natvis_file = "..."`, `gdb_script_file = "..."`, `lldb_python_file = "..."` or `lldb_type_summary_file = "..."`
 
 passes_debug_visualizer_placement =
     attribute should be applied to a module
@@ -8,6 +8,7 @@
     query::{LocalCrate, Providers},
     ty::TyCtxt,
 };
+use rustc_session::parse::feature_err;
 use rustc_session::Session;
 use rustc_span::sym;
 
@@ -39,12 +40,33 @@ fn check_for_debugger_visualizer(&mut self, attr: &Attribute) {
                     (sym::gdb_script_file, Some(value)) => {
                         (DebuggerVisualizerType::GdbPrettyPrinter, value)
                     }
+                    (sym::lldb_python_file, Some(value)) => {
+                        (DebuggerVisualizerType::LldbPythonScript, value)
+                    }
+                    (sym::lldb_type_summary_file, Some(value)) => {
+                        (DebuggerVisualizerType::LldbTypeSummary, value)
+                    }
                     (_, _) => {
                         self.sess.emit_err(DebugVisualizerInvalid { span: meta_item.span });
                         return;
                     }
                 };
 
+            if matches!(
+                visualizer_type,
+                DebuggerVisualizerType::LldbPythonScript | DebuggerVisualizerType::LldbTypeSummary
+            ) && !self.lldb_debugger_visualizer
+            {
+                feature_err(
+                    &self.sess.parse_sess,
+                    sym::lldb_debugger_visualizer,
+                    meta_item.span,
+                    "embedding LLDB formatters is experimental",
+                )
+                .emit();
+                return;
+            }
+
             let file =
                 match resolve_path(&self.sess.parse_sess, visualizer_path.as_str(), attr.span) {
                     Ok(file) => file,
@@ -76,6 +98,8 @@ fn check_for_debugger_visualizer(&mut self, attr: &Attribute) {
 
 struct DebuggerVisualizerCollector<'a> {
     sess: &'a Session,
+    /// Whether `#![feature(lldb_debugger_visualizer)]` is enabled.
+    lldb_debugger_visualizer: bool,
     visualizers: Vec<DebuggerVisualizerFile>,
 }
 
@@ -91,7 +115,11 @@ fn debugger_visualizers(tcx: TyCtxt<'_>, _: LocalCrate) -> Vec<DebuggerVisualize
     let resolver_and_krate = tcx.resolver_for_lowering(()).borrow();
     let krate = &*resolver_and_krate.1;
 
-    let mut visitor = DebuggerVisualizerCollector { sess: tcx.sess, visualizers: Vec::new() };
+    let mut visitor = DebuggerVisualizerCollector {
+        sess: tcx.sess,
+        lldb_debugger_visualizer: tcx.features().lldb_debugger_visualizer,
+        visualizers: Vec::new(),
+    };
     rustc_ast::visit::Visitor::visit_crate(&mut visitor, krate);
 
     // We are collecting visualizers in AST-order, which is deterministic,
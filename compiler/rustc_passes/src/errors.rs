@@ -601,9 +601,7 @@ pub struct DebugVisualizerPlacement {
 
 #[derive(Diagnostic)]
 #[diag(passes_debug_visualizer_invalid)]
-#[note(passes_note_1)]
-#[note(passes_note_2)]
-#[note(passes_note_3)]
+#[note]
 pub struct DebugVisualizerInvalid {
     #[primary_span]
     pub span: Span,
@@ -14,6 +14,7 @@
 use std::hash::Hash;
 use std::marker::PhantomData;
 use std::sync::atomic::Ordering::Relaxed;
+use std::time::Instant;
 
 use super::query::DepGraphQuery;
 use super::serialized::{GraphEncoder, SerializedDepGraph, SerializedDepNodeIndex};
@@ -353,6 +354,8 @@ pub fn with_task<Ctxt: HasDepContext<Deps = D>, A: Debug, R>(
                  - dep-node: {key:?}"
         );
 
+        let start = self.current.record_graph.then(Instant::now);
+
         let with_deps = |task_deps| D::with_deps(task_deps, || task(cx, arg));
         let (result, edges) = if cx.dep_context().is_eval_always(key.kind) {
             (with_deps(TaskDepsRef::EvalAlways), EdgesVec::new())
@@ -383,6 +386,10 @@ pub fn with_task<Ctxt: HasDepContext<Deps = D>, A: Debug, R>(
 
         hashing_timer.finish_with_query_invocation_id(dep_node_index.into());
 
+        if let Some(start) = start {
+            self.current.encoder.borrow().record_duration(dep_node_index, start.elapsed());
+        }
+
         if let Some((prev_index, color)) = prev_and_color {
             debug_assert!(
                 self.colors.get(prev_index).is_none(),
@@ -675,7 +682,8 @@ pub fn previous_work_product(&self, v: &WorkProductId) -> Option<WorkProduct> {
     }
 
     /// Access the map of work-products created during the cached run. Only
-    /// used during saving of the dep-graph.
+    /// used during saving of the dep-graph, and to read the backend timings
+    /// recorded for `-Z cgu-partitioning=timing-guided`.
     pub fn previous_work_products(&self) -> &WorkProductMap {
         &self.data.as_ref().unwrap().previous_work_products
     }
@@ -1037,6 +1045,21 @@ pub struct WorkProduct {
     /// By convention, file extensions are currently used as identifiers, i.e. the key "o" maps to
     /// the object file's path, and "dwo" to the dwarf object file's path.
     pub saved_files: UnordMap<String, String>,
+    /// How long the backend took to process this CGU. Only recorded with
+    /// `-Z cgu-partitioning=timing-guided`.
+    pub backend_timing: Option<CguTiming>,
+}
+
+/// The backend time of a CGU, used by `-Z cgu-partitioning=timing-guided` to estimate how long
+/// the CGUs of the next session will take.
+#[derive(Clone, Debug, Encodable, Decodable)]
+pub struct CguTiming {
+    /// Time spent generating backend IR for the CGU, optimizing it (including LTO) and emitting
+    /// code for it, in nanoseconds.
+    pub nanos: u64,
+    /// The CGUs this one was merged from, as they were named before merging, along with their
+    /// size estimates. The time is attributed to them in proportion to their size.
+    pub initial_cgus: Vec<(String, usize)>,
 }
 
 pub type WorkProductMap = UnordMap<WorkProductId, WorkProduct>;
@@ -1107,6 +1130,10 @@ pub(super) struct CurrentDepGraph<D: Deps> {
     /// which may incur too much overhead.
     /// This will be None if self-profiling is disabled.
     node_intern_event_id: Option<EventId>,
+
+    /// Whether the graph is recorded into a `DepGraphQuery`, in which case we also
+    /// measure how long each task takes. Cached here to avoid borrowing the encoder.
+    record_graph: bool,
 }
 
 impl<D: Deps> CurrentDepGraph<D> {
@@ -1167,6 +1194,7 @@ fn new(
             total_read_count: AtomicU64::new(0),
             total_duplicate_read_count: AtomicU64::new(0),
             node_intern_event_id,
+            record_graph,
         }
     }
 
@@ -1,6 +1,8 @@
+use rustc_data_structures::fingerprint::Fingerprint;
 use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::graph::implementation::{Direction, Graph, NodeIndex, INCOMING};
 use rustc_index::IndexVec;
+use std::time::Duration;
 
 use super::{DepNode, DepNodeIndex};
 
@@ -8,6 +10,12 @@ pub struct DepGraphQuery {
     pub graph: Graph<DepNode, ()>,
     pub indices: FxHashMap<DepNode, NodeIndex>,
     pub dep_index_to_index: IndexVec<DepNodeIndex, Option<NodeIndex>>,
+    /// The fingerprint of each node's result, indexed by `NodeIndex`.
+    pub fingerprints: Vec<Fingerprint>,
+    /// How long the task of each node took to run, including the time spent in the tasks it
+    /// called, indexed by `NodeIndex`. `None` for nodes that were not executed as a task in
+    /// this session, e.g. because they were marked green.
+    pub durations: Vec<Option<Duration>>,
 }
 
 impl DepGraphQuery {
@@ -18,14 +26,25 @@ pub fn new(prev_node_count: usize) -> DepGraphQuery {
         let graph = Graph::with_capacity(node_count, edge_count);
         let indices = FxHashMap::default();
         let dep_index_to_index = IndexVec::new();
+        let fingerprints = Vec::with_capacity(node_count);
+        let durations = Vec::with_capacity(node_count);
 
-        DepGraphQuery { graph, indices, dep_index_to_index }
+        DepGraphQuery { graph, indices, dep_index_to_index, fingerprints, durations }
     }
 
-    pub fn push(&mut self, index: DepNodeIndex, node: DepNode, edges: &[DepNodeIndex]) {
+    pub fn push(
+        &mut self,
+        index: DepNodeIndex,
+        node: DepNode,
+        fingerprint: Fingerprint,
+        edges: &[DepNodeIndex],
+    ) {
         let source = self.graph.add_node(node);
         self.dep_index_to_index.insert(index, source);
         self.indices.insert(node, source);
+        debug_assert_eq!(self.fingerprints.len(), source.node_id());
+        self.fingerprints.push(fingerprint);
+        self.durations.push(None);
 
         for &target in edges.iter() {
             let target = self.dep_index_to_index[target];
@@ -37,6 +56,12 @@ pub fn push(&mut self, index: DepNodeIndex, node: DepNode, edges: &[DepNodeIndex
         }
     }
 
+    pub fn record_duration(&mut self, index: DepNodeIndex, duration: Duration) {
+        if let Some(Some(node)) = self.dep_index_to_index.get(index) {
+            self.durations[node.node_id()] = Some(duration);
+        }
+    }
+
     pub fn nodes(&self) -> Vec<&DepNode> {
         self.graph.all_nodes().iter().map(|n| &n.data).collect()
     }
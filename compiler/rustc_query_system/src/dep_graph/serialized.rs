@@ -49,6 +49,7 @@
 use rustc_serialize::{Decodable, Decoder, Encodable, Encoder};
 use std::iter;
 use std::marker::PhantomData;
+use std::time::Duration;
 
 // The maximum value of `SerializedDepNodeIndex` leaves the upper two bits
 // unused so that we can store multiple index types in `CompressedHybridIndex`,
@@ -462,7 +463,7 @@ fn encode_node(
         if let Some(record_graph) = &record_graph {
             // Do not ICE when a query is called from within `with_query`.
             if let Some(record_graph) = &mut record_graph.try_lock() {
-                record_graph.push(index, node.node, &node.edges);
+                record_graph.push(index, node.node, node.fingerprint, &node.edges);
             }
         }
 
@@ -536,6 +537,15 @@ pub(crate) fn with_query(&self, f: impl Fn(&DepGraphQuery)) {
         }
     }
 
+    pub(crate) fn record_duration(&self, index: DepNodeIndex, duration: Duration) {
+        if let Some(record_graph) = &self.record_graph {
+            // Do not ICE when a query is called from within `with_query`.
+            if let Some(record_graph) = &mut record_graph.try_lock() {
+                record_graph.record_duration(index, duration);
+            }
+        }
+    }
+
     pub(crate) fn print_incremental_info(
         &self,
         total_read_count: u64,
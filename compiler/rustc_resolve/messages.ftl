@@ -246,6 +246,9 @@ resolve_self_in_generic_param_default =
     generic parameters cannot use `Self` in their defaults
     .label = `Self` in generic parameter default
 
+resolve_test_helper_attribute =
+    `#[{$ident}]` is only accepted after `#[test]`, on the function of a test
+
 resolve_tool_module_imported =
     cannot use a tool module through an import
     .note = the tool module imported here
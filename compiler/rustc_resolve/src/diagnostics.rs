@@ -29,7 +29,7 @@
 
 use crate::errors::{
     AddedMacroUse, ChangeImportBinding, ChangeImportBindingSuggestion, ConsiderAddingADerive,
-    ExplicitUnsafeTraits,
+    ExplicitUnsafeTraits, TestHelperAttribute,
 };
 use crate::imports::{Import, ImportKind};
 use crate::late::{PatternSource, Rib};
@@ -1425,6 +1425,10 @@ pub(crate) fn unresolved_macro_suggestions(
             err.subdiagnostic(ExplicitUnsafeTraits { span: ident.span, ident });
             return;
         }
+        if macro_kind == MacroKind::Attr && matches!(ident.name, sym::timeout | sym::case) {
+            err.subdiagnostic(TestHelperAttribute { ident });
+            return;
+        }
         if self.macro_names.contains(&ident.normalize_to_macros_2_0()) {
             err.subdiagnostic(AddedMacroUse);
             return;
@@ -665,6 +665,12 @@ pub(crate) struct ExplicitUnsafeTraits {
     pub(crate) ident: Ident,
 }
 
+#[derive(Subdiagnostic)]
+#[note(resolve_test_helper_attribute)]
+pub(crate) struct TestHelperAttribute {
+    pub(crate) ident: Ident,
+}
+
 #[derive(Subdiagnostic)]
 #[help(resolve_added_macro_use)]
 pub(crate) struct AddedMacroUse;
@@ -94,6 +94,8 @@ session_split_debuginfo_unstable_platform = `-Csplit-debuginfo={$debuginfo}` is
 
 session_split_lto_unit_requires_lto = `-Zsplit-lto-unit` requires `-Clto`, `-Clto=thin`, or `-Clinker-plugin-lto`
 
+session_stack_depth_report_requires_elf = `-Zstack-depth-report` is only supported for targets producing ELF objects
+
 session_target_requires_unwind_tables = target requires unwind tables, they cannot be disabled with `-C force-unwind-tables=no`
 
 session_target_stack_protector_not_supported = `-Z stack-protector={$stack_protector}` is not supported for target {$target_triple} and will be ignored
@@ -431,9 +431,18 @@ fn from_str(s: &str) -> Result<Self, ()> {
 pub enum OutputType {
     Bitcode,
     Assembly,
+    /// Assembly annotated with source lines and demangled symbol names, meant to be read by
+    /// humans rather than assembled.
+    AssemblyAnnotated,
     LlvmAssembly,
     Mir,
     Metadata,
+    /// Metadata with only the information needed to type check downstream crates.
+    ///
+    /// Unlike `Metadata`, this contains no MIR that is only used by codegen, so downstream
+    /// crates that need to be linked cannot be compiled against it. The MIR of functions isn't
+    /// even built, so errors that are only found by borrow checking aren't reported.
+    MetadataInterface,
     Object,
     Exe,
     DepInfo,
@@ -455,9 +464,13 @@ fn to_stable_hash_key(&self, _: &HCX) -> Self::KeyType {
 impl OutputType {
     fn is_compatible_with_codegen_units_and_single_output_file(&self) -> bool {
         match *self {
-            OutputType::Exe | OutputType::DepInfo | OutputType::Metadata => true,
+            OutputType::Exe
+            | OutputType::DepInfo
+            | OutputType::Metadata
+            | OutputType::MetadataInterface => true,
             OutputType::Bitcode
             | OutputType::Assembly
+            | OutputType::AssemblyAnnotated
             | OutputType::LlvmAssembly
             | OutputType::Mir
             | OutputType::Object => false,
@@ -468,10 +481,12 @@ pub fn shorthand(&self) -> &'static str {
         match *self {
             OutputType::Bitcode => "llvm-bc",
             OutputType::Assembly => "asm",
+            OutputType::AssemblyAnnotated => "asm-annotated",
             OutputType::LlvmAssembly => "llvm-ir",
             OutputType::Mir => "mir",
             OutputType::Object => "obj",
             OutputType::Metadata => "metadata",
+            OutputType::MetadataInterface => "metadata-interface",
             OutputType::Exe => "link",
             OutputType::DepInfo => "dep-info",
         }
@@ -480,11 +495,13 @@ pub fn shorthand(&self) -> &'static str {
     fn from_shorthand(shorthand: &str) -> Option<Self> {
         Some(match shorthand {
             "asm" => OutputType::Assembly,
+            "asm-annotated" => OutputType::AssemblyAnnotated,
             "llvm-ir" => OutputType::LlvmAssembly,
             "mir" => OutputType::Mir,
             "llvm-bc" => OutputType::Bitcode,
             "obj" => OutputType::Object,
             "metadata" => OutputType::Metadata,
+            "metadata-interface" => OutputType::MetadataInterface,
             "link" => OutputType::Exe,
             "dep-info" => OutputType::DepInfo,
             _ => return None,
@@ -493,13 +510,15 @@ fn from_shorthand(shorthand: &str) -> Option<Self> {
 
     fn shorthands_display() -> String {
         format!(
-            "`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`",
+            "`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`",
             OutputType::Bitcode.shorthand(),
             OutputType::Assembly.shorthand(),
+            OutputType::AssemblyAnnotated.shorthand(),
             OutputType::LlvmAssembly.shorthand(),
             OutputType::Mir.shorthand(),
             OutputType::Object.shorthand(),
             OutputType::Metadata.shorthand(),
+            OutputType::MetadataInterface.shorthand(),
             OutputType::Exe.shorthand(),
             OutputType::DepInfo.shorthand(),
         )
@@ -509,10 +528,11 @@ pub fn extension(&self) -> &'static str {
         match *self {
             OutputType::Bitcode => "bc",
             OutputType::Assembly => "s",
+            OutputType::AssemblyAnnotated => "annotated.s",
             OutputType::LlvmAssembly => "ll",
             OutputType::Mir => "mir",
             OutputType::Object => "o",
-            OutputType::Metadata => "rmeta",
+            OutputType::Metadata | OutputType::MetadataInterface => "rmeta",
             OutputType::DepInfo => "d",
             OutputType::Exe => "",
         }
@@ -521,12 +541,15 @@ pub fn extension(&self) -> &'static str {
     pub fn is_text_output(&self) -> bool {
         match *self {
             OutputType::Assembly
+            | OutputType::AssemblyAnnotated
             | OutputType::LlvmAssembly
             | OutputType::Mir
             | OutputType::DepInfo => true,
-            OutputType::Bitcode | OutputType::Object | OutputType::Metadata | OutputType::Exe => {
-                false
-            }
+            OutputType::Bitcode
+            | OutputType::Object
+            | OutputType::Metadata
+            | OutputType::MetadataInterface
+            | OutputType::Exe => false,
         }
     }
 }
@@ -617,22 +640,31 @@ pub fn should_codegen(&self) -> bool {
         self.0.keys().any(|k| match *k {
             OutputType::Bitcode
             | OutputType::Assembly
+            | OutputType::AssemblyAnnotated
             | OutputType::LlvmAssembly
             | OutputType::Mir
             | OutputType::Object
             | OutputType::Exe => true,
-            OutputType::Metadata | OutputType::DepInfo => false,
+            OutputType::Metadata | OutputType::MetadataInterface | OutputType::DepInfo => false,
         })
     }
 
+    /// Returns `true` if the crate metadata should only contain what is needed to type check
+    /// downstream crates, see [`OutputType::MetadataInterface`].
+    pub fn is_metadata_interface(&self) -> bool {
+        self.0.contains_key(&OutputType::MetadataInterface)
+    }
+
     /// Returns `true` if any of the output types require linking.
     pub fn should_link(&self) -> bool {
         self.0.keys().any(|k| match *k {
             OutputType::Bitcode
             | OutputType::Assembly
+            | OutputType::AssemblyAnnotated
             | OutputType::LlvmAssembly
             | OutputType::Mir
             | OutputType::Metadata
+            | OutputType::MetadataInterface
             | OutputType::Object
             | OutputType::DepInfo => false,
             OutputType::Exe => true,
@@ -939,7 +971,7 @@ pub fn path(&self, flavor: OutputType) -> OutFileName {
     pub fn output_path(&self, flavor: OutputType) -> PathBuf {
         let extension = flavor.extension();
         match flavor {
-            OutputType::Metadata => {
+            OutputType::Metadata | OutputType::MetadataInterface => {
                 self.out_directory.join(format!("lib{}.{}", self.crate_stem, extension))
             }
             _ => self.with_directory_and_extension(&self.out_directory, extension),
@@ -1115,6 +1147,7 @@ fn default() -> Options {
             pretty: None,
             working_dir: RealFileName::LocalPath(std::env::current_dir().unwrap()),
             color: ColorConfig::Auto,
+            export_symbols: None,
         }
     }
 }
@@ -1123,7 +1156,7 @@ impl Options {
     /// Returns `true` if there is a reason to build the dep graph.
     pub fn build_dep_graph(&self) -> bool {
         self.incremental.is_some()
-            || self.unstable_opts.dump_dep_graph
+            || self.unstable_opts.dump_dep_graph.is_some()
             || self.unstable_opts.query_dep_graph
     }
 
@@ -2061,6 +2094,17 @@ fn parse_output_types(
                         display = OutputType::shorthands_display(),
                     ))
                 });
+                if matches!(
+                    output_type,
+                    OutputType::MetadataInterface | OutputType::AssemblyAnnotated
+                ) && !unstable_opts.unstable_options
+                {
+                    handler.early_error(format!(
+                        "the `-Z unstable-options` flag must also be passed to enable \
+                         `--emit={}`",
+                        output_type.shorthand()
+                    ));
+                }
                 output_types.insert(output_type, path);
             }
         }
@@ -2068,7 +2112,20 @@ fn parse_output_types(
     if output_types.is_empty() {
         output_types.insert(OutputType::Exe, None);
     }
-    OutputTypes(output_types)
+    let output_types = OutputTypes(output_types);
+    if output_types.is_metadata_interface() {
+        // The interface leaves out MIR needed for codegen, so it can't be produced along with
+        // anything that is built from that MIR, nor can it stand in for the full metadata.
+        for ot in output_types.keys() {
+            if !matches!(ot, OutputType::MetadataInterface | OutputType::DepInfo) {
+                handler.early_error(format!(
+                    "`--emit=metadata-interface` cannot be combined with `--emit={}`",
+                    ot.shorthand()
+                ));
+            }
+        }
+    }
+    output_types
 }
 
 fn split_out_file_name(arg: &str) -> (&str, Option<OutFileName>) {
@@ -2591,6 +2648,12 @@ fn parse_remap_path_prefix(
             Ok(cwd) => mapping.push((cwd, to.clone())),
             Err(_) => (),
         },
+        // `-Z reproducible` makes the output independent of where the crate is built from. Unlike
+        // an explicit `-Z remap-cwd-prefix`, this has lower precedence than any user mapping.
+        None if unstable_opts.reproducible => match std::env::current_dir() {
+            Ok(cwd) => mapping.insert(0, (cwd, PathBuf::from("."))),
+            Err(_) => (),
+        },
         None => (),
     };
     mapping
@@ -2688,6 +2751,13 @@ pub fn build_session_options(
         }
     }
 
+    let export_symbols = cg.export_symbols.as_deref().map(|path| {
+        if !unstable_opts.unstable_options {
+            handler.early_error("`-C export-symbols` requires `-Z unstable-options`");
+        }
+        parse_export_symbols(handler, path)
+    });
+
     // Check for unstable values of `-C instrument-coverage`.
     // This is what prevents them from being used on stable compilers.
     match cg.instrument_coverage {
@@ -2734,6 +2804,13 @@ pub fn build_session_options(
         unstable_opts.graphviz_font = graphviz_font;
     }
 
+    if unstable_opts.reproducible {
+        // Paths that were not remapped in some scope would leak the build directory.
+        unstable_opts.remap_path_scope = RemapPathScopeComponents::all();
+        // Human readable CGU names are only meant for debugging the compiler.
+        unstable_opts.human_readable_cgu_names = false;
+    }
+
     if !cg.embed_bitcode {
         match cg.lto {
             LtoCli::No | LtoCli::Unspecified => {}
@@ -2821,7 +2898,7 @@ pub fn build_session_options(
     let pretty = parse_pretty(handler, &unstable_opts);
 
     // query-dep-graph is required if dump-dep-graph is given #106736
-    if unstable_opts.dump_dep_graph && !unstable_opts.query_dep_graph {
+    if unstable_opts.dump_dep_graph.is_some() && !unstable_opts.query_dep_graph {
         handler.early_error("can't dump dependency graph without `-Z query-dep-graph`");
     }
 
@@ -2905,9 +2982,80 @@ pub fn build_session_options(
         pretty,
         working_dir,
         color,
+        export_symbols,
     }
 }
 
+/// Parses the file passed to `-C export-symbols`. Each line lists an exported symbol as
+/// `NAME [@VERSION] [weak]`, hides a symbol rustc would export as `!NAME`, or includes the symbols
+/// rustc exports by default as `* [@VERSION]`. Everything after a `#` is a comment.
+fn parse_export_symbols(handler: &EarlyErrorHandler, path: &Path) -> ExportSymbolList {
+    let contents = fs::read_to_string(path).unwrap_or_else(|e| {
+        handler.early_error(format!("failed to read export list `{}`: {e}", path.display()))
+    });
+
+    let mut list = ExportSymbolList::default();
+    let mut unversioned = None;
+    for (i, line) in contents.lines().enumerate() {
+        let error = |msg: String| -> ! {
+            handler.early_error(format!("{}:{}: {msg}", path.display(), i + 1))
+        };
+        let line = line.split('#').next().unwrap();
+        let mut words = line.split_whitespace();
+        let Some(name) = words.next() else { continue };
+
+        if let Some(hidden) = name.strip_prefix('!') {
+            if hidden.is_empty() {
+                error("expected a symbol name after `!`".to_owned());
+            }
+            if let Some(word) = words.next() {
+                error(format!("unexpected `{word}` after a hidden symbol"));
+            }
+            list.hidden.push(hidden.to_owned());
+            continue;
+        }
+
+        let mut version = None;
+        let mut weak = false;
+        for word in words {
+            match word.strip_prefix('@') {
+                Some("") => error("expected a version after `@`".to_owned()),
+                Some(v) if version.is_none() => version = Some(v.to_owned()),
+                Some(_) => error(format!("more than one version for `{name}`")),
+                None if word == "weak" && name != "*" => weak = true,
+                None => error(format!("unexpected `{word}`, expected `@VERSION` or `weak`")),
+            }
+        }
+        if version.is_none() {
+            unversioned.get_or_insert(i + 1);
+        }
+
+        if name == "*" {
+            if list.include_default {
+                error("`*` is listed more than once".to_owned());
+            }
+            list.include_default = true;
+            list.default_version = version;
+        } else {
+            if list.symbols.iter().any(|symbol| symbol.name == name) {
+                error(format!("`{name}` is listed more than once"));
+            }
+            list.symbols.push(ExportedSymbolSpec { name: name.to_owned(), version, weak });
+        }
+    }
+
+    // Version scripts can't mix symbols of the anonymous version node with named version nodes.
+    if list.has_versions()
+        && let Some(line) = unversioned
+    {
+        handler.early_error(format!(
+            "{}:{line}: missing version, every export must have a version when any has one",
+            path.display()
+        ));
+    }
+    list
+}
+
 fn parse_pretty(handler: &EarlyErrorHandler, unstable_opts: &UnstableOptions) -> Option<PpMode> {
     use PpMode::*;
 
@@ -3160,11 +3308,12 @@ pub fn needs_analysis(&self) -> bool {
 /// how the hash should be calculated when adding a new command-line argument.
 pub(crate) mod dep_tracking {
     use super::{
-        BranchProtection, CFGuard, CFProtection, CrateType, DebugInfo, DebugInfoCompression,
-        ErrorOutputType, InstrumentCoverage, InstrumentXRay, LinkerPluginLto, LocationDetail,
-        LtoCli, OomStrategy, OptLevel, OutFileName, OutputType, OutputTypes, Polonius,
-        RemapPathScopeComponents, ResolveDocLinks, SourceFileHashAlgorithm, SplitDwarfKind,
-        SwitchWithOptPath, SymbolManglingVersion, TraitSolver, TrimmedDefPaths,
+        BranchProtection, CFGuard, CFProtection, CguPartitioning, CrateType, DebugInfo,
+        DebugInfoCompression, ErrorOutputType, ExportSymbolList, InstrumentCoverage,
+        InstrumentXRay, LinkerPluginLto, LocationDetail, LtoCli, OomStrategy, OptLevel,
+        OutFileName, OutputType, OutputTypes, Polonius, RemapPathScopeComponents, ResolveDocLinks,
+        SourceFileHashAlgorithm, SplitDwarfKind, SwitchWithOptPath, SymbolManglingVersion,
+        TraitSolver, TrimmedDefPaths, UbChecks,
     };
     use crate::lint;
     use crate::options::WasiExecModel;
@@ -3270,6 +3419,9 @@ fn hash(
         LanguageIdentifier,
         TraitSolver,
         Polonius,
+        CguPartitioning,
+        UbChecks,
+        ExportSymbolList,
     );
 
     impl<T1, T2> DepTrackingHash for (T1, T2)
@@ -3391,6 +3543,25 @@ pub enum ProcMacroExecutionStrategy {
     CrossThread,
 }
 
+/// How to balance codegen units, selected with `-Z cgu-partitioning`.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum CguPartitioning {
+    /// Merge codegen units by their estimated size.
+    SizeBased,
+    /// Merge codegen units by their expected backend time, estimated from the timings recorded
+    /// in the previous incremental session. Behaves like `SizeBased` without such timings.
+    TimingGuided,
+}
+
+/// Which format to use for `-Z dump-dep-graph`
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum DumpDepGraphFormat {
+    /// Graphviz graph of the dep-kinds, plus a text file with the edges
+    Dot,
+    /// Emit structured JSON with every node, its fingerprint and timing, and its edges
+    Json,
+}
+
 /// Which format to use for `-Z dump-mono-stats`
 #[derive(Clone, Copy, PartialEq, Hash, Debug)]
 pub enum DumpMonoStatsFormat {
@@ -3409,6 +3580,89 @@ pub fn extension(self) -> &'static str {
     }
 }
 
+/// The runtime checks for undefined behavior inserted by `-Z ub-checks`.
+#[derive(Clone, Copy, Default, PartialEq, Hash, Debug)]
+pub struct UbChecks {
+    /// Check that raw pointers aren't null when they are dereferenced.
+    pub null: bool,
+    /// Check that the length passed to `slice::from_raw_parts` doesn't exceed `isize::MAX` bytes.
+    pub from_raw_parts: bool,
+    /// Check that `bool`, `char` and enum values read through raw pointers are valid.
+    pub loads: bool,
+    /// Check that scalar values produced by `transmute` are valid.
+    pub transmute: bool,
+}
+
+impl UbChecks {
+    pub fn all() -> Self {
+        UbChecks { null: true, from_raw_parts: true, loads: true, transmute: true }
+    }
+
+    pub fn any(&self) -> bool {
+        self.null || self.from_raw_parts || self.loads || self.transmute
+    }
+}
+
+/// A symbol exported by a cdylib, listed in the file passed to `-C export-symbols`.
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct ExportedSymbolSpec {
+    pub name: String,
+    /// The version node the symbol belongs to, like `FOO_1.0`.
+    pub version: Option<String>,
+    /// Whether the symbol is defined with weak linkage.
+    pub weak: bool,
+}
+
+/// The exports of a cdylib, read from the file passed to `-C export-symbols`.
+#[derive(Clone, Default, PartialEq, Hash, Debug)]
+pub struct ExportSymbolList {
+    /// Whether the symbols rustc exports by default are exported, with a `*` line.
+    pub include_default: bool,
+    /// The version node of the symbols exported by default.
+    pub default_version: Option<String>,
+    /// The symbols listed explicitly.
+    pub symbols: Vec<ExportedSymbolSpec>,
+    /// The symbols that aren't exported even though rustc would by default, with `!NAME` lines.
+    pub hidden: Vec<String>,
+}
+
+impl ExportSymbolList {
+    /// Computes the exported symbols from the ones rustc exports by default.
+    pub fn exports(&self, default: Vec<String>) -> Vec<String> {
+        let mut exports = FxIndexSet::default();
+        if self.include_default {
+            exports.extend(default.into_iter().filter(|symbol| !self.hidden.contains(symbol)));
+        }
+        exports.extend(self.symbols.iter().map(|symbol| symbol.name.clone()));
+        exports.into_iter().collect()
+    }
+
+    /// Whether the exports are assigned to version nodes. When they are, every exported symbol
+    /// has a version.
+    pub fn has_versions(&self) -> bool {
+        self.default_version.is_some() || self.symbols.iter().any(|symbol| symbol.version.is_some())
+    }
+
+    /// Returns the version node of an exported symbol.
+    pub fn version(&self, name: &str) -> Option<&str> {
+        match self.symbols.iter().find(|symbol| symbol.name == name) {
+            Some(symbol) => symbol.version.as_deref(),
+            None => self.default_version.as_deref(),
+        }
+    }
+
+    pub fn is_weak(&self, name: &str) -> bool {
+        self.symbols.iter().any(|symbol| symbol.weak && symbol.name == name)
+    }
+}
+
+/// Which format to use for `-Z size-report`
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum SizeReportFormat {
+    /// Emit structured JSON
+    Json,
+}
+
 /// `-Zpolonius` values, enabling the borrow checker polonius analysis, and which version: legacy,
 /// or future prototype.
 #[derive(Clone, Copy, PartialEq, Hash, Debug, Default)]
@@ -131,6 +131,10 @@ pub struct UnsupportedDwarfVersion {
     pub dwarf_version: u32,
 }
 
+#[derive(Diagnostic)]
+#[diag(session_stack_depth_report_requires_elf)]
+pub struct StackDepthReportRequiresElf;
+
 #[derive(Diagnostic)]
 #[diag(session_target_stack_protector_not_supported)]
 pub struct StackProtectorNotSupportedForTarget<'a> {
@@ -220,6 +220,9 @@ pub struct Options {
         /// The (potentially remapped) working directory
         working_dir: RealFileName [TRACKED],
         color: ColorConfig [UNTRACKED],
+
+        /// The exports of cdylibs, as read from the file passed to `-C export-symbols`.
+        export_symbols: Option<ExportSymbolList> [TRACKED],
     }
 );
 
@@ -380,6 +383,7 @@ mod desc {
     pub const parse_sanitizer_memory_track_origins: &str = "0, 1, or 2";
     pub const parse_cfguard: &str =
         "either a boolean (`yes`, `no`, `on`, `off`, etc), `checks`, or `nochecks`";
+    pub const parse_cgu_partitioning: &str = "`size-based` (default) or `timing-guided`";
     pub const parse_cfprotection: &str = "`none`|`no`|`n` (default), `branch`, `return`, or `full`|`yes`|`y` (equivalent to `branch` and `return`)";
     pub const parse_debuginfo: &str = "either an integer (0, 1, 2), `none`, `line-directives-only`, `line-tables-only`, `limited`, or `full`";
     pub const parse_debuginfo_compression: &str = "one of `none`, `zlib`, or `zstd`";
@@ -387,7 +391,11 @@ mod desc {
     pub const parse_linker_flavor: &str = ::rustc_target::spec::LinkerFlavorCli::one_of();
     pub const parse_optimization_fuel: &str = "crate=integer";
     pub const parse_mir_spanview: &str = "`statement` (default), `terminator`, or `block`";
+    pub const parse_dump_dep_graph: &str =
+        "either a boolean (`yes`, `no`, `on`, `off`, etc), `dot`, or `json`";
     pub const parse_dump_mono_stats: &str = "`markdown` (default) or `json`";
+    pub const parse_size_report: &str = "`json`";
+    pub const parse_ub_checks: &str = "either no value (all checks) or a comma separated list of checks: `all`, `null`, `from-raw-parts`, `loads`, or `transmute`";
     pub const parse_instrument_coverage: &str =
         "`all` (default), `branch`, `except-unused-generics`, `except-unused-functions`, or `off`";
     pub const parse_instrument_xray: &str = "either a boolean (`yes`, `no`, `on`, `off`, etc), or a comma separated list of settings: `always` or `never` (mutually exclusive), `ignore-loops`, `instruction-threshold=N`, `skip-entry`, `skip-exit`";
@@ -774,6 +782,15 @@ pub(crate) fn parse_cfguard(slot: &mut CFGuard, v: Option<&str>) -> bool {
         true
     }
 
+    pub(crate) fn parse_cgu_partitioning(slot: &mut CguPartitioning, v: Option<&str>) -> bool {
+        match v {
+            Some("size-based") => *slot = CguPartitioning::SizeBased,
+            Some("timing-guided") => *slot = CguPartitioning::TimingGuided,
+            _ => return false,
+        }
+        true
+    }
+
     pub(crate) fn parse_cfprotection(slot: &mut CFProtection, v: Option<&str>) -> bool {
         if v.is_some() {
             let mut bool_arg = None;
@@ -897,6 +914,24 @@ pub(crate) fn parse_time_passes_format(slot: &mut TimePassesFormat, v: Option<&s
         }
     }
 
+    pub(crate) fn parse_dump_dep_graph(
+        slot: &mut Option<DumpDepGraphFormat>,
+        v: Option<&str>,
+    ) -> bool {
+        match v {
+            Some("dot") => *slot = Some(DumpDepGraphFormat::Dot),
+            Some("json") => *slot = Some(DumpDepGraphFormat::Json),
+            _ => {
+                let mut bool_arg = None;
+                if !parse_opt_bool(&mut bool_arg, v) {
+                    return false;
+                }
+                *slot = bool_arg.unwrap().then_some(DumpDepGraphFormat::Dot);
+            }
+        }
+        true
+    }
+
     pub(crate) fn parse_dump_mono_stats(slot: &mut DumpMonoStatsFormat, v: Option<&str>) -> bool {
         match v {
             None => true,
@@ -912,6 +947,37 @@ pub(crate) fn parse_dump_mono_stats(slot: &mut DumpMonoStatsFormat, v: Option<&s
         }
     }
 
+    pub(crate) fn parse_size_report(slot: &mut Option<SizeReportFormat>, v: Option<&str>) -> bool {
+        match v {
+            Some("json") => {
+                *slot = Some(SizeReportFormat::Json);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub(crate) fn parse_ub_checks(slot: &mut UbChecks, v: Option<&str>) -> bool {
+        let Some(v) = v else {
+            *slot = UbChecks::all();
+            return true;
+        };
+
+        let mut checks = UbChecks::default();
+        for check in v.split(',') {
+            match check {
+                "all" => checks = UbChecks::all(),
+                "null" => checks.null = true,
+                "from-raw-parts" => checks.from_raw_parts = true,
+                "loads" => checks.loads = true,
+                "transmute" => checks.transmute = true,
+                _ => return false,
+            }
+        }
+        *slot = checks;
+        true
+    }
+
     pub(crate) fn parse_instrument_coverage(
         slot: &mut InstrumentCoverage,
         v: Option<&str>,
@@ -1340,6 +1406,9 @@ pub(crate) fn parse_dump_solver_proof_tree(
         "import library generation tool (ignored except when targeting windows-gnu)"),
     embed_bitcode: bool = (true, parse_bool, [TRACKED],
         "emit bitcode in rlibs (default: yes)"),
+    export_symbols: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "file listing the symbols exported by a cdylib, along with their versions \
+        and whether they are weak (unstable, requires `-Z unstable-options`)"),
     extra_filename: String = (String::new(), parse_string, [UNTRACKED],
         "extra data to put in each output filename"),
     force_frame_pointers: Option<bool> = (None, parse_opt_bool, [TRACKED],
@@ -1458,6 +1527,9 @@ pub(crate) fn parse_dump_solver_proof_tree(
         "encode MIR of all functions into the crate metadata (default: no)"),
     asm_comments: bool = (false, parse_bool, [TRACKED],
         "generate comments into the assembly (may change behavior) (default: no)"),
+    asm_filter: Option<String> = (None, parse_opt_string, [TRACKED],
+        "only keep the functions whose demangled name contains this string in the output of \
+        `--emit=asm-annotated`"),
     assert_incr_state: Option<String> = (None, parse_opt_string, [UNTRACKED],
         "assert that the incremental cache is in given state: \
          either `loaded` or `not-loaded`."),
@@ -1473,6 +1545,13 @@ pub(crate) fn parse_dump_solver_proof_tree(
         "set options for branch target identification and pointer authentication on AArch64"),
     cf_protection: CFProtection = (CFProtection::None, parse_cfprotection, [TRACKED],
         "instrument control-flow architecture protection"),
+    cgu_partitioning: CguPartitioning = (CguPartitioning::SizeBased, parse_cgu_partitioning, [TRACKED],
+        "how to balance codegen units: `size-based` merges them by estimated size, \
+        `timing-guided` by the backend time recorded in the previous incremental session \
+        (default: size-based)"),
+    cgu_partitioning_timings: Option<PathBuf> = (None, parse_opt_pathbuf, [TRACKED],
+        "read the backend timings used by `-Z cgu-partitioning=timing-guided` from a file of \
+        `<CGU name> <nanoseconds>` lines instead of the previous incremental session"),
     codegen_backend: Option<String> = (None, parse_opt_string, [TRACKED],
         "the backend to use"),
     combine_cgu: bool = (false, parse_bool, [TRACKED],
@@ -1497,9 +1576,13 @@ pub(crate) fn parse_dump_solver_proof_tree(
         (default: no)"),
     dual_proc_macros: bool = (false, parse_bool, [TRACKED],
         "load proc macros for both target and host, but only link to the target (default: no)"),
-    dump_dep_graph: bool = (false, parse_bool, [UNTRACKED],
-        "dump the dependency graph to $RUST_DEP_GRAPH (default: /tmp/dep_graph.gv) \
-        (default: no)"),
+    dump_dep_graph: Option<DumpDepGraphFormat> = (None, parse_dump_dep_graph, [UNTRACKED],
+        "dump the dependency graph to $RUST_DEP_GRAPH (default: dep_graph), either as \
+        `dot` (the default when no format is given) or as `json` (default: no)"),
+    dump_dep_graph_filter: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "only dump the dependency graph nodes matching this filter, and their direct \
+        neighbours; the filter is a `&`-separated list of dep-kinds or def paths which must \
+        all be part of the node's description (default: dump all nodes)"),
     dump_mir: Option<String> = (None, parse_opt_string, [UNTRACKED],
         "dump MIR state to file.
         `val` is used to select which passes and functions to dump. For example:
@@ -1535,8 +1618,15 @@ pub(crate) fn parse_dump_solver_proof_tree(
         "enables LTO for dylib crate type"),
     emit_stack_sizes: bool = (false, parse_bool, [UNTRACKED],
         "emit a section containing stack size metadata (default: no)"),
+    emit_symbol_order: Option<PathBuf> = (None, parse_opt_pathbuf, [TRACKED],
+        "write a symbol ordering file listing the functions executed the most in an LLVM \
+        profile first, and pass it to the linker"),
     emit_thin_lto: bool = (true, parse_bool, [TRACKED],
         "emit the bc module with thin LTO info (default: yes)"),
+    encode_mir_summaries: bool = (false, parse_bool, [TRACKED],
+        "encode inlining cost and size summaries of exported MIR into the crate metadata, \
+        so that downstream crates can make inlining and partitioning decisions without \
+        decoding the MIR (default: no)"),
     export_executable_symbols: bool = (false, parse_bool, [TRACKED],
         "export symbols from executables, as if they were dynamic libraries"),
     extra_const_ub_checks: bool = (false, parse_bool, [TRACKED],
@@ -1753,6 +1843,11 @@ pub(crate) fn parse_dump_solver_proof_tree(
 written to standard error output)"),
     report_delayed_bugs: bool = (false, parse_bool, [TRACKED],
         "immediately print bugs registered with `delay_span_bug` (default: no)"),
+    reproducible: bool = (false, parse_bool, [TRACKED],
+        "make the output independent of the build directory and of codegen scheduling: \
+        remaps the working directory to `.` unless otherwise remapped, remaps paths in all \
+        scopes, sorts archive members, uses hashed CGU names and gives outputs a content \
+        build-id (default: no)"),
     sanitizer: SanitizerSet = (SanitizerSet::empty(), parse_sanitizers, [TRACKED],
         "use a sanitizer"),
     sanitizer_cfi_canonical_jump_tables: Option<bool> = (Some(true), parse_opt_bool, [TRACKED],
@@ -1790,6 +1885,10 @@ pub(crate) fn parse_dump_solver_proof_tree(
     simulate_remapped_rust_src_base: Option<PathBuf> = (None, parse_opt_pathbuf, [TRACKED],
         "simulate the effect of remap-debuginfo = true at bootstrapping by remapping path \
         to rust's source base directory. only meant for testing purposes"),
+    size_report: Option<SizeReportFormat> = (None, parse_size_report, [UNTRACKED],
+        "write the size of every symbol of the crate's object files, attributed to the mono \
+        item that produced it and rolled up by crate and by generic function, to a \
+        `.size-report.json` file next to the output (default: no)"),
     span_debug: bool = (false, parse_bool, [UNTRACKED],
         "forward proc_macro::Span's `Debug` impl to `Span`"),
     /// o/w tests have closure@path
@@ -1810,6 +1909,9 @@ pub(crate) fn parse_dump_solver_proof_tree(
         "enable LTO unit splitting (default: no)"),
     src_hash_algorithm: Option<SourceFileHashAlgorithm> = (None, parse_src_file_hash, [TRACKED],
         "hash algorithm of source files in debug info (`md5`, `sha1`, or `sha256`)"),
+    stack_depth_report: bool = (false, parse_bool, [TRACKED],
+        "write the worst-case stack depth of every entry point to a `.stack-depth` file next \
+        to the output; implies `-Z emit-stack-sizes` (ELF only) (default: no)"),
     #[rustc_lint_opt_deny_field_access("use `Session::stack_protector` instead of this field")]
     stack_protector: StackProtector = (StackProtector::None, parse_stack_protector, [TRACKED],
         "control stack smash protection strategy (`rustc --print stack-protector-strategies` for details)"),
@@ -1877,6 +1979,10 @@ pub(crate) fn parse_dump_solver_proof_tree(
         "in diagnostics, use heuristics to shorten paths referring to items"),
     tune_cpu: Option<String> = (None, parse_opt_string, [TRACKED],
         "select processor to schedule for (`rustc --print target-cpus` for details)"),
+    ub_checks: UbChecks = (UbChecks::default(), parse_ub_checks, [TRACKED],
+        "insert runtime checks for undefined behavior: null pointer dereferences, \
+        `slice::from_raw_parts` lengths, invalid values read through raw pointers and \
+        invalid values produced by `transmute` (default: no)"),
     ui_testing: bool = (false, parse_bool, [UNTRACKED],
         "emit compiler diagnostics in a form suitable for UI testing (default: no)"),
     uninit_const_chunk_threshold: usize = (16, parse_number, [TRACKED],
@@ -1918,6 +2024,9 @@ pub(crate) fn parse_dump_solver_proof_tree(
         Requires `-Clto[=[fat,yes]]`"),
     wasi_exec_model: Option<WasiExecModel> = (None, parse_wasi_exec_model, [TRACKED],
         "whether to build a wasi command or reactor"),
+    why_reachable: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "print the shortest chain of uses leading from a root of the mono item collection to \
+        the items matching a symbol name or path"),
     write_long_types_to_disk: bool = (true, parse_bool, [UNTRACKED],
         "whether long type names should be written to files instead of being printed in errors"),
     // tidy-alphabetical-end
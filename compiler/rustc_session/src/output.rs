@@ -120,7 +120,12 @@ pub fn validate_crate_name(sess: &Session, s: Symbol, sp: Option<Span>) {
 }
 
 pub fn filename_for_metadata(sess: &Session, outputs: &OutputFilenames) -> OutFileName {
-    let out_filename = outputs.path(OutputType::Metadata);
+    let flavor = if sess.opts.output_types.is_metadata_interface() {
+        OutputType::MetadataInterface
+    } else {
+        OutputType::Metadata
+    };
+    let out_filename = outputs.path(flavor);
     if let OutFileName::Real(ref path) = out_filename {
         check_file_is_writeable(path, sess);
     }
@@ -1681,6 +1681,16 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
         }
     }
 
+    // `.stack_sizes` sections are only emitted into ELF objects.
+    if sess.opts.unstable_opts.stack_depth_report
+        && (sess.target.is_like_osx
+            || sess.target.is_like_windows
+            || sess.target.is_like_wasm
+            || sess.target.is_like_aix)
+    {
+        sess.emit_err(errors::StackDepthReportRequiresElf);
+    }
+
     if sess.opts.unstable_opts.branch_protection.is_some() && sess.target.arch != "aarch64" {
         sess.emit_err(errors::BranchProtectionRequiresAArch64);
     }
@@ -812,6 +812,18 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
                     found: found.stable(tables),
                 }
             }
+            AssertKind::NullPointerDereference => {
+                stable_mir::mir::AssertMessage::NullPointerDereference
+            }
+            AssertKind::InvalidValue { value } => {
+                stable_mir::mir::AssertMessage::InvalidValue { value: value.stable(tables) }
+            }
+            AssertKind::SliceFromRawPartsOverflow { len, size } => {
+                stable_mir::mir::AssertMessage::SliceFromRawPartsOverflow {
+                    len: len.stable(tables),
+                    size: size.stable(tables),
+                }
+            }
         }
     }
 }
@@ -483,6 +483,7 @@
         call_once,
         caller_location,
         capture_disjoint_fields,
+        case,
         cause,
         cdylib,
         ceilf32,
@@ -952,6 +953,9 @@
         linker,
         lint_reasons,
         literal,
+        lldb_debugger_visualizer,
+        lldb_python_file,
+        lldb_type_summary_file,
         load,
         loaded_from_disk,
         local,
@@ -1151,14 +1155,18 @@
         panic_implementation,
         panic_in_cleanup,
         panic_info,
+        panic_invalid_value,
         panic_location,
         panic_misaligned_pointer_dereference,
         panic_nounwind,
+        panic_null_pointer_dereference,
         panic_runtime,
+        panic_slice_from_raw_parts_overflow,
         panic_str,
         panic_unwind,
         panicking,
         param_attrs,
+        parameterized_tests,
         parent_label,
         partial_cmp,
         partial_ord,
@@ -1617,12 +1625,14 @@
         test_case,
         test_removed_feature,
         test_runner,
+        test_timeout,
         test_unstable_lint,
         thread,
         thread_local,
         thread_local_macro,
         thumb2,
         thumb_mode: "thumb-mode",
+        timeout,
         tmm_reg,
         to_owned_method,
         to_string,
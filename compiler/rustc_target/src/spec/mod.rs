@@ -2597,20 +2597,52 @@ pub fn from_json(obj: Json) -> Result<(Target, TargetWarnings), String> {
             _ => return Err("Expected JSON object for target")?,
         };
 
-        let mut get_req_field = |name: &str| {
-            obj.remove(name)
-                .and_then(|j| j.as_str().map(str::to_string))
-                .ok_or_else(|| format!("Field {name} in target specification is required"))
+        // A target can be specified as a set of overrides on top of a built-in target, in which
+        // case the fields that are otherwise required are optional as well.
+        let inherits = match obj.remove("inherits") {
+            Some(Value::String(inherits)) => Some(
+                load_builtin(&inherits)
+                    .ok_or_else(|| format!("Cannot inherit from unknown target {inherits}"))?,
+            ),
+            Some(_) => return Err("Field inherits in target specification must be a string")?,
+            None => None,
         };
 
-        let mut base = Target {
-            llvm_target: get_req_field("llvm-target")?.into(),
-            pointer_width: get_req_field("target-pointer-width")?
-                .parse::<u32>()
-                .map_err(|_| "target-pointer-width must be an integer".to_string())?,
-            data_layout: get_req_field("data-layout")?.into(),
-            arch: get_req_field("arch")?.into(),
-            options: Default::default(),
+        let mut get_field =
+            |name: &str| obj.remove(name).and_then(|j| j.as_str().map(str::to_string));
+        let parse_pointer_width = |s: String| {
+            s.parse::<u32>().map_err(|_| "target-pointer-width must be an integer".to_string())
+        };
+
+        let mut base = if let Some(mut base) = inherits {
+            base.is_builtin = false;
+            // The JSON counterparts of the linker options are what the overrides below apply to.
+            base.update_to_cli();
+            if let Some(llvm_target) = get_field("llvm-target") {
+                base.llvm_target = llvm_target.into();
+            }
+            if let Some(pointer_width) = get_field("target-pointer-width") {
+                base.pointer_width = parse_pointer_width(pointer_width)?;
+            }
+            if let Some(data_layout) = get_field("data-layout") {
+                base.data_layout = data_layout.into();
+            }
+            if let Some(arch) = get_field("arch") {
+                base.arch = arch.into();
+            }
+            base
+        } else {
+            let mut get_req_field = |name: &str| {
+                get_field(name)
+                    .ok_or_else(|| format!("Field {name} in target specification is required"))
+            };
+            Target {
+                llvm_target: get_req_field("llvm-target")?.into(),
+                pointer_width: parse_pointer_width(get_req_field("target-pointer-width")?)?,
+                data_layout: get_req_field("data-layout")?.into(),
+                arch: get_req_field("arch")?.into(),
+                options: Default::default(),
+            }
         };
 
         let mut incorrect_type = vec![];
@@ -2846,6 +2878,8 @@ macro_rules! key {
                 let name = (stringify!($key_name)).replace("_", "-");
                 if let Some(o) = obj.remove(&name) {
                     if let Some(a) = o.as_array() {
+                        // Replace rather than extend the set of an inherited target.
+                        base.$key_name = SanitizerSet::empty();
                         for s in a {
                             base.$key_name |= match s.as_str() {
                                 Some("address") => SanitizerSet::ADDRESS,
@@ -2975,6 +3009,7 @@ macro_rules! key {
                 let name = (stringify!($key_name)).replace("_", "-");
                 if let Some(o) = obj.remove(&name) {
                     if let Some(a) = o.as_array() {
+                        base.$key_name = Default::default();
                         for o in a {
                             if let Some(s) = o.as_str() {
                                 let p = s.split('=').collect::<Vec<_>>();
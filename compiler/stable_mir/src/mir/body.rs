@@ -149,6 +149,9 @@ pub enum AssertMessage {
     ResumedAfterReturn(CoroutineKind),
     ResumedAfterPanic(CoroutineKind),
     MisalignedPointerDereference { required: Operand, found: Operand },
+    NullPointerDereference,
+    InvalidValue { value: Operand },
+    SliceFromRawPartsOverflow { len: Operand, size: Operand },
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
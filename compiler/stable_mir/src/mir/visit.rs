@@ -366,12 +366,21 @@ fn super_assert_msg(&mut self, msg: &AssertMessage, location: Location) {
             | AssertMessage::RemainderByZero(op) => {
                 self.visit_operand(op, location);
             }
-            AssertMessage::ResumedAfterReturn(_) | AssertMessage::ResumedAfterPanic(_) => { //nothing to visit
+            AssertMessage::ResumedAfterReturn(_)
+            | AssertMessage::ResumedAfterPanic(_)
+            | AssertMessage::NullPointerDereference => { //nothing to visit
             }
             AssertMessage::MisalignedPointerDereference { required, found } => {
                 self.visit_operand(required, location);
                 self.visit_operand(found, location);
             }
+            AssertMessage::InvalidValue { value } => {
+                self.visit_operand(value, location);
+            }
+            AssertMessage::SliceFromRawPartsOverflow { len, size } => {
+                self.visit_operand(len, location);
+                self.visit_operand(size, location);
+            }
         }
     }
 }
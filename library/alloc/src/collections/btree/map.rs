@@ -3199,6 +3199,72 @@ pub fn insert_before(&mut self, key: K, value: V) {
         }
     }
 
+    /// Inserts the elements from the given `BTreeMap` after the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new elements are
+    /// inserted at the front of the `BTreeMap`. The cursor does not move.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if:
+    /// - the smallest key of `other` compares less than or equal to the current
+    ///   element (if any).
+    /// - the largest key of `other` compares greater than or equal to the next
+    ///   element (if any).
+    #[unstable(feature = "btree_cursors", issue = "107540")]
+    pub fn splice_after(&mut self, other: BTreeMap<K, V, A>) {
+        if let (Some(current), Some((first, _))) = (self.key(), other.first_key_value()) {
+            if first <= current {
+                panic!("keys must be ordered above the current element");
+            }
+        }
+        if let (Some((next, _)), Some((last, _))) = (self.peek_next(), other.last_key_value()) {
+            if last >= next {
+                panic!("keys must be ordered below the next element");
+            }
+        }
+        // Each element is inserted right after the current one, so going from
+        // the largest key down leaves them in sorted order.
+        for (key, value) in other.into_iter().rev() {
+            unsafe {
+                self.insert_after_unchecked(key, value);
+            }
+        }
+    }
+
+    /// Inserts the elements from the given `BTreeMap` before the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new elements are
+    /// inserted at the end of the `BTreeMap`. The cursor does not move.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if:
+    /// - the largest key of `other` compares greater than or equal to the
+    ///   current element (if any).
+    /// - the smallest key of `other` compares less than or equal to the
+    ///   previous element (if any).
+    #[unstable(feature = "btree_cursors", issue = "107540")]
+    pub fn splice_before(&mut self, other: BTreeMap<K, V, A>) {
+        if let (Some(current), Some((last, _))) = (self.key(), other.last_key_value()) {
+            if last >= current {
+                panic!("keys must be ordered below the current element");
+            }
+        }
+        if let (Some((prev, _)), Some((first, _))) = (self.peek_prev(), other.first_key_value()) {
+            if first <= prev {
+                panic!("keys must be ordered above the previous element");
+            }
+        }
+        // Each element is inserted right before the current one, so going from
+        // the smallest key up leaves them in sorted order.
+        for (key, value) in other {
+            unsafe {
+                self.insert_before_unchecked(key, value);
+            }
+        }
+    }
+
     /// Removes the current element from the `BTreeMap`.
     ///
     /// The element that was removed is returned, and the cursor is
@@ -2462,6 +2462,63 @@ fn test_cursor_mut_insert_after_4() {
     cur.insert_after(4, 'd');
 }
 
+#[test]
+fn test_cursor_mut_splice() {
+    let mut map = BTreeMap::from([(1, 'a'), (5, 'e'), (9, 'i')]);
+    let mut cur = map.lower_bound_mut(Bound::Included(&5));
+    cur.splice_after(BTreeMap::from([(6, 'f'), (7, 'g'), (8, 'h')]));
+    assert_eq!(cur.key(), Some(&5));
+    assert_eq!(cur.peek_next(), Some((&6, &mut 'f')));
+    cur.splice_before(BTreeMap::from([(2, 'b'), (3, 'c'), (4, 'd')]));
+    assert_eq!(cur.key(), Some(&5));
+    assert_eq!(cur.peek_prev(), Some((&4, &mut 'd')));
+    cur.splice_after(BTreeMap::new());
+    cur.move_prev();
+    cur.move_prev();
+    cur.move_prev();
+    cur.move_prev();
+    cur.move_prev();
+    assert_eq!(cur.key(), None);
+    cur.splice_after(BTreeMap::from([(-1, '?'), (0, '?')]));
+    cur.splice_before(BTreeMap::from([(10, 'j'), (11, 'k')]));
+    assert_eq!(cur.key(), None);
+    assert_eq!(map.len(), 13);
+    assert_eq!(map.keys().copied().collect::<Vec<_>>(), (-1..=11).collect::<Vec<_>>());
+    map.check();
+}
+
+#[should_panic(expected = "keys must be ordered above the current element")]
+#[test]
+fn test_cursor_mut_splice_after_1() {
+    let mut map = BTreeMap::from([(1, 'a'), (2, 'b'), (5, 'e')]);
+    let mut cur = map.upper_bound_mut(Bound::Included(&2));
+    cur.splice_after(BTreeMap::from([(2, 'x'), (3, 'c')]));
+}
+
+#[should_panic(expected = "keys must be ordered below the next element")]
+#[test]
+fn test_cursor_mut_splice_after_2() {
+    let mut map = BTreeMap::from([(1, 'a'), (2, 'b'), (5, 'e')]);
+    let mut cur = map.upper_bound_mut(Bound::Included(&2));
+    cur.splice_after(BTreeMap::from([(3, 'c'), (5, 'x')]));
+}
+
+#[should_panic(expected = "keys must be ordered below the current element")]
+#[test]
+fn test_cursor_mut_splice_before_1() {
+    let mut map = BTreeMap::from([(1, 'a'), (4, 'd'), (5, 'e')]);
+    let mut cur = map.upper_bound_mut(Bound::Included(&4));
+    cur.splice_before(BTreeMap::from([(2, 'b'), (4, 'x')]));
+}
+
+#[should_panic(expected = "keys must be ordered above the previous element")]
+#[test]
+fn test_cursor_mut_splice_before_2() {
+    let mut map = BTreeMap::from([(1, 'a'), (4, 'd'), (5, 'e')]);
+    let mut cur = map.upper_bound_mut(Bound::Included(&4));
+    cur.splice_before(BTreeMap::from([(1, 'x'), (2, 'b')]));
+}
+
 #[test]
 fn cursor_peek_prev_agrees_with_cursor_mut() {
     let mut map = BTreeMap::from([(1, 1), (2, 2), (3, 3)]);
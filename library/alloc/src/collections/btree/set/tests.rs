@@ -4,7 +4,7 @@
 use crate::vec::Vec;
 use std::cmp::Ordering;
 use std::hash::{Hash, Hasher};
-use std::ops::Bound::{Excluded, Included};
+use std::ops::Bound::{self, Excluded, Included};
 use std::panic::{catch_unwind, AssertUnwindSafe};
 
 #[test]
@@ -856,3 +856,77 @@ fn test_range_panic_2() {
 
     let _invalid_range = set.range((Excluded(&5), Excluded(&5)));
 }
+
+#[test]
+fn test_cursor() {
+    let set = BTreeSet::from([1, 2, 3]);
+
+    let mut cur = set.lower_bound(Bound::Unbounded);
+    assert_eq!(cur.current(), Some(&1));
+    cur.move_next();
+    assert_eq!(cur.current(), Some(&2));
+    assert_eq!(cur.peek_next(), Some(&3));
+    cur.move_prev();
+    assert_eq!(cur.current(), Some(&1));
+    assert_eq!(cur.peek_prev(), None);
+
+    let mut cur = set.upper_bound(Bound::Excluded(&1));
+    assert_eq!(cur.current(), None);
+    cur.move_next();
+    assert_eq!(cur.current(), Some(&1));
+    cur.move_prev();
+    assert_eq!(cur.current(), None);
+    assert_eq!(cur.peek_prev(), Some(&3));
+}
+
+#[test]
+fn test_cursor_mut() {
+    let mut set = BTreeSet::from([1, 3, 5]);
+    let mut cur = set.lower_bound_mut(Bound::Excluded(&3));
+    assert_eq!(cur.current(), Some(&5));
+    cur.insert_before(4);
+    assert_eq!(cur.current(), Some(&5));
+    assert_eq!(cur.peek_prev(), Some(&4));
+    cur.move_next();
+    assert_eq!(cur.current(), None);
+    cur.insert_before(6);
+    assert_eq!(cur.remove_current(), None);
+    cur.insert_after(0);
+    assert_eq!(cur.as_cursor().peek_next(), Some(&0));
+    assert_eq!(set, BTreeSet::from([0, 1, 3, 4, 5, 6]));
+
+    let mut cur = set.upper_bound_mut(Bound::Included(&5));
+    assert_eq!(cur.remove_current(), Some(5));
+    assert_eq!(cur.current(), Some(&6));
+    assert_eq!(cur.remove_current_and_move_back(), Some(6));
+    assert_eq!(cur.current(), Some(&4));
+    assert_eq!(set, BTreeSet::from([0, 1, 3, 4]));
+}
+
+#[should_panic(expected = "key must be ordered above the current element")]
+#[test]
+fn test_cursor_mut_insert_after() {
+    let mut set = BTreeSet::from([1, 2, 3]);
+    let mut cur = set.upper_bound_mut(Bound::Included(&2));
+    cur.insert_after(2);
+}
+
+#[test]
+fn test_cursor_mut_splice() {
+    let mut set = BTreeSet::from([1, 5, 9]);
+    let mut cur = set.lower_bound_mut(Bound::Included(&5));
+    cur.splice_after(BTreeSet::from([6, 7, 8]));
+    cur.splice_before(BTreeSet::from([2, 3, 4]));
+    assert_eq!(cur.current(), Some(&5));
+    assert_eq!(cur.peek_next(), Some(&6));
+    assert_eq!(cur.peek_prev(), Some(&4));
+    assert_eq!(set, BTreeSet::from_iter(1..=9));
+}
+
+#[should_panic(expected = "keys must be ordered below the next element")]
+#[test]
+fn test_cursor_mut_splice_after() {
+    let mut set = BTreeSet::from([1, 2, 5]);
+    let mut cur = set.upper_bound_mut(Bound::Included(&2));
+    cur.splice_after(BTreeSet::from([3, 4, 5]));
+}
@@ -762,6 +762,70 @@ fn test_cursor_pop_front_back() {
     assert_eq!(c.index, 2);
 }
 
+#[test]
+fn test_cursor_remove_deallocates_in_list_allocator() {
+    use crate::alloc::{AllocError, Layout};
+    use std::cell::Cell;
+
+    // Counts the nodes which are live in this allocator, so that freeing a
+    // node through another allocator shows up as a leak.
+    struct CountingAlloc {
+        live: Cell<usize>,
+    }
+    unsafe impl Allocator for &CountingAlloc {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            self.live.set(self.live.get() + 1);
+            Global.allocate(layout)
+        }
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.live.set(self.live.get() - 1);
+            unsafe { Global.deallocate(ptr, layout) }
+        }
+    }
+
+    let alloc = CountingAlloc { live: Cell::new(0) };
+    let mut ll = LinkedList::new_in(&alloc);
+    let mut c = ll.cursor_front_mut();
+    c.insert_after(1);
+    c.insert_after(2);
+    c.insert_after(3);
+    c.insert_after(4);
+    assert_eq!(alloc.live.get(), 4);
+    c.move_next();
+    assert_eq!(c.remove_current(), Some(4));
+    assert_eq!(alloc.live.get(), 3);
+    assert_eq!(c.remove_current_and_move_back(), Some(3));
+    assert_eq!(alloc.live.get(), 2);
+    drop(c);
+    assert_eq!(ll.extract_if(|x| *x == 2).collect::<Vec<_>>(), [2]);
+    assert_eq!(alloc.live.get(), 1);
+    drop(ll);
+    assert_eq!(alloc.live.get(), 0);
+}
+
+#[test]
+fn test_cursor_remove_current_and_move_back() {
+    let mut ll: LinkedList<u32> = LinkedList::new();
+    ll.extend(&[1, 2, 3, 4]);
+    let mut c = ll.cursor_front_mut();
+    c.move_next();
+    c.move_next();
+    assert_eq!(c.remove_current_and_move_back(), Some(3));
+    assert_eq!(c.current(), Some(&mut 2));
+    assert_eq!(c.index(), Some(1));
+    c.move_prev();
+    assert_eq!(c.remove_current_and_move_back(), Some(1));
+    assert_eq!(c.current(), None);
+    assert_eq!(c.index, 2);
+    assert_eq!(c.remove_current_and_move_back(), None);
+    assert_eq!(c.index, 2);
+    c.move_next();
+    assert_eq!(c.current(), Some(&mut 2));
+    drop(c);
+    assert_eq!(ll, [2, 4].into_iter().collect());
+    check_links(&ll);
+}
+
 #[test]
 fn test_extend_ref() {
     let mut a = LinkedList::new();
@@ -0,0 +1,453 @@
+use core::cmp;
+use core::fmt;
+use core::mem;
+
+use crate::alloc::{Allocator, Global};
+
+use super::VecDeque;
+
+/// A cursor over a `VecDeque`.
+///
+/// A `Cursor` is like an iterator, except that it can freely seek back-and-forth.
+///
+/// Cursors always rest between two elements in the deque, and index in a logically circular way.
+/// To accommodate this, there is a "ghost" non-element that yields `None` between the back and
+/// front of the deque.
+///
+/// This `struct` is created by the [`cursor_front`], [`cursor_back`] and [`cursor_at`] methods
+/// on [`VecDeque`].
+///
+/// [`cursor_front`]: VecDeque::cursor_front
+/// [`cursor_back`]: VecDeque::cursor_back
+/// [`cursor_at`]: VecDeque::cursor_at
+#[unstable(feature = "vec_deque_cursors", issue = "none")]
+pub struct Cursor<
+    'a,
+    T: 'a,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator = Global,
+> {
+    // The position of the current element, or `deque.len()` for the "ghost" non-element.
+    index: usize,
+    deque: &'a VecDeque<T, A>,
+}
+
+#[unstable(feature = "vec_deque_cursors", issue = "none")]
+impl<T, A: Allocator> Clone for Cursor<'_, T, A> {
+    fn clone(&self) -> Self {
+        let Cursor { index, deque } = *self;
+        Cursor { index, deque }
+    }
+}
+
+#[unstable(feature = "vec_deque_cursors", issue = "none")]
+impl<T: fmt::Debug, A: Allocator> fmt::Debug for Cursor<'_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Cursor").field(&self.deque).field(&self.index()).finish()
+    }
+}
+
+/// A cursor over a `VecDeque` with editing operations.
+///
+/// A `Cursor` is like an iterator, except that it can freely seek back-and-forth, and can
+/// safely mutate the deque during iteration. This is because the lifetime of its yielded
+/// references is tied to its own lifetime, instead of just the underlying deque. This means
+/// cursors cannot yield multiple elements at once.
+///
+/// Cursors always rest between two elements in the deque, and index in a logically circular way.
+/// To accommodate this, there is a "ghost" non-element that yields `None` between the back and
+/// front of the deque.
+///
+/// Inserting or removing an element through the cursor moves the elements on the shorter side
+/// of it, like [`VecDeque::insert`] and [`VecDeque::remove`] do.
+///
+/// This `struct` is created by the [`cursor_front_mut`], [`cursor_back_mut`] and
+/// [`cursor_at_mut`] methods on [`VecDeque`].
+///
+/// [`cursor_front_mut`]: VecDeque::cursor_front_mut
+/// [`cursor_back_mut`]: VecDeque::cursor_back_mut
+/// [`cursor_at_mut`]: VecDeque::cursor_at_mut
+#[unstable(feature = "vec_deque_cursors", issue = "none")]
+pub struct CursorMut<
+    'a,
+    T: 'a,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator = Global,
+> {
+    // The position of the current element, or `deque.len()` for the "ghost" non-element.
+    index: usize,
+    deque: &'a mut VecDeque<T, A>,
+}
+
+#[unstable(feature = "vec_deque_cursors", issue = "none")]
+impl<T: fmt::Debug, A: Allocator> fmt::Debug for CursorMut<'_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CursorMut").field(&self.deque).field(&self.index()).finish()
+    }
+}
+
+impl<'a, T, A: Allocator> Cursor<'a, T, A> {
+    pub(super) fn new(index: usize, deque: &'a VecDeque<T, A>) -> Self {
+        Cursor { index, deque }
+    }
+
+    /// Returns the cursor position index within the `VecDeque`.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[must_use]
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn index(&self) -> Option<usize> {
+        if self.index < self.deque.len() { Some(self.index) } else { None }
+    }
+
+    /// Moves the cursor to the next element of the `VecDeque`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the first element of the `VecDeque`. If it is pointing to the last
+    /// element of the `VecDeque` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn move_next(&mut self) {
+        self.index = if self.index == self.deque.len() { 0 } else { self.index + 1 };
+    }
+
+    /// Moves the cursor to the previous element of the `VecDeque`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the last element of the `VecDeque`. If it is pointing to the first
+    /// element of the `VecDeque` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn move_prev(&mut self) {
+        self.index = self.index.checked_sub(1).unwrap_or_else(|| self.deque.len());
+    }
+
+    /// Returns a reference to the element that the cursor is currently
+    /// pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[must_use]
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn current(&self) -> Option<&'a T> {
+        self.deque.get(self.index)
+    }
+
+    /// Returns a reference to the next element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the first element of the `VecDeque`. If it is pointing to the last
+    /// element of the `VecDeque` then this returns `None`.
+    #[must_use]
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn peek_next(&self) -> Option<&'a T> {
+        if self.index == self.deque.len() {
+            self.deque.front()
+        } else {
+            self.deque.get(self.index + 1)
+        }
+    }
+
+    /// Returns a reference to the previous element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the last element of the `VecDeque`. If it is pointing to the first
+    /// element of the `VecDeque` then this returns `None`.
+    #[must_use]
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        self.index.checked_sub(1).and_then(|prev| self.deque.get(prev))
+    }
+
+    /// Provides a reference to the front element of the cursor's parent deque,
+    /// or None if the deque is empty.
+    #[must_use]
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn front(&self) -> Option<&'a T> {
+        self.deque.front()
+    }
+
+    /// Provides a reference to the back element of the cursor's parent deque,
+    /// or None if the deque is empty.
+    #[must_use]
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn back(&self) -> Option<&'a T> {
+        self.deque.back()
+    }
+}
+
+impl<'a, T, A: Allocator> CursorMut<'a, T, A> {
+    pub(super) fn new(index: usize, deque: &'a mut VecDeque<T, A>) -> Self {
+        CursorMut { index, deque }
+    }
+
+    /// Returns the cursor position index within the `VecDeque`.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[must_use]
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn index(&self) -> Option<usize> {
+        if self.index < self.deque.len() { Some(self.index) } else { None }
+    }
+
+    /// Moves the cursor to the next element of the `VecDeque`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the first element of the `VecDeque`. If it is pointing to the last
+    /// element of the `VecDeque` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn move_next(&mut self) {
+        self.index = if self.index == self.deque.len() { 0 } else { self.index + 1 };
+    }
+
+    /// Moves the cursor to the previous element of the `VecDeque`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the last element of the `VecDeque`. If it is pointing to the first
+    /// element of the `VecDeque` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn move_prev(&mut self) {
+        self.index = self.index.checked_sub(1).unwrap_or_else(|| self.deque.len());
+    }
+
+    /// Returns a reference to the element that the cursor is currently
+    /// pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[must_use]
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.deque.get_mut(self.index)
+    }
+
+    /// Returns a reference to the next element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the first element of the `VecDeque`. If it is pointing to the last
+    /// element of the `VecDeque` then this returns `None`.
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        if self.index == self.deque.len() {
+            self.deque.front_mut()
+        } else {
+            self.deque.get_mut(self.index + 1)
+        }
+    }
+
+    /// Returns a reference to the previous element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the last element of the `VecDeque`. If it is pointing to the first
+    /// element of the `VecDeque` then this returns `None`.
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        self.index.checked_sub(1).and_then(|prev| self.deque.get_mut(prev))
+    }
+
+    /// Returns a read-only cursor pointing to the current element.
+    ///
+    /// The lifetime of the returned `Cursor` is bound to that of the
+    /// `CursorMut`, which means it cannot outlive the `CursorMut` and that the
+    /// `CursorMut` is frozen for the lifetime of the `Cursor`.
+    #[must_use]
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn as_cursor(&self) -> Cursor<'_, T, A> {
+        Cursor { index: self.index, deque: self.deque }
+    }
+}
+
+// Now the deque editing operations
+
+impl<'a, T> CursorMut<'a, T> {
+    /// Inserts the elements from the given `VecDeque` after the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new elements are
+    /// inserted at the start of the `VecDeque`.
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn splice_after(&mut self, mut other: VecDeque<T>) {
+        let at = if self.index == self.deque.len() { 0 } else { self.index + 1 };
+        let count = other.len();
+        self.deque.append(&mut other);
+        self.deque.make_contiguous()[at..].rotate_right(count);
+        if at == 0 {
+            // The "ghost" non-element's index has changed.
+            self.index = self.deque.len();
+        }
+    }
+
+    /// Inserts the elements from the given `VecDeque` before the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new elements are
+    /// inserted at the end of the `VecDeque`.
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn splice_before(&mut self, mut other: VecDeque<T>) {
+        let count = other.len();
+        self.deque.append(&mut other);
+        self.deque.make_contiguous()[self.index..].rotate_right(count);
+        self.index += count;
+    }
+}
+
+impl<'a, T, A: Allocator> CursorMut<'a, T, A> {
+    /// Inserts a new element into the `VecDeque` after the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new element is
+    /// inserted at the front of the `VecDeque`.
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn insert_after(&mut self, item: T) {
+        if self.index == self.deque.len() {
+            self.deque.push_front(item);
+            // The "ghost" non-element's index has changed.
+            self.index = self.deque.len();
+        } else {
+            self.deque.insert(self.index + 1, item);
+        }
+    }
+
+    /// Inserts a new element into the `VecDeque` before the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new element is
+    /// inserted at the end of the `VecDeque`.
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn insert_before(&mut self, item: T) {
+        self.deque.insert(self.index, item);
+        self.index += 1;
+    }
+
+    /// Removes the current element from the `VecDeque`.
+    ///
+    /// The element that was removed is returned, and the cursor is
+    /// moved to point to the next element in the `VecDeque`.
+    ///
+    /// If the cursor is currently pointing to the "ghost" non-element then no element
+    /// is removed and `None` is returned.
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn remove_current(&mut self) -> Option<T> {
+        self.deque.remove(self.index)
+    }
+
+    /// Removes the current element from the `VecDeque`.
+    ///
+    /// The element that was removed is returned, and the cursor is
+    /// moved to point to the previous element in the `VecDeque`.
+    ///
+    /// If the cursor is currently pointing to the "ghost" non-element then no element
+    /// is removed and `None` is returned. The cursor is not moved in this case.
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn remove_current_and_move_back(&mut self) -> Option<T> {
+        let removed = self.deque.remove(self.index)?;
+        self.move_prev();
+        Some(removed)
+    }
+
+    /// Splits the deque into two after the current element. This will return a
+    /// new deque consisting of everything after the cursor, with the original
+    /// deque retaining everything before.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the entire contents
+    /// of the `VecDeque` are moved.
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn split_after(&mut self) -> VecDeque<T, A>
+    where
+        A: Clone,
+    {
+        if self.index == self.deque.len() {
+            // The "ghost" non-element's index has changed to 0.
+            self.index = 0;
+            return self.deque.split_off(0);
+        }
+        self.deque.split_off(self.index + 1)
+    }
+
+    /// Splits the deque into two before the current element. This will return a
+    /// new deque consisting of everything before the cursor, with the original
+    /// deque retaining everything after.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the entire contents
+    /// of the `VecDeque` are moved.
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn split_before(&mut self) -> VecDeque<T, A>
+    where
+        A: Clone,
+    {
+        let back = self.deque.split_off(self.index);
+        self.index = 0;
+        mem::replace(self.deque, back)
+    }
+
+    /// Appends an element to the front of the cursor's parent deque. The element
+    /// that the cursor points to is unchanged, even if it is the "ghost" non-element.
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn push_front(&mut self, elt: T) {
+        self.deque.push_front(elt);
+        self.index += 1;
+    }
+
+    /// Appends an element to the back of the cursor's parent deque. The element
+    /// that the cursor points to is unchanged, even if it is the "ghost" non-element.
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn push_back(&mut self, elt: T) {
+        if self.index == self.deque.len() {
+            // The "ghost" non-element's index has changed.
+            self.index += 1;
+        }
+        self.deque.push_back(elt);
+    }
+
+    /// Removes the first element from the cursor's parent deque and returns it,
+    /// or None if the deque is empty. The element the cursor points to remains
+    /// unchanged, unless it was pointing to the front element. In that case, it
+    /// points to the new front element.
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn pop_front(&mut self) -> Option<T> {
+        let front = self.deque.pop_front()?;
+        // If the cursor was at the front it stays at index 0, which is the new
+        // front element, or the "ghost" non-element if the deque is now empty.
+        self.index = self.index.saturating_sub(1);
+        Some(front)
+    }
+
+    /// Removes the last element from the cursor's parent deque and returns it,
+    /// or None if the deque is empty. The element the cursor points to remains
+    /// unchanged, unless it was pointing to the back element. In that case, it
+    /// points to the "ghost" non-element.
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn pop_back(&mut self) -> Option<T> {
+        let back = self.deque.pop_back()?;
+        // If the cursor was at the back, its index is now the length of the
+        // deque, which is the "ghost" non-element.
+        self.index = cmp::min(self.index, self.deque.len());
+        Some(back)
+    }
+
+    /// Provides a reference to the front element of the cursor's parent deque,
+    /// or None if the deque is empty.
+    #[must_use]
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn front(&self) -> Option<&T> {
+        self.deque.front()
+    }
+
+    /// Provides a mutable reference to the front element of the cursor's
+    /// parent deque, or None if the deque is empty.
+    #[must_use]
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.deque.front_mut()
+    }
+
+    /// Provides a reference to the back element of the cursor's parent deque,
+    /// or None if the deque is empty.
+    #[must_use]
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn back(&self) -> Option<&T> {
+        self.deque.back()
+    }
+
+    /// Provides a mutable reference to the back element of the cursor's parent
+    /// deque, or None if the deque is empty.
+    #[must_use]
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.deque.back_mut()
+    }
+}
@@ -31,6 +31,11 @@
 #[macro_use]
 mod macros;
 
+#[unstable(feature = "vec_deque_cursors", issue = "none")]
+pub use self::cursor::{Cursor, CursorMut};
+
+mod cursor;
+
 #[stable(feature = "drain", since = "1.6.0")]
 pub use self::drain::Drain;
 
@@ -1424,6 +1429,108 @@ pub fn drain<R>(&mut self, range: R) -> Drain<'_, T, A>
         unsafe { Drain::new(self, drain_start, drain_len) }
     }
 
+    /// Provides a cursor at the front element.
+    ///
+    /// The cursor is pointing to the "ghost" non-element if the deque is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(vec_deque_cursors)]
+    ///
+    /// use std::collections::VecDeque;
+    ///
+    /// let deque = VecDeque::from([1, 2, 3]);
+    /// let mut cursor = deque.cursor_front();
+    /// assert_eq!(cursor.current(), Some(&1));
+    /// cursor.move_next();
+    /// assert_eq!(cursor.current(), Some(&2));
+    /// assert_eq!(cursor.peek_next(), Some(&3));
+    /// ```
+    #[inline]
+    #[must_use]
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn cursor_front(&self) -> Cursor<'_, T, A> {
+        Cursor::new(0, self)
+    }
+
+    /// Provides a cursor with editing operations at the front element.
+    ///
+    /// The cursor is pointing to the "ghost" non-element if the deque is empty.
+    #[inline]
+    #[must_use]
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T, A> {
+        CursorMut::new(0, self)
+    }
+
+    /// Provides a cursor at the back element.
+    ///
+    /// The cursor is pointing to the "ghost" non-element if the deque is empty.
+    #[inline]
+    #[must_use]
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn cursor_back(&self) -> Cursor<'_, T, A> {
+        Cursor::new(self.len.saturating_sub(1), self)
+    }
+
+    /// Provides a cursor with editing operations at the back element.
+    ///
+    /// The cursor is pointing to the "ghost" non-element if the deque is empty.
+    #[inline]
+    #[must_use]
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T, A> {
+        CursorMut::new(self.len.saturating_sub(1), self)
+    }
+
+    /// Provides a cursor at the element at `index`.
+    ///
+    /// The cursor is pointing to the "ghost" non-element if `index` equals
+    /// the length of the deque.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the length of the deque.
+    #[inline]
+    #[must_use]
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn cursor_at(&self, index: usize) -> Cursor<'_, T, A> {
+        assert!(index <= self.len, "index out of bounds");
+        Cursor::new(index, self)
+    }
+
+    /// Provides a cursor with editing operations at the element at `index`.
+    ///
+    /// The cursor is pointing to the "ghost" non-element if `index` equals
+    /// the length of the deque.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the length of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(vec_deque_cursors)]
+    ///
+    /// use std::collections::VecDeque;
+    ///
+    /// let mut deque = VecDeque::from([1, 2, 4, 5]);
+    /// let mut cursor = deque.cursor_at_mut(2);
+    /// cursor.insert_before(3);
+    /// assert_eq!(cursor.remove_current(), Some(4));
+    /// assert_eq!(cursor.current(), Some(&mut 5));
+    /// assert_eq!(deque, [1, 2, 3, 5]);
+    /// ```
+    #[inline]
+    #[must_use]
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn cursor_at_mut(&mut self, index: usize) -> CursorMut<'_, T, A> {
+        assert!(index <= self.len, "index out of bounds");
+        CursorMut::new(index, self)
+    }
+
     /// Clears the deque, removing all values.
     ///
     /// # Examples
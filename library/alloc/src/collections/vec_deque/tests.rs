@@ -1172,3 +1172,135 @@ fn hash_code(value: impl Hash) -> u64 {
     assert_eq!(vda, vdb);
     assert_eq!(hash_code(vda), hash_code(vdb));
 }
+
+#[test]
+fn test_cursor_move_peek() {
+    let deq: VecDeque<u32> = VecDeque::from([1, 2, 3, 4, 5, 6]);
+    let mut cursor = deq.cursor_front();
+    assert_eq!(cursor.current(), Some(&1));
+    assert_eq!(cursor.peek_next(), Some(&2));
+    assert_eq!(cursor.peek_prev(), None);
+    assert_eq!(cursor.index(), Some(0));
+    cursor.move_prev();
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.peek_next(), Some(&1));
+    assert_eq!(cursor.peek_prev(), Some(&6));
+    assert_eq!(cursor.index(), None);
+    cursor.move_next();
+    cursor.move_next();
+    assert_eq!(cursor.current(), Some(&2));
+    assert_eq!(cursor.index(), Some(1));
+
+    let mut cursor = deq.cursor_back();
+    assert_eq!(cursor.current(), Some(&6));
+    assert_eq!(cursor.peek_next(), None);
+    assert_eq!(cursor.peek_prev(), Some(&5));
+    cursor.move_next();
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.peek_next(), Some(&1));
+    cursor.move_prev();
+    assert_eq!(cursor.current(), Some(&6));
+
+    let empty: VecDeque<u32> = VecDeque::new();
+    let mut cursor = empty.cursor_back();
+    assert_eq!(cursor.current(), None);
+    cursor.move_next();
+    assert_eq!(cursor.current(), None);
+    cursor.move_prev();
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.peek_next(), None);
+    assert_eq!(cursor.peek_prev(), None);
+}
+
+#[test]
+fn test_cursor_mut_insert_remove() {
+    let mut deq: VecDeque<u32> = VecDeque::from([1, 2, 3, 4, 5, 6]);
+    let mut cursor = deq.cursor_front_mut();
+    cursor.insert_before(7);
+    cursor.insert_after(8);
+    assert_eq!(cursor.current(), Some(&mut 1));
+    assert_eq!(cursor.index(), Some(1));
+    assert_eq!(deq, [7, 1, 8, 2, 3, 4, 5, 6]);
+
+    let mut cursor = deq.cursor_front_mut();
+    cursor.move_prev();
+    cursor.insert_before(9);
+    cursor.insert_after(10);
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.peek_next(), Some(&mut 10));
+    assert_eq!(cursor.peek_prev(), Some(&mut 9));
+    assert_eq!(deq, [10, 7, 1, 8, 2, 3, 4, 5, 6, 9]);
+
+    let mut cursor = deq.cursor_at_mut(1);
+    assert_eq!(cursor.remove_current(), Some(7));
+    assert_eq!(cursor.current(), Some(&mut 1));
+    assert_eq!(cursor.remove_current_and_move_back(), Some(1));
+    assert_eq!(cursor.current(), Some(&mut 10));
+    assert_eq!(cursor.remove_current_and_move_back(), Some(10));
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.remove_current(), None);
+    cursor.move_prev();
+    assert_eq!(cursor.remove_current(), Some(9));
+    assert_eq!(cursor.current(), None);
+    assert_eq!(deq, [8, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn test_cursor_mut_splice_split() {
+    let mut deq: VecDeque<u32> = VecDeque::from([1, 8, 2, 3, 4, 5, 6]);
+    let mut cursor = deq.cursor_front_mut();
+    cursor.splice_after(VecDeque::from([100, 101, 102, 103]));
+    cursor.splice_before(VecDeque::from([200, 201, 202, 203]));
+    assert_eq!(cursor.current(), Some(&mut 1));
+    assert_eq!(cursor.index(), Some(4));
+    cursor.move_prev();
+    cursor.move_prev();
+    cursor.move_prev();
+    cursor.move_prev();
+    cursor.move_prev();
+    cursor.splice_after(VecDeque::from([300]));
+    cursor.splice_before(VecDeque::from([400]));
+    assert_eq!(cursor.current(), None);
+    assert_eq!(deq, [300, 200, 201, 202, 203, 1, 100, 101, 102, 103, 8, 2, 3, 4, 5, 6, 400]);
+
+    let mut cursor = deq.cursor_at_mut(7);
+    let tail = cursor.split_after();
+    assert_eq!(tail, [102, 103, 8, 2, 3, 4, 5, 6, 400]);
+    assert_eq!(cursor.current(), Some(&mut 101));
+    let head = cursor.split_before();
+    assert_eq!(head, [300, 200, 201, 202, 203, 1, 100]);
+    assert_eq!(cursor.index(), Some(0));
+    assert_eq!(deq, [101]);
+
+    let mut cursor = deq.cursor_at_mut(1);
+    let all = cursor.split_after();
+    assert_eq!(all, [101]);
+    assert_eq!(cursor.current(), None);
+    assert!(deq.is_empty());
+}
+
+#[test]
+fn test_cursor_mut_push_pop() {
+    let mut deq: VecDeque<u32> = VecDeque::from([1, 2, 3, 4]);
+    let mut cursor = deq.cursor_front_mut();
+    cursor.push_front(0);
+    assert_eq!(cursor.current(), Some(&mut 1));
+    assert_eq!(cursor.index(), Some(1));
+    assert_eq!(cursor.pop_front(), Some(0));
+    assert_eq!(cursor.pop_front(), Some(1));
+    assert_eq!(cursor.current(), Some(&mut 2));
+    assert_eq!(cursor.index(), Some(0));
+    cursor.move_prev();
+    cursor.push_back(5);
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.peek_prev(), Some(&mut 5));
+    assert_eq!(cursor.pop_back(), Some(5));
+    assert_eq!(cursor.current(), None);
+    cursor.move_prev();
+    assert_eq!(cursor.pop_back(), Some(4));
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.front(), Some(&2));
+    *cursor.back_mut().unwrap() = 30;
+    assert_eq!(cursor.back(), Some(&30));
+    assert_eq!(deq, [2, 30]);
+}
@@ -208,6 +208,61 @@ fn panic_misaligned_pointer_dereference(required: usize, found: usize) -> ! {
     )
 }
 
+#[cold]
+#[cfg_attr(not(feature = "panic_immediate_abort"), inline(never))]
+#[track_caller]
+#[cfg(not(bootstrap))]
+#[lang = "panic_null_pointer_dereference"] // needed by codegen for panic on null pointer deref
+#[rustc_nounwind] // `CheckUb` MIR pass requires this function to never unwind
+fn panic_null_pointer_dereference() -> ! {
+    if cfg!(feature = "panic_immediate_abort") {
+        super::intrinsics::abort()
+    }
+
+    panic_nounwind_fmt(
+        format_args!("null pointer dereference occurred"),
+        /* force_no_backtrace */ false,
+    )
+}
+
+#[cold]
+#[cfg_attr(not(feature = "panic_immediate_abort"), inline(never))]
+#[track_caller]
+#[cfg(not(bootstrap))]
+#[lang = "panic_invalid_value"] // needed by codegen for panic on invalid scalar values
+#[rustc_nounwind] // `CheckUb` MIR pass requires this function to never unwind
+fn panic_invalid_value(value: u128) -> ! {
+    if cfg!(feature = "panic_immediate_abort") {
+        super::intrinsics::abort()
+    }
+
+    panic_nounwind_fmt(
+        format_args!(
+            "invalid value {value:#x} read through a pointer or transmuted into a type that does not allow it"
+        ),
+        /* force_no_backtrace */ false,
+    )
+}
+
+#[cold]
+#[cfg_attr(not(feature = "panic_immediate_abort"), inline(never))]
+#[track_caller]
+#[cfg(not(bootstrap))]
+#[lang = "panic_slice_from_raw_parts_overflow"] // needed by codegen for panic on oversized slices
+#[rustc_nounwind] // `CheckUb` MIR pass requires this function to never unwind
+fn panic_slice_from_raw_parts_overflow(len: usize, size: usize) -> ! {
+    if cfg!(feature = "panic_immediate_abort") {
+        super::intrinsics::abort()
+    }
+
+    panic_nounwind_fmt(
+        format_args!(
+            "slice::from_raw_parts called with {len} elements of {size} bytes, which is more than isize::MAX bytes"
+        ),
+        /* force_no_backtrace */ false,
+    )
+}
+
 /// Panic because we cannot unwind out of a function.
 ///
 /// This is a separate function to avoid the codesize impact of each crate containing the string to
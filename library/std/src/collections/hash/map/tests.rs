@@ -1,8 +1,11 @@
 use super::Entry::{Occupied, Vacant};
 use super::HashMap;
 use super::RandomState;
+use crate::alloc::{AllocError, Allocator, Global, Layout};
 use crate::assert_matches::assert_matches;
-use crate::cell::RefCell;
+use crate::cell::{Cell, RefCell};
+use crate::ptr::NonNull;
+use crate::rc::Rc;
 use crate::test_helpers::test_rng;
 use rand::Rng;
 use realstd::collections::TryReserveErrorKind::*;
@@ -1124,3 +1127,52 @@ fn const_with_hasher() {
     const X: HashMap<(), (), ()> = HashMap::with_hasher(());
     assert_eq!(X.len(), 0);
 }
+
+/// An allocator that counts the live allocations made through it.
+#[derive(Clone, Default)]
+struct CountingAlloc(Rc<Cell<usize>>);
+
+unsafe impl Allocator for CountingAlloc {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.0.set(self.0.get() + 1);
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.0.set(self.0.get() - 1);
+        unsafe { Global.deallocate(ptr, layout) }
+    }
+}
+
+#[test]
+fn test_allocator() {
+    let alloc = CountingAlloc::default();
+    let mut m = HashMap::new_in(alloc.clone());
+    assert_eq!(alloc.0.get(), 0);
+
+    for i in 0..100 {
+        m.insert(i, i * 10);
+    }
+    *m.entry(1).or_insert(0) += 1;
+    assert_eq!(m[&1], 11);
+    assert_eq!(alloc.0.get(), 1);
+
+    let m2 = m.clone();
+    assert_eq!(alloc.0.get(), 2);
+    assert_eq!(m, m2);
+    assert_eq!(m2.into_iter().count(), 100);
+    assert_eq!(alloc.0.get(), 1);
+
+    assert_eq!(m.extract_if(|&k, _| k % 2 == 0).count(), 50);
+    assert_eq!(m.drain().count(), 50);
+    assert!(m.is_empty());
+    drop(m);
+    assert_eq!(alloc.0.get(), 0);
+
+    let m: HashMap<i32, i32, _, _> =
+        HashMap::with_capacity_and_hasher_in(10, RandomState::new(), alloc.clone());
+    assert!(m.capacity() >= 10);
+    assert_eq!(m.allocator().0.get(), 1);
+    drop(m);
+    assert_eq!(alloc.0.get(), 0);
+}
@@ -1,7 +1,11 @@
 use super::super::map::RandomState;
 use super::HashSet;
 
+use crate::alloc::{AllocError, Allocator, Global, Layout};
+use crate::cell::Cell;
 use crate::panic::{catch_unwind, AssertUnwindSafe};
+use crate::ptr::NonNull;
+use crate::rc::Rc;
 use crate::sync::atomic::{AtomicU32, Ordering};
 use crate::sync::Arc;
 
@@ -524,3 +528,41 @@ fn test_insert_does_not_overwrite_the_value() {
             must be the same as first value pointer we inserted"
     );
 }
+
+/// An allocator that counts the live allocations made through it.
+#[derive(Clone, Default)]
+struct CountingAlloc(Rc<Cell<usize>>);
+
+unsafe impl Allocator for CountingAlloc {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.0.set(self.0.get() + 1);
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.0.set(self.0.get() - 1);
+        unsafe { Global.deallocate(ptr, layout) }
+    }
+}
+
+#[test]
+fn test_allocator() {
+    let alloc = CountingAlloc::default();
+    let mut a = HashSet::new_in(alloc.clone());
+    let mut b = HashSet::with_capacity_in(10, alloc.clone());
+    assert_eq!(alloc.0.get(), 1);
+
+    a.extend(0..10);
+    b.extend(5..15);
+    assert_eq!(alloc.0.get(), 2);
+    assert_eq!(a.intersection(&b).count(), 5);
+    assert_eq!(a.union(&b).count(), 15);
+    assert_eq!(a.symmetric_difference(&b).count(), 10);
+    assert!(!a.is_subset(&b));
+
+    assert_eq!(b.into_iter().count(), 10);
+    assert_eq!(alloc.0.get(), 1);
+    assert_eq!(a.drain().count(), 10);
+    drop(a);
+    assert_eq!(alloc.0.get(), 0);
+}
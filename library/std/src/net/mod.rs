@@ -7,6 +7,8 @@
 //!
 //! * [`TcpListener`] and [`TcpStream`] provide functionality for communication over TCP
 //! * [`UdpSocket`] provides functionality for communication over UDP
+//! * [`TcpSocketBuilder`] and [`UdpSocketBuilder`] allow setting socket options before
+//!   binding or connecting a socket
 //! * [`IpAddr`] represents IP addresses of either IPv4 or IPv6; [`Ipv4Addr`] and
 //!   [`Ipv6Addr`] are respectively IPv4 and IPv6 addresses
 //! * [`SocketAddr`] represents socket addresses of either IPv4 or IPv6; [`SocketAddrV4`]
@@ -29,10 +31,14 @@
 pub use self::socket_addr::{SocketAddr, SocketAddrV4, SocketAddrV6, ToSocketAddrs};
 #[unstable(feature = "tcplistener_into_incoming", issue = "88339")]
 pub use self::tcp::IntoIncoming;
+#[unstable(feature = "socket_builder", issue = "none")]
+pub use self::tcp::TcpSocketBuilder;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::tcp::{Incoming, TcpListener, TcpStream};
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::udp::UdpSocket;
+#[unstable(feature = "socket_builder", issue = "none")]
+pub use self::udp::UdpSocketBuilder;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use core::net::AddrParseError;
 
@@ -87,3 +93,12 @@ fn each_addr<A: ToSocketAddrs, F, T>(addr: A, mut f: F) -> io::Result<T>
         io::const_io_error!(ErrorKind::InvalidInput, "could not resolve to any addresses")
     }))
 }
+
+fn first_addr_of_family<A: ToSocketAddrs>(addr: A, ipv6: bool) -> io::Result<SocketAddr> {
+    addr.to_socket_addrs()?.find(|addr| addr.is_ipv6() == ipv6).ok_or_else(|| {
+        io::const_io_error!(
+            ErrorKind::InvalidInput,
+            "could not resolve to any addresses of the socket's family",
+        )
+    })
+}
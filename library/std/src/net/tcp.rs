@@ -110,6 +110,38 @@ pub struct IntoIncoming {
     listener: TcpListener,
 }
 
+/// A builder for a TCP socket, which allows setting socket options before
+/// the socket is bound, and turning it into a [`TcpListener`] or a
+/// [`TcpStream`].
+///
+/// [`TcpListener::bind`] and [`TcpStream::connect`] create their socket and
+/// bind or connect it in one step, with the default options of the platform.
+/// Options such as `SO_REUSEPORT` or `IPV6_V6ONLY`, or the size of the
+/// backlog of pending connections, have to be chosen before that, which is
+/// what a `TcpSocketBuilder` is for.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(socket_builder)]
+/// use std::net::TcpSocketBuilder;
+///
+/// fn main() -> std::io::Result<()> {
+///     let listener = TcpSocketBuilder::new_v6()?
+///         .only_v6(false)?
+///         .reuse_address(true)?
+///         .bind("[::]:8080")?
+///         .listen(1024)?;
+///
+///     for stream in listener.incoming() {
+///         // ...
+///     }
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "socket_builder", issue = "none")]
+pub struct TcpSocketBuilder(net_imp::SocketBuilder);
+
 impl TcpStream {
     /// Opens a TCP connection to a remote host.
     ///
@@ -1069,3 +1101,287 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.0.fmt(f)
     }
 }
+
+impl TcpSocketBuilder {
+    /// Creates a new IPv4 TCP socket, which can be bound to an IPv4 address.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_builder)]
+    /// use std::net::TcpSocketBuilder;
+    ///
+    /// let builder = TcpSocketBuilder::new_v4().expect("couldn't create socket");
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn new_v4() -> io::Result<TcpSocketBuilder> {
+        net_imp::SocketBuilder::tcp(false).map(TcpSocketBuilder)
+    }
+
+    /// Creates a new IPv6 TCP socket, which can be bound to an IPv6 address.
+    ///
+    /// Depending on [`only_v6`], the socket may also accept connections from
+    /// or connect to IPv4 addresses, in the form of IPv4-mapped IPv6
+    /// addresses.
+    ///
+    /// [`only_v6`]: TcpSocketBuilder::only_v6
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_builder)]
+    /// use std::net::TcpSocketBuilder;
+    ///
+    /// let builder = TcpSocketBuilder::new_v6().expect("couldn't create socket");
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn new_v6() -> io::Result<TcpSocketBuilder> {
+        net_imp::SocketBuilder::tcp(true).map(TcpSocketBuilder)
+    }
+
+    /// Sets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// On Unix platforms, this allows binding to an address which is still in
+    /// use by a socket in the `TIME_WAIT` state, which is what
+    /// [`TcpListener::bind`] always does.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Windows, `SO_REUSEADDR` allows binding to an address which is
+    /// actively in use by another socket, even one from another process,
+    /// which can then steal the connections meant for it. Windows already
+    /// allows binding to an address in the `TIME_WAIT` state without it, so
+    /// [`TcpListener::bind`] doesn't set it there, and servers shouldn't
+    /// either. Conversely, a listener bound without this option can still be
+    /// hijacked by a socket which sets it, unless the listener sets
+    /// `SO_EXCLUSIVEADDRUSE`, which this builder doesn't do.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_builder)]
+    /// use std::net::TcpSocketBuilder;
+    ///
+    /// let listener = TcpSocketBuilder::new_v4()?
+    ///     .reuse_address(true)?
+    ///     .bind("127.0.0.1:8080")?
+    ///     .listen(128)?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn reuse_address(self, reuse: bool) -> io::Result<TcpSocketBuilder> {
+        self.0.set_reuse_address(reuse)?;
+        Ok(self)
+    }
+
+    /// Sets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// This allows several sockets to be bound to the same address, as long as
+    /// all of them set this option. On Linux, the incoming connections are
+    /// then distributed between the listeners.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This returns an error of the kind [`io::ErrorKind::Unsupported`] on
+    /// platforms without `SO_REUSEPORT`, such as Windows.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_builder)]
+    /// use std::net::TcpSocketBuilder;
+    ///
+    /// let listener = TcpSocketBuilder::new_v4()?
+    ///     .reuse_port(true)?
+    ///     .bind("0.0.0.0:8080")?
+    ///     .listen(128)?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn reuse_port(self, reuse: bool) -> io::Result<TcpSocketBuilder> {
+        self.0.set_reuse_port(reuse)?;
+        Ok(self)
+    }
+
+    /// Sets the value of the `IPV6_V6ONLY` option on this socket.
+    ///
+    /// If this is set to `true`, the socket is restricted to IPv6
+    /// communication only. Otherwise, it can also communicate with IPv4
+    /// addresses through IPv4-mapped IPv6 addresses. Unlike
+    /// [`TcpListener::set_only_v6`], this takes effect because the socket
+    /// isn't bound yet.
+    ///
+    /// This returns an error if the socket was created with
+    /// [`TcpSocketBuilder::new_v4`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_builder)]
+    /// use std::net::TcpSocketBuilder;
+    ///
+    /// let listener = TcpSocketBuilder::new_v6()?
+    ///     .only_v6(true)?
+    ///     .bind("[::]:8080")?
+    ///     .listen(128)?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn only_v6(self, only_v6: bool) -> io::Result<TcpSocketBuilder> {
+        self.0.set_only_v6(only_v6)?;
+        Ok(self)
+    }
+
+    /// Sets the size of the send buffer of this socket, in bytes, with the
+    /// `SO_SNDBUF` option.
+    ///
+    /// The operating system may adjust the size, such as Linux doubling it to
+    /// account for its bookkeeping.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_builder)]
+    /// use std::net::TcpSocketBuilder;
+    ///
+    /// let stream = TcpSocketBuilder::new_v4()?
+    ///     .send_buffer_size(1 << 20)?
+    ///     .connect("127.0.0.1:8080")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn send_buffer_size(self, size: usize) -> io::Result<TcpSocketBuilder> {
+        self.0.set_send_buffer_size(size)?;
+        Ok(self)
+    }
+
+    /// Sets the size of the receive buffer of this socket, in bytes, with the
+    /// `SO_RCVBUF` option.
+    ///
+    /// The operating system may adjust the size, such as Linux doubling it to
+    /// account for its bookkeeping. The size has to be set before connecting
+    /// for the TCP window scale to account for it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_builder)]
+    /// use std::net::TcpSocketBuilder;
+    ///
+    /// let stream = TcpSocketBuilder::new_v4()?
+    ///     .recv_buffer_size(1 << 20)?
+    ///     .connect("127.0.0.1:8080")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn recv_buffer_size(self, size: usize) -> io::Result<TcpSocketBuilder> {
+        self.0.set_recv_buffer_size(size)?;
+        Ok(self)
+    }
+
+    /// Binds this socket to the specified address.
+    ///
+    /// This is needed before [`listen`]ing, and can be used to choose the
+    /// local address before [`connect`]ing. Binding with a port number of 0
+    /// will request that the OS assigns a port to this socket.
+    ///
+    /// If `addr` yields multiple addresses, `bind` will be attempted with each
+    /// of the addresses until one succeeds. If none of the addresses succeed,
+    /// the error returned from the last attempt (the last address) is returned.
+    ///
+    /// [`connect`]: TcpSocketBuilder::connect
+    /// [`listen`]: TcpSocketBuilder::listen
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_builder)]
+    /// use std::net::TcpSocketBuilder;
+    ///
+    /// let stream = TcpSocketBuilder::new_v4()?
+    ///     .bind("192.168.0.2:0")?
+    ///     .connect("192.168.0.1:8080")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn bind<A: ToSocketAddrs>(self, addr: A) -> io::Result<TcpSocketBuilder> {
+        super::each_addr(addr, |addr| self.0.bind(addr))?;
+        Ok(self)
+    }
+
+    /// Returns the local socket address of this socket.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_builder)]
+    /// use std::net::TcpSocketBuilder;
+    ///
+    /// let builder = TcpSocketBuilder::new_v4()?.bind("127.0.0.1:0")?;
+    /// println!("bound to {}", builder.local_addr()?);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.0.socket_addr()
+    }
+
+    /// Starts listening for connections on this socket, which must have been
+    /// bound with [`bind`], and returns it as a [`TcpListener`].
+    ///
+    /// `backlog` is the number of pending connections which the operating
+    /// system keeps before refusing new ones. It may be capped by the
+    /// operating system, such as by `net.core.somaxconn` on Linux.
+    /// [`TcpListener::bind`] uses a backlog of 128.
+    ///
+    /// [`bind`]: TcpSocketBuilder::bind
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_builder)]
+    /// use std::net::TcpSocketBuilder;
+    ///
+    /// let listener = TcpSocketBuilder::new_v4()?.bind("127.0.0.1:8080")?.listen(4096)?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn listen(self, backlog: u32) -> io::Result<TcpListener> {
+        self.0.listen(backlog)?;
+        Ok(TcpListener(self.0.into_tcp_listener()))
+    }
+
+    /// Connects this socket to a remote host, and returns it as a
+    /// [`TcpStream`].
+    ///
+    /// Unlike [`TcpStream::connect`], only the first address yielded by
+    /// `addr` which has the family of this socket, IPv4 or IPv6, is tried:
+    /// once a connection attempt failed, the socket can't portably be
+    /// connected again, as BSD and macOS reject it with `EINVAL`. If `addr`
+    /// yields no address of that family, an error of the kind
+    /// [`io::ErrorKind::InvalidInput`] is returned. To fall back to other
+    /// addresses, resolve them with [`ToSocketAddrs`] and use a new builder
+    /// for each of them.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_builder)]
+    /// use std::net::TcpSocketBuilder;
+    ///
+    /// let stream = TcpSocketBuilder::new_v4()?.connect("127.0.0.1:8080")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn connect<A: ToSocketAddrs>(self, addr: A) -> io::Result<TcpStream> {
+        self.0.connect(Ok(&super::first_addr_of_family(addr, self.0.is_ipv6())?))?;
+        Ok(TcpStream(self.0.into_tcp_stream()))
+    }
+}
+
+#[unstable(feature = "socket_builder", issue = "none")]
+impl fmt::Debug for TcpSocketBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
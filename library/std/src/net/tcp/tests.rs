@@ -914,3 +914,80 @@ fn connect_timeout_valid() {
     let addr = listener.local_addr().unwrap();
     TcpStream::connect_timeout(&addr, Duration::from_secs(2)).unwrap();
 }
+
+fn tcp_builder(addr: &SocketAddr) -> TcpSocketBuilder {
+    if addr.is_ipv4() { t!(TcpSocketBuilder::new_v4()) } else { t!(TcpSocketBuilder::new_v6()) }
+}
+
+#[test]
+#[cfg_attr(target_env = "sgx", ignore)] // sockets can't be created before binding on SGX
+fn socket_builder_listen_connect() {
+    each_ip(&mut |addr| {
+        let builder = t!(tcp_builder(&addr).reuse_address(true));
+        let builder = t!(builder.recv_buffer_size(64 * 1024));
+        let builder = t!(builder.bind(&addr));
+        assert_eq!(t!(builder.local_addr()), addr);
+        let listener = t!(builder.listen(16));
+        assert_eq!(t!(listener.local_addr()), addr);
+
+        let _t = thread::spawn(move || {
+            let builder = t!(tcp_builder(&addr).send_buffer_size(64 * 1024));
+            let mut stream = t!(builder.connect(&addr));
+            assert_eq!(t!(stream.peer_addr()), addr);
+            t!(stream.write(&[99]));
+        });
+
+        let mut stream = t!(listener.accept()).0;
+        let mut buf = [0];
+        t!(stream.read(&mut buf));
+        assert_eq!(buf[0], 99);
+    })
+}
+
+#[test]
+#[cfg_attr(target_env = "sgx", ignore)] // sockets can't be created before binding on SGX
+fn socket_builder_only_v6() {
+    let addr = next_test_ip6();
+    let listener = t!(t!(t!(TcpSocketBuilder::new_v6()).only_v6(true)).bind(&addr)).listen(16);
+    assert!(t!(t!(listener).only_v6()));
+
+    assert!(t!(TcpSocketBuilder::new_v4()).only_v6(true).is_err());
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn socket_builder_reuse_port() {
+    let addr = next_test_ip4();
+    let listen = || t!(t!(t!(TcpSocketBuilder::new_v4()).reuse_port(true)).bind(&addr)).listen(16);
+    let _first = t!(listen());
+    let _second = t!(listen());
+
+    // Without `SO_REUSEPORT`, the address is in use.
+    let err = t!(TcpSocketBuilder::new_v4()).bind(&addr).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::AddrInUse);
+}
+
+#[test]
+#[cfg_attr(target_env = "sgx", ignore)] // sockets can't be created before binding on SGX
+fn socket_builder_connect_first_addr() {
+    let addrs: &[SocketAddr] = &[];
+    let err = t!(TcpSocketBuilder::new_v4()).connect(addrs).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+    // Only the first address is tried, even though the second one accepts
+    // the connection.
+    let listener = t!(TcpListener::bind(&next_test_ip4()));
+    let closed = t!(t!(TcpListener::bind(&next_test_ip4())).local_addr());
+    let addrs = [closed, t!(listener.local_addr())];
+    assert!(t!(TcpSocketBuilder::new_v4()).connect(&addrs[..]).is_err());
+
+    // Addresses of the other family are skipped, like `::1` coming before
+    // `127.0.0.1` when resolving `localhost`.
+    let addrs = [next_test_ip6(), t!(listener.local_addr())];
+    let stream = t!(t!(TcpSocketBuilder::new_v4()).connect(&addrs[..]));
+    assert_eq!(t!(stream.peer_addr()), addrs[1]);
+
+    let addrs = [t!(listener.local_addr())];
+    let err = t!(TcpSocketBuilder::new_v6()).connect(&addrs[..]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
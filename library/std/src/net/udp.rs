@@ -57,6 +57,34 @@
 #[stable(feature = "rust1", since = "1.0.0")]
 pub struct UdpSocket(net_imp::UdpSocket);
 
+/// A builder for a UDP socket, which allows setting socket options before
+/// the socket is bound, and turning it into a [`UdpSocket`].
+///
+/// [`UdpSocket::bind`] creates its socket and binds it in one step, with the
+/// default options of the platform. Options such as `SO_REUSEPORT` or
+/// `IPV6_V6ONLY` have to be chosen before that, which is what a
+/// `UdpSocketBuilder` is for.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(socket_builder)]
+/// use std::net::UdpSocketBuilder;
+///
+/// fn main() -> std::io::Result<()> {
+///     let socket = UdpSocketBuilder::new_v4()?
+///         .reuse_address(true)?
+///         .recv_buffer_size(1 << 20)?
+///         .bind("0.0.0.0:5353")?;
+///
+///     let mut buf = [0; 512];
+///     let (amt, src) = socket.recv_from(&mut buf)?;
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "socket_builder", issue = "none")]
+pub struct UdpSocketBuilder(net_imp::SocketBuilder);
+
 impl UdpSocket {
     /// Creates a UDP socket from the given address.
     ///
@@ -840,3 +868,190 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.0.fmt(f)
     }
 }
+
+impl UdpSocketBuilder {
+    /// Creates a new IPv4 UDP socket, which can be bound to an IPv4 address.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_builder)]
+    /// use std::net::UdpSocketBuilder;
+    ///
+    /// let builder = UdpSocketBuilder::new_v4().expect("couldn't create socket");
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn new_v4() -> io::Result<UdpSocketBuilder> {
+        net_imp::SocketBuilder::udp(false).map(UdpSocketBuilder)
+    }
+
+    /// Creates a new IPv6 UDP socket, which can be bound to an IPv6 address.
+    ///
+    /// Depending on [`only_v6`], the socket may also communicate with IPv4
+    /// addresses, in the form of IPv4-mapped IPv6 addresses.
+    ///
+    /// [`only_v6`]: UdpSocketBuilder::only_v6
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_builder)]
+    /// use std::net::UdpSocketBuilder;
+    ///
+    /// let builder = UdpSocketBuilder::new_v6().expect("couldn't create socket");
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn new_v6() -> io::Result<UdpSocketBuilder> {
+        net_imp::SocketBuilder::udp(true).map(UdpSocketBuilder)
+    }
+
+    /// Sets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// On most platforms, this allows several UDP sockets to be bound to the
+    /// same multicast address, as long as all of them set this option.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Windows, `SO_REUSEADDR` also allows binding to a unicast address
+    /// which is actively in use by another socket, even one from another
+    /// process, which then receives some of the datagrams meant for it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_builder)]
+    /// use std::net::UdpSocketBuilder;
+    ///
+    /// let socket = UdpSocketBuilder::new_v4()?.reuse_address(true)?.bind("0.0.0.0:5353")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn reuse_address(self, reuse: bool) -> io::Result<UdpSocketBuilder> {
+        self.0.set_reuse_address(reuse)?;
+        Ok(self)
+    }
+
+    /// Sets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// This allows several sockets to be bound to the same address, as long as
+    /// all of them set this option. On Linux, the datagrams sent to a unicast
+    /// address are then distributed between the sockets.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This returns an error of the kind [`io::ErrorKind::Unsupported`] on
+    /// platforms without `SO_REUSEPORT`, such as Windows.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_builder)]
+    /// use std::net::UdpSocketBuilder;
+    ///
+    /// let socket = UdpSocketBuilder::new_v4()?.reuse_port(true)?.bind("0.0.0.0:8080")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn reuse_port(self, reuse: bool) -> io::Result<UdpSocketBuilder> {
+        self.0.set_reuse_port(reuse)?;
+        Ok(self)
+    }
+
+    /// Sets the value of the `IPV6_V6ONLY` option on this socket.
+    ///
+    /// If this is set to `true`, the socket is restricted to IPv6
+    /// communication only. Otherwise, it can also communicate with IPv4
+    /// addresses through IPv4-mapped IPv6 addresses.
+    ///
+    /// This returns an error if the socket was created with
+    /// [`UdpSocketBuilder::new_v4`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_builder)]
+    /// use std::net::UdpSocketBuilder;
+    ///
+    /// let socket = UdpSocketBuilder::new_v6()?.only_v6(true)?.bind("[::]:8080")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn only_v6(self, only_v6: bool) -> io::Result<UdpSocketBuilder> {
+        self.0.set_only_v6(only_v6)?;
+        Ok(self)
+    }
+
+    /// Sets the size of the send buffer of this socket, in bytes, with the
+    /// `SO_SNDBUF` option.
+    ///
+    /// The operating system may adjust the size, such as Linux doubling it to
+    /// account for its bookkeeping.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_builder)]
+    /// use std::net::UdpSocketBuilder;
+    ///
+    /// let socket = UdpSocketBuilder::new_v4()?.send_buffer_size(1 << 20)?.bind("0.0.0.0:0")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn send_buffer_size(self, size: usize) -> io::Result<UdpSocketBuilder> {
+        self.0.set_send_buffer_size(size)?;
+        Ok(self)
+    }
+
+    /// Sets the size of the receive buffer of this socket, in bytes, with the
+    /// `SO_RCVBUF` option.
+    ///
+    /// The operating system may adjust the size, such as Linux doubling it to
+    /// account for its bookkeeping.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_builder)]
+    /// use std::net::UdpSocketBuilder;
+    ///
+    /// let socket = UdpSocketBuilder::new_v4()?.recv_buffer_size(1 << 20)?.bind("0.0.0.0:0")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn recv_buffer_size(self, size: usize) -> io::Result<UdpSocketBuilder> {
+        self.0.set_recv_buffer_size(size)?;
+        Ok(self)
+    }
+
+    /// Binds this socket to the specified address, and returns it as a
+    /// [`UdpSocket`].
+    ///
+    /// Binding with a port number of 0 will request that the OS assigns a port
+    /// to this socket.
+    ///
+    /// If `addr` yields multiple addresses, `bind` will be attempted with each
+    /// of the addresses until one succeeds. If none of the addresses succeed,
+    /// the error returned from the last attempt (the last address) is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_builder)]
+    /// use std::net::UdpSocketBuilder;
+    ///
+    /// let socket = UdpSocketBuilder::new_v4()?.bind("127.0.0.1:34254")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn bind<A: ToSocketAddrs>(self, addr: A) -> io::Result<UdpSocket> {
+        super::each_addr(addr, |addr| self.0.bind(addr))?;
+        Ok(UdpSocket(self.0.into_udp_socket()))
+    }
+}
+
+#[unstable(feature = "socket_builder", issue = "none")]
+impl fmt::Debug for UdpSocketBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
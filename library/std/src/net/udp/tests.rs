@@ -366,3 +366,39 @@ fn set_nonblocking() {
         }
     })
 }
+
+#[test]
+fn socket_builder_bind() {
+    each_ip(&mut |server_ip, client_ip| {
+        let builder = |addr: &SocketAddr| {
+            if addr.is_ipv4() {
+                t!(UdpSocketBuilder::new_v4())
+            } else {
+                t!(UdpSocketBuilder::new_v6())
+            }
+        };
+        let server = t!(t!(builder(&server_ip).recv_buffer_size(64 * 1024)).bind(&server_ip));
+        let client = t!(t!(builder(&client_ip).send_buffer_size(64 * 1024)).bind(&client_ip));
+        assert_eq!(t!(server.local_addr()), server_ip);
+
+        t!(client.send_to(&[99], &server_ip));
+        let mut buf = [0];
+        let (nread, src) = t!(server.recv_from(&mut buf));
+        assert_eq!(nread, 1);
+        assert_eq!(buf[0], 99);
+        assert_eq!(src, client_ip);
+    })
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn socket_builder_reuse_port() {
+    let addr = next_test_ip4();
+    let bind = || t!(t!(UdpSocketBuilder::new_v4()).reuse_port(true)).bind(&addr);
+    let _first = t!(bind());
+    let _second = t!(bind());
+
+    // Without `SO_REUSEPORT`, the address is in use.
+    let err = t!(UdpSocketBuilder::new_v4()).bind(&addr).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::AddrInUse);
+}
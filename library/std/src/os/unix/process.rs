@@ -350,6 +350,86 @@ fn into_raw(self) -> i32 {
     }
 }
 
+/// Unix-specific extensions to [`process::Child`].
+///
+/// This trait is sealed: it cannot be implemented outside the standard library.
+/// This is so that future additional methods are not breaking changes.
+#[unstable(feature = "unix_child_signal", issue = "none")]
+pub trait ChildExt: Sealed {
+    /// Sends the signal `signal` to the child, like `kill(2)`.
+    ///
+    /// If the child has already been waited on, this does nothing and returns `Ok(())`,
+    /// since its process ID may have been reused by an unrelated process.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_child_signal)]
+    ///
+    /// use std::os::unix::process::ChildExt;
+    /// use std::process::Command;
+    ///
+    /// const SIGTERM: i32 = 15;
+    ///
+    /// let mut child = Command::new("sleep").arg("10").spawn().unwrap();
+    /// child.signal(SIGTERM).unwrap();
+    /// child.wait().unwrap();
+    /// ```
+    #[unstable(feature = "unix_child_signal", issue = "none")]
+    fn signal(&mut self, signal: i32) -> io::Result<()>;
+
+    /// Sends the signal `signal` to every process of the child's process group,
+    /// like `killpg(3)`.
+    ///
+    /// The process group is the one that the child was put in with
+    /// [`CommandExt::process_group`]: with `process_group(0)`, it is the new group
+    /// led by the child, which also contains the processes that the child spawned,
+    /// unless they changed groups. This lets a supervisor stop a whole tree of
+    /// processes, even after the child itself has exited.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`InvalidInput`] if the child wasn't spawned with
+    /// [`CommandExt::process_group`], since the child is then in the group of the
+    /// current process.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_child_signal)]
+    ///
+    /// use std::os::unix::process::{ChildExt, CommandExt};
+    /// use std::process::Command;
+    ///
+    /// const SIGTERM: i32 = 15;
+    ///
+    /// let mut child = Command::new("sh")
+    ///     .arg("-c")
+    ///     .arg("sleep 10 & sleep 10")
+    ///     .process_group(0)
+    ///     .spawn()
+    ///     .unwrap();
+    /// // Stops both `sleep`s, not just the shell.
+    /// child.kill_process_group(SIGTERM).unwrap();
+    /// child.wait().unwrap();
+    /// ```
+    ///
+    /// [`InvalidInput`]: io::ErrorKind::InvalidInput
+    #[unstable(feature = "unix_child_signal", issue = "none")]
+    fn kill_process_group(&mut self, signal: i32) -> io::Result<()>;
+}
+
+#[unstable(feature = "unix_child_signal", issue = "none")]
+impl ChildExt for process::Child {
+    fn signal(&mut self, signal: i32) -> io::Result<()> {
+        self.handle.send_signal(signal)
+    }
+
+    fn kill_process_group(&mut self, signal: i32) -> io::Result<()> {
+        self.handle.kill_process_group(signal)
+    }
+}
+
 #[stable(feature = "process_extensions", since = "1.2.0")]
 impl FromRawFd for process::Stdio {
     #[inline]
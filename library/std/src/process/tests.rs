@@ -120,6 +120,22 @@ fn stdin_works() {
     assert_eq!(out, "foobar\n");
 }
 
+#[test]
+#[cfg_attr(any(windows, target_os = "vxworks"), ignore)]
+fn test_wait_timeout() {
+    use crate::time::Duration;
+
+    let mut p =
+        shell_cmd().arg("-c").arg("read line; exit 0").stdin(Stdio::piped()).spawn().unwrap();
+    assert!(p.wait_timeout(Duration::ZERO).unwrap().is_none());
+    assert!(p.wait_timeout(Duration::from_millis(50)).unwrap().is_none());
+    drop(p.stdin.take());
+    let status = p.wait_timeout(Duration::from_secs(60)).unwrap().expect("child didn't exit");
+    assert!(status.success());
+    // The status is still returned once the child has been reaped.
+    assert_eq!(p.wait_timeout(Duration::ZERO).unwrap(), Some(status));
+}
+
 #[test]
 #[cfg_attr(any(target_os = "vxworks"), ignore)]
 fn child_stdout_read_buf() {
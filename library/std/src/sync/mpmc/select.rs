@@ -1,3 +1,16 @@
+//! Selection over multiple channel operations.
+
+use super::context::Context;
+use super::error::*;
+use super::utils;
+use super::{Receiver, Sender};
+
+use crate::fmt;
+use crate::marker::PhantomData;
+use crate::mem;
+use crate::thread;
+use crate::time::{Duration, Instant};
+
 /// Temporary data that gets initialized during a blocking operation, and is consumed by
 /// `read` or `write`.
 ///
@@ -6,7 +19,6 @@
 pub struct Token {
     pub(crate) array: super::array::ArrayToken,
     pub(crate) list: super::list::ListToken,
-    #[allow(dead_code)]
     pub(crate) zero: super::zero::ZeroToken,
 }
 
@@ -69,3 +81,445 @@ fn into(self) -> usize {
         }
     }
 }
+
+/// A channel operation that can be selected among others.
+///
+/// The flavors implement this trait on their `counter::Sender` for send operations, and on their
+/// `counter::Receiver` for receive operations.
+pub(crate) trait SelectHandle {
+    /// Attempts to select the operation without blocking, initializing `token` on success.
+    fn try_select(&self, token: &mut Token) -> bool;
+
+    /// Registers the operation so that the current thread is woken up once it may be ready.
+    ///
+    /// Returns `true` if the operation is ready already.
+    fn register(&self, oper: Operation, cx: &Context) -> bool;
+
+    /// Unregisters an operation registered by the current thread.
+    fn unregister(&self, oper: Operation);
+
+    /// Attempts to complete the operation after another thread selected it for us.
+    fn accept(&self, token: &mut Token, cx: &Context) -> bool;
+}
+
+/// How long a select operation is allowed to block.
+#[derive(Debug, Clone, Copy)]
+enum Timeout {
+    /// No blocking.
+    Now,
+
+    /// Block forever.
+    Never,
+
+    /// Block until the specified instant.
+    At(Instant),
+}
+
+/// Selects one of the operations, blocking according to `timeout`.
+///
+/// On success, returns the token initialized for the selected operation along with its index
+/// and the address of its channel handle.
+fn run_select(
+    handles: &mut [(&dyn SelectHandle, usize, *const u8)],
+    timeout: Timeout,
+) -> Option<(Token, usize, *const u8)> {
+    if handles.is_empty() {
+        // Wait until the timeout and return.
+        match timeout {
+            Timeout::Now => return None,
+            Timeout::Never => {
+                utils::sleep_until(None);
+                unreachable!();
+            }
+            Timeout::At(when) => {
+                utils::sleep_until(Some(when));
+                return None;
+            }
+        }
+    }
+
+    // Shuffle the operations for fairness.
+    utils::shuffle(handles);
+
+    // Create a token, which serves as a temporary variable that gets initialized in this function
+    // and is later used by a call to `read` or `write` that completes the selected operation.
+    let mut token = Token::default();
+
+    // Try selecting one of the operations without blocking.
+    for &(handle, i, ptr) in handles.iter() {
+        if handle.try_select(&mut token) {
+            return Some((token, i, ptr));
+        }
+    }
+
+    loop {
+        // Prepare for blocking.
+        let res = Context::with(|cx| {
+            let mut sel = Selected::Waiting;
+            let mut registered_count = 0;
+            let mut index_ready = None;
+
+            if let Timeout::Now = timeout {
+                cx.try_select(Selected::Aborted).unwrap();
+            }
+
+            // Register all operations.
+            for (handle, i, _) in handles.iter_mut() {
+                registered_count += 1;
+
+                // If registration returns `true`, the operation has just become ready.
+                if handle.register(Operation::hook::<&dyn SelectHandle>(handle), cx) {
+                    // Try aborting select.
+                    sel = match cx.try_select(Selected::Aborted) {
+                        Ok(()) => {
+                            index_ready = Some(*i);
+                            Selected::Aborted
+                        }
+                        Err(s) => s,
+                    };
+                    break;
+                }
+
+                // If another thread has already selected one of the operations, stop
+                // registration.
+                sel = cx.selected();
+                if sel != Selected::Waiting {
+                    break;
+                }
+            }
+
+            if sel == Selected::Waiting {
+                let deadline = match timeout {
+                    Timeout::Now => unreachable!(),
+                    Timeout::Never => None,
+                    Timeout::At(when) => Some(when),
+                };
+
+                // Block the current thread.
+                sel = cx.wait_until(deadline);
+            }
+
+            // Unregister all registered operations.
+            for (handle, _, _) in handles.iter_mut().take(registered_count) {
+                handle.unregister(Operation::hook::<&dyn SelectHandle>(handle));
+            }
+
+            match sel {
+                Selected::Waiting => unreachable!(),
+                Selected::Aborted => {
+                    // If an operation became ready during registration, try selecting it.
+                    if let Some(index_ready) = index_ready {
+                        for &(handle, i, ptr) in handles.iter() {
+                            if i == index_ready && handle.try_select(&mut token) {
+                                return Some((i, ptr));
+                            }
+                        }
+                    }
+                }
+                Selected::Disconnected => {}
+                Selected::Operation(_) => {
+                    // Find the selected operation.
+                    for (handle, i, ptr) in handles.iter_mut() {
+                        // Is this the selected operation?
+                        if sel == Selected::Operation(Operation::hook::<&dyn SelectHandle>(handle))
+                        {
+                            // Try selecting this operation.
+                            if handle.accept(&mut token, cx) {
+                                return Some((*i, *ptr));
+                            }
+                        }
+                    }
+                }
+            }
+
+            None
+        });
+
+        // Return if an operation was selected.
+        if let Some((i, ptr)) = res {
+            return Some((token, i, ptr));
+        }
+
+        // Try selecting one of the operations without blocking.
+        for &(handle, i, ptr) in handles.iter() {
+            if handle.try_select(&mut token) {
+                return Some((token, i, ptr));
+            }
+        }
+
+        match timeout {
+            Timeout::Now => return None,
+            Timeout::Never => {}
+            Timeout::At(when) => {
+                if Instant::now() >= when {
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+/// Selects from a set of channel operations.
+///
+/// `Select` allows you to define a set of send and receive operations on any number of channels,
+/// and then wait until any one of them becomes ready. The selected operation must then be
+/// completed by calling [`SelectedOperation::send`] or [`SelectedOperation::recv`] with the
+/// same channel handle that was used to add it.
+///
+/// A send operation is ready when the message can be sent into the channel, and a receive
+/// operation is ready when a message can be received from it. Operations on disconnected
+/// channels are ready too, and completing them returns an error. When several operations are
+/// ready at the same time, one of them is chosen at random.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(mpmc_channel)]
+///
+/// use std::sync::mpmc::{channel, Select};
+/// use std::thread;
+///
+/// let (tx1, rx1) = channel::<i32>();
+/// let (tx2, rx2) = channel();
+///
+/// thread::spawn(move || tx2.send(10).unwrap());
+///
+/// let mut sel = Select::new();
+/// let oper1 = sel.recv(&rx1);
+/// let oper2 = sel.recv(&rx2);
+///
+/// // Only the second channel receives a message, so only `oper2` can be selected.
+/// let oper = sel.select();
+/// match oper.index() {
+///     i if i == oper1 => unreachable!(),
+///     i if i == oper2 => assert_eq!(oper.recv(&rx2), Ok(10)),
+///     _ => unreachable!(),
+/// }
+/// # drop(tx1);
+/// ```
+#[unstable(feature = "mpmc_channel", issue = "126840")]
+pub struct Select<'a> {
+    /// A list of operations, each with its index and the address of its channel handle.
+    handles: Vec<(&'a dyn SelectHandle, usize, *const u8)>,
+}
+
+// SAFETY: Selection only registers and unregisters operations with the channels, which is
+// synchronized by the channels themselves, and never moves messages between threads. The raw
+// pointers are only compared, never dereferenced.
+#[unstable(feature = "mpmc_channel", issue = "126840")]
+unsafe impl Send for Select<'_> {}
+// SAFETY: See the `Send` impl above. `Select` has no interior mutability.
+#[unstable(feature = "mpmc_channel", issue = "126840")]
+unsafe impl Sync for Select<'_> {}
+
+impl<'a> Select<'a> {
+    /// Creates an empty list of channel operations for selection.
+    #[must_use]
+    #[unstable(feature = "mpmc_channel", issue = "126840")]
+    pub fn new() -> Select<'a> {
+        Select { handles: Vec::new() }
+    }
+
+    /// Adds a send operation.
+    ///
+    /// Returns the index of the added operation, which is compared with
+    /// [`SelectedOperation::index`] to find out which operation was selected.
+    #[unstable(feature = "mpmc_channel", issue = "126840")]
+    pub fn send<T>(&mut self, s: &'a Sender<T>) -> usize {
+        let i = self.handles.len();
+        let ptr = s as *const Sender<T> as *const u8;
+        self.handles.push((s, i, ptr));
+        i
+    }
+
+    /// Adds a receive operation.
+    ///
+    /// Returns the index of the added operation, which is compared with
+    /// [`SelectedOperation::index`] to find out which operation was selected.
+    #[unstable(feature = "mpmc_channel", issue = "126840")]
+    pub fn recv<T>(&mut self, r: &'a Receiver<T>) -> usize {
+        let i = self.handles.len();
+        let ptr = r as *const Receiver<T> as *const u8;
+        self.handles.push((r, i, ptr));
+        i
+    }
+
+    /// Attempts to select one of the operations without blocking.
+    ///
+    /// If an operation is ready, it is selected and returned. If several operations are ready at
+    /// the same time, a random one among them is selected. If none of the operations are ready,
+    /// an error is returned.
+    ///
+    /// The selected operation must be completed with [`SelectedOperation::send`] or
+    /// [`SelectedOperation::recv`].
+    #[unstable(feature = "mpmc_channel", issue = "126840")]
+    pub fn try_select(&mut self) -> Result<SelectedOperation<'a>, TrySelectError> {
+        match run_select(&mut self.handles, Timeout::Now) {
+            Some((token, index, ptr)) => Ok(SelectedOperation::new(token, index, ptr)),
+            None => Err(TrySelectError),
+        }
+    }
+
+    /// Blocks until one of the operations becomes ready and selects it.
+    ///
+    /// If several operations are ready at the same time, a random one among them is selected.
+    /// If the list of operations is empty, this method blocks forever.
+    ///
+    /// The selected operation must be completed with [`SelectedOperation::send`] or
+    /// [`SelectedOperation::recv`].
+    #[unstable(feature = "mpmc_channel", issue = "126840")]
+    pub fn select(&mut self) -> SelectedOperation<'a> {
+        let (token, index, ptr) = run_select(&mut self.handles, Timeout::Never).unwrap();
+        SelectedOperation::new(token, index, ptr)
+    }
+
+    /// Blocks for a limited time until one of the operations becomes ready and selects it.
+    ///
+    /// If several operations are ready at the same time, a random one among them is selected.
+    /// If none of the operations become ready before the timeout, an error is returned.
+    ///
+    /// The selected operation must be completed with [`SelectedOperation::send`] or
+    /// [`SelectedOperation::recv`].
+    #[unstable(feature = "mpmc_channel", issue = "126840")]
+    pub fn select_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<SelectedOperation<'a>, SelectTimeoutError> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.select_deadline(deadline),
+            // So far in the future that it's practically the same as waiting indefinitely.
+            None => Ok(self.select()),
+        }
+    }
+
+    /// Blocks until a given deadline, or until one of the operations becomes ready and selects
+    /// it.
+    ///
+    /// If several operations are ready at the same time, a random one among them is selected.
+    /// If none of the operations become ready before the deadline, an error is returned.
+    ///
+    /// The selected operation must be completed with [`SelectedOperation::send`] or
+    /// [`SelectedOperation::recv`].
+    #[unstable(feature = "mpmc_channel", issue = "126840")]
+    pub fn select_deadline(
+        &mut self,
+        deadline: Instant,
+    ) -> Result<SelectedOperation<'a>, SelectTimeoutError> {
+        match run_select(&mut self.handles, Timeout::At(deadline)) {
+            Some((token, index, ptr)) => Ok(SelectedOperation::new(token, index, ptr)),
+            None => Err(SelectTimeoutError),
+        }
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "126840")]
+impl<'a> Default for Select<'a> {
+    fn default() -> Select<'a> {
+        Select::new()
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "126840")]
+impl fmt::Debug for Select<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Select { .. }")
+    }
+}
+
+/// A selected operation that needs to be completed.
+///
+/// The operation is completed by calling [`send`] or [`recv`], with the channel handle that was
+/// used to add the operation to the [`Select`].
+///
+/// Dropping a `SelectedOperation` without completing it is a bug, as it may have reserved a slot
+/// in the channel or paired up with a thread blocked on the other side of the channel. The drop
+/// panics in that case, unless the thread is already panicking.
+///
+/// [`send`]: SelectedOperation::send
+/// [`recv`]: SelectedOperation::recv
+#[must_use = "the selected operation must be completed with `send` or `recv`"]
+#[unstable(feature = "mpmc_channel", issue = "126840")]
+pub struct SelectedOperation<'a> {
+    /// Token needed to complete the operation.
+    token: Token,
+
+    /// The index of the selected operation.
+    index: usize,
+
+    /// The address of the selected channel handle.
+    ptr: *const u8,
+
+    /// Indicates that `Select`s with lifetime `'a` are borrowed.
+    _marker: PhantomData<&'a ()>,
+}
+
+impl SelectedOperation<'_> {
+    fn new(token: Token, index: usize, ptr: *const u8) -> Self {
+        SelectedOperation { token, index, ptr, _marker: PhantomData }
+    }
+
+    /// Returns the index of the selected operation.
+    ///
+    /// This is the index that was returned by [`Select::send`] or [`Select::recv`] when the
+    /// operation was added.
+    #[must_use]
+    #[unstable(feature = "mpmc_channel", issue = "126840")]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Completes the send operation.
+    ///
+    /// The passed [`Sender`] reference must be the same one that was used in [`Select::send`]
+    /// when the operation was added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an incorrect [`Sender`] reference is passed.
+    #[unstable(feature = "mpmc_channel", issue = "126840")]
+    pub fn send<T>(mut self, s: &Sender<T>, msg: T) -> Result<(), SendError<T>> {
+        assert!(
+            s as *const Sender<T> as *const u8 == self.ptr,
+            "passed a sender that wasn't selected",
+        );
+        let res = unsafe { s.write(&mut self.token, msg) };
+        mem::forget(self);
+        res.map_err(SendError)
+    }
+
+    /// Completes the receive operation.
+    ///
+    /// The passed [`Receiver`] reference must be the same one that was used in [`Select::recv`]
+    /// when the operation was added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an incorrect [`Receiver`] reference is passed.
+    #[unstable(feature = "mpmc_channel", issue = "126840")]
+    pub fn recv<T>(mut self, r: &Receiver<T>) -> Result<T, RecvError> {
+        assert!(
+            r as *const Receiver<T> as *const u8 == self.ptr,
+            "passed a receiver that wasn't selected",
+        );
+        let res = unsafe { r.read(&mut self.token) };
+        mem::forget(self);
+        res.map_err(|_| RecvError)
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "126840")]
+impl fmt::Debug for SelectedOperation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SelectedOperation").field("index", &self.index).finish_non_exhaustive()
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "126840")]
+impl Drop for SelectedOperation<'_> {
+    fn drop(&mut self) {
+        // Don't turn a panic between selecting and completing the operation into an abort.
+        if !thread::panicking() {
+            panic!("dropped `SelectedOperation` without completing the operation");
+        }
+    }
+}
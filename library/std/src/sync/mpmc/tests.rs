@@ -0,0 +1,267 @@
+use super::*;
+use crate::collections::HashSet;
+use crate::thread;
+use crate::time::{Duration, Instant};
+
+#[test]
+fn smoke() {
+    let (tx, rx) = channel::<i32>();
+    tx.send(1).unwrap();
+    assert_eq!(rx.recv().unwrap(), 1);
+}
+
+#[test]
+fn smoke_sync() {
+    let (tx, rx) = sync_channel::<i32>(1);
+    tx.send(1).unwrap();
+    assert_eq!(tx.try_send(2), Err(TrySendError::Full(2)));
+    assert_eq!(rx.recv().unwrap(), 1);
+}
+
+#[test]
+fn cloned_receivers_share_messages() {
+    for cap in [None, Some(0), Some(1), Some(16)] {
+        let (tx, rx) = match cap {
+            None => channel(),
+            Some(cap) => sync_channel(cap),
+        };
+
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let rx = rx.clone();
+                thread::spawn(move || {
+                    let mut received = Vec::new();
+                    while let Ok(n) = rx.recv() {
+                        received.push(n);
+                    }
+                    received
+                })
+            })
+            .collect();
+        drop(rx);
+
+        for n in 0..1000 {
+            tx.send(n).unwrap();
+        }
+        drop(tx);
+
+        // Every message is received exactly once.
+        let mut received: Vec<i32> = threads.into_iter().flat_map(|t| t.join().unwrap()).collect();
+        received.sort();
+        assert_eq!(received, (0..1000).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn disconnected_when_all_receivers_dropped() {
+    let (tx, rx) = channel::<i32>();
+    let rx2 = rx.clone();
+    drop(rx);
+    tx.send(1).unwrap();
+    drop(rx2);
+    assert_eq!(tx.send(2), Err(SendError(2)));
+}
+
+#[test]
+fn try_recv() {
+    let (tx, rx) = channel::<i32>();
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    tx.send(1).unwrap();
+    assert_eq!(rx.try_recv(), Ok(1));
+    drop(tx);
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+}
+
+#[test]
+fn recv_timeout() {
+    let (tx, rx) = sync_channel::<i32>(0);
+    let start = Instant::now();
+    assert_eq!(rx.recv_timeout(Duration::from_millis(10)), Err(RecvTimeoutError::Timeout));
+    assert!(start.elapsed() >= Duration::from_millis(10));
+
+    let t = thread::spawn(move || tx.send(1).unwrap());
+    assert_eq!(rx.recv_timeout(Duration::from_secs(10)), Ok(1));
+    t.join().unwrap();
+    assert_eq!(rx.recv_timeout(Duration::from_millis(10)), Err(RecvTimeoutError::Disconnected));
+}
+
+#[test]
+fn recv_deadline() {
+    let (tx, rx) = channel::<i32>();
+    let deadline = Instant::now() + Duration::from_millis(10);
+    assert_eq!(rx.recv_deadline(deadline), Err(RecvTimeoutError::Timeout));
+    assert!(Instant::now() >= deadline);
+
+    tx.send(1).unwrap();
+    assert_eq!(rx.recv_deadline(deadline), Ok(1));
+}
+
+#[test]
+fn select_recv() {
+    let (tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = channel::<i32>();
+    tx2.send(2).unwrap();
+
+    let mut sel = Select::new();
+    let oper1 = sel.recv(&rx1);
+    let oper2 = sel.recv(&rx2);
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper2);
+    assert_eq!(oper.recv(&rx2), Ok(2));
+
+    tx1.send(1).unwrap();
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper1);
+    assert_eq!(oper.recv(&rx1), Ok(1));
+}
+
+#[test]
+fn select_send() {
+    let (tx1, rx1) = sync_channel::<i32>(1);
+    let (tx2, _rx2) = sync_channel::<i32>(1);
+    tx2.send(0).unwrap();
+
+    let mut sel = Select::new();
+    let oper1 = sel.send(&tx1);
+    sel.send(&tx2);
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper1);
+    oper.send(&tx1, 1).unwrap();
+    assert_eq!(rx1.recv(), Ok(1));
+}
+
+#[test]
+fn select_blocks_until_ready() {
+    for cap in [None, Some(0), Some(1)] {
+        let (tx1, rx1) = match cap {
+            None => channel::<i32>(),
+            Some(cap) => sync_channel::<i32>(cap),
+        };
+        let (_tx2, rx2) = channel::<i32>();
+
+        let t = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            tx1.send(1).unwrap();
+        });
+
+        let mut sel = Select::new();
+        let oper1 = sel.recv(&rx1);
+        sel.recv(&rx2);
+        let oper = sel.select();
+        assert_eq!(oper.index(), oper1);
+        assert_eq!(oper.recv(&rx1), Ok(1));
+        t.join().unwrap();
+    }
+}
+
+#[test]
+fn select_zero_capacity_pairs() {
+    // Both sides select on a rendezvous channel, so they have to pair up with each other.
+    let (tx, rx) = sync_channel::<i32>(0);
+
+    let t = thread::spawn(move || {
+        let mut sel = Select::new();
+        sel.send(&tx);
+        let oper = sel.select();
+        oper.send(&tx, 1).unwrap();
+    });
+
+    let mut sel = Select::new();
+    sel.recv(&rx);
+    let oper = sel.select();
+    assert_eq!(oper.recv(&rx), Ok(1));
+    t.join().unwrap();
+}
+
+#[test]
+fn select_disconnected() {
+    let (tx, rx) = channel::<i32>();
+    drop(tx);
+
+    let mut sel = Select::new();
+    sel.recv(&rx);
+    let oper = sel.select();
+    assert_eq!(oper.recv(&rx), Err(RecvError));
+}
+
+#[test]
+fn try_select() {
+    let (tx, rx) = channel::<i32>();
+
+    let mut sel = Select::new();
+    sel.recv(&rx);
+    assert!(sel.try_select().is_err());
+
+    tx.send(1).unwrap();
+    let oper = sel.try_select().unwrap();
+    assert_eq!(oper.recv(&rx), Ok(1));
+}
+
+#[test]
+fn select_timeout() {
+    let (_tx, rx) = sync_channel::<i32>(0);
+
+    let mut sel = Select::new();
+    sel.recv(&rx);
+    let start = Instant::now();
+    assert_eq!(sel.select_timeout(Duration::from_millis(10)).unwrap_err(), SelectTimeoutError);
+    assert!(start.elapsed() >= Duration::from_millis(10));
+
+    let mut sel = Select::new();
+    assert_eq!(sel.select_timeout(Duration::from_millis(10)).unwrap_err(), SelectTimeoutError);
+}
+
+#[test]
+fn select_is_fair() {
+    let (tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = channel::<i32>();
+    for _ in 0..100 {
+        tx1.send(1).unwrap();
+        tx2.send(2).unwrap();
+    }
+
+    let mut sel = Select::new();
+    sel.recv(&rx1);
+    sel.recv(&rx2);
+    let mut seen = HashSet::new();
+    for _ in 0..100 {
+        let oper = sel.select();
+        let rx = if oper.index() == 0 { &rx1 } else { &rx2 };
+        seen.insert(oper.recv(rx).unwrap());
+    }
+    assert_eq!(seen.len(), 2);
+}
+
+#[test]
+#[should_panic(expected = "passed a receiver that wasn't selected")]
+fn selected_wrong_receiver() {
+    let (tx, rx) = channel::<i32>();
+    let rx2 = rx.clone();
+    tx.send(1).unwrap();
+
+    let mut sel = Select::new();
+    sel.recv(&rx);
+    let oper = sel.select();
+    let _ = oper.recv(&rx2);
+}
+
+#[test]
+fn select_on_another_thread() {
+    let (tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = channel::<i32>();
+
+    let mut sel = Select::new();
+    sel.recv(&rx1);
+    let oper2 = sel.recv(&rx2);
+    thread::scope(|s| {
+        let rx2 = &rx2;
+        let selecting = s.spawn(move || {
+            let oper = sel.select();
+            assert_eq!(oper.index(), oper2);
+            oper.recv(rx2)
+        });
+        tx2.send(2).unwrap();
+        assert_eq!(selecting.join().unwrap(), Ok(2));
+    });
+    drop(tx1);
+}
@@ -458,6 +458,76 @@ fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+pub struct SocketBuilder(!);
+
+impl SocketBuilder {
+    pub fn tcp(_: bool) -> io::Result<SocketBuilder> {
+        unsupported()
+    }
+
+    pub fn udp(_: bool) -> io::Result<SocketBuilder> {
+        unsupported()
+    }
+
+    pub fn set_reuse_address(&self, _: bool) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn set_reuse_port(&self, _: bool) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn set_only_v6(&self, _: bool) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn bind(&self, _: io::Result<&SocketAddr>) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn listen(&self, _: u32) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn connect(&self, _: io::Result<&SocketAddr>) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn socket_addr(&self) -> io::Result<SocketAddr> {
+        self.0
+    }
+
+    pub fn is_ipv6(&self) -> bool {
+        self.0
+    }
+
+    pub fn into_tcp_listener(self) -> TcpListener {
+        self.0
+    }
+
+    pub fn into_tcp_stream(self) -> TcpStream {
+        self.0
+    }
+
+    pub fn into_udp_socket(self) -> UdpSocket {
+        self.0
+    }
+}
+
+impl fmt::Debug for SocketBuilder {
+    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0
+    }
+}
+
 #[derive(Debug)]
 pub struct NonIpSockAddr {
     host: String,
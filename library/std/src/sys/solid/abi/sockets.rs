@@ -26,8 +26,10 @@
 pub const SOL_SOCKET: i32 = 4095;
 pub const SO_BROADCAST: i32 = 32;
 pub const SO_ERROR: i32 = 4103;
+pub const SO_RCVBUF: i32 = 4098;
 pub const SO_RCVTIMEO: i32 = 4102;
 pub const SO_REUSEADDR: i32 = 4;
+pub const SO_SNDBUF: i32 = 4097;
 pub const SO_SNDTIMEO: i32 = 4101;
 pub const SO_LINGER: i32 = 128;
 pub const TCP_NODELAY: i32 = 1;
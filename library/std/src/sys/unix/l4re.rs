@@ -521,6 +521,78 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         }
     }
 
+    pub struct SocketBuilder {
+        inner: Socket,
+    }
+
+    impl SocketBuilder {
+        pub fn tcp(_: bool) -> io::Result<SocketBuilder> {
+            unimpl!();
+        }
+
+        pub fn udp(_: bool) -> io::Result<SocketBuilder> {
+            unimpl!();
+        }
+
+        pub fn set_reuse_address(&self, _: bool) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn set_reuse_port(&self, _: bool) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn set_only_v6(&self, _: bool) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn bind(&self, _: io::Result<&SocketAddr>) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn listen(&self, _: u32) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn connect(&self, _: io::Result<&SocketAddr>) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn socket_addr(&self) -> io::Result<SocketAddr> {
+            unimpl!();
+        }
+
+        pub fn is_ipv6(&self) -> bool {
+            false
+        }
+
+        pub fn into_tcp_listener(self) -> TcpListener {
+            TcpListener { inner: self.inner }
+        }
+
+        pub fn into_tcp_stream(self) -> TcpStream {
+            TcpStream { inner: self.inner }
+        }
+
+        pub fn into_udp_socket(self) -> UdpSocket {
+            UdpSocket { inner: self.inner }
+        }
+    }
+
+    impl fmt::Debug for SocketBuilder {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "No networking support on L4Re available.")
+        }
+    }
+
     pub struct LookupHost {
         original: *mut libc::addrinfo,
         cur: *mut libc::addrinfo,
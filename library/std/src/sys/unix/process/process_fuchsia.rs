@@ -6,6 +6,7 @@
 
 use crate::sys::process::process_common::*;
 use crate::sys::process::zircon::{zx_handle_t, Handle};
+use crate::time::Duration;
 
 use libc::{c_int, size_t};
 
@@ -233,6 +234,36 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         }
         Ok(Some(ExitStatus(proc_info.return_code)))
     }
+
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        use crate::sys::process::zircon::*;
+
+        let timeout = zx_duration_t::try_from(timeout.as_nanos()).unwrap_or(zx_duration_t::MAX);
+        unsafe {
+            let status = zx_object_wait_one(
+                self.handle.raw(),
+                ZX_TASK_TERMINATED,
+                zx_deadline_after(timeout),
+                ptr::null_mut(),
+            );
+            if status == ERR_TIMED_OUT {
+                return Ok(None);
+            }
+            zx_cvt(status)?;
+        }
+        self.try_wait()
+    }
+
+    pub fn send_signal(&mut self, _signal: i32) -> io::Result<()> {
+        Err(io::const_io_error!(io::ErrorKind::Unsupported, "signals are not supported on Fuchsia"))
+    }
+
+    pub fn kill_process_group(&mut self, _signal: i32) -> io::Result<()> {
+        Err(io::const_io_error!(
+            io::ErrorKind::Unsupported,
+            "process groups are not supported on Fuchsia",
+        ))
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
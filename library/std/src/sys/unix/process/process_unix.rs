@@ -5,6 +5,8 @@
 use crate::sys;
 use crate::sys::cvt;
 use crate::sys::process::process_common::*;
+use crate::thread;
+use crate::time::{Duration, Instant};
 use core::ffi::NonZero_c_int;
 
 #[cfg(target_os = "linux")]
@@ -37,9 +39,7 @@
 
 cfg_if::cfg_if! {
     if #[cfg(all(target_os = "nto", target_env = "nto71"))] {
-        use crate::thread;
         use libc::{c_char, posix_spawn_file_actions_t, posix_spawnattr_t};
-        use crate::time::Duration;
         use crate::sync::LazyLock;
         // Get smallest amount of time we can sleep.
         // Return a common value if it cannot be determined.
@@ -148,6 +148,7 @@ pub fn spawn(
         // Safety: We obtained the pidfd from calling `clone3` with
         // `CLONE_PIDFD` so it's valid an otherwise unowned.
         let mut p = unsafe { Process::new(pid, pidfd) };
+        p.pgroup = self.get_pgroup();
         let mut bytes = [0; 8];
 
         // loop to handle EINTR
@@ -554,6 +555,7 @@ fn posix_spawn_file_actions_addchdir_np(
 
         // Safety: -1 indicates we don't have a pidfd.
         let mut p = unsafe { Process::new(0, -1) };
+        p.pgroup = pgroup;
 
         struct PosixSpawnFileActions<'a>(&'a mut MaybeUninit<libc::posix_spawn_file_actions_t>);
 
@@ -790,6 +792,9 @@ pub struct Process {
     // (e.g. the `clone3` syscall was not available).
     #[cfg(target_os = "linux")]
     pidfd: Option<PidFd>,
+    // The process group the child was put in with `CommandExt::process_group`,
+    // where 0 stands for a new group whose ID is the child's pid.
+    pgroup: Option<pid_t>,
 }
 
 impl Process {
@@ -799,12 +804,12 @@ unsafe fn new(pid: pid_t, pidfd: pid_t) -> Self {
         use crate::sys_common::FromInner;
         // Safety: If `pidfd` is nonnegative, we assume it's valid and otherwise unowned.
         let pidfd = (pidfd >= 0).then(|| PidFd::from_inner(sys::fd::FileDesc::from_raw_fd(pidfd)));
-        Process { pid, status: None, pidfd }
+        Process { pid, status: None, pidfd, pgroup: None }
     }
 
     #[cfg(not(target_os = "linux"))]
     unsafe fn new(pid: pid_t, _pidfd: pid_t) -> Self {
-        Process { pid, status: None }
+        Process { pid, status: None, pgroup: None }
     }
 
     pub fn id(&self) -> u32 {
@@ -846,6 +851,113 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        if let Some(status) = self.try_wait()? {
+            return Ok(Some(status));
+        }
+        // `None` means that the deadline is too far away to be represented, i.e. never.
+        let deadline = Instant::now().checked_add(timeout);
+
+        #[cfg(target_os = "linux")]
+        if let Some(status) = self.wait_pidfd(deadline)? {
+            return Ok(status);
+        }
+
+        // Without a pidfd there is nothing to wait on with a timeout, so poll
+        // the child, backing off up to a few milliseconds between attempts.
+        const MAX_POLL_INTERVAL: Duration = Duration::from_millis(10);
+        let mut interval = Duration::from_micros(100);
+        loop {
+            let remaining = match deadline {
+                Some(deadline) => deadline.saturating_duration_since(Instant::now()),
+                None => MAX_POLL_INTERVAL,
+            };
+            if remaining.is_zero() {
+                return Ok(None);
+            }
+            thread::sleep(interval.min(remaining));
+            if let Some(status) = self.try_wait()? {
+                return Ok(Some(status));
+            }
+            interval = (interval * 2).min(MAX_POLL_INTERVAL);
+        }
+    }
+
+    /// Waits until the child exits or `deadline` passes by polling a pidfd of the child.
+    ///
+    /// Returns `Ok(None)` if no pidfd could be opened, in which case the caller has
+    /// to fall back to polling the child itself.
+    #[cfg(target_os = "linux")]
+    fn wait_pidfd(&mut self, deadline: Option<Instant>) -> io::Result<Option<Option<ExitStatus>>> {
+        use crate::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+        // The child hasn't been reaped yet, so its pid can't have been reused
+        // and it is fine to open a pidfd for it if `Command` didn't create one.
+        let opened;
+        let pidfd = match &self.pidfd {
+            Some(pidfd) => pidfd.as_raw_fd(),
+            None => {
+                let fd = unsafe { libc::syscall(libc::SYS_pidfd_open, self.pid, 0) };
+                if fd < 0 {
+                    // The syscall is unavailable (before Linux 5.3) or forbidden,
+                    // e.g. by a seccomp filter.
+                    return Ok(None);
+                }
+                // Safety: `pidfd_open` returned a new file descriptor that we own.
+                opened = unsafe { OwnedFd::from_raw_fd(fd as c_int) };
+                opened.as_raw_fd()
+            }
+        };
+
+        loop {
+            let timeout_ms = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    // Round up, so that we don't wake up just before the deadline.
+                    let ms = remaining.as_nanos().div_ceil(1_000_000);
+                    ms.min(c_int::MAX as u128) as c_int
+                }
+                None => -1,
+            };
+            let mut pollfd = libc::pollfd { fd: pidfd, events: libc::POLLIN, revents: 0 };
+            match cvt(unsafe { libc::poll(&mut pollfd, 1, timeout_ms) }) {
+                // The pidfd becomes readable once the child has exited, and
+                // `poll` timing out means the deadline has passed.
+                Ok(_) => return self.try_wait().map(Some),
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    pub fn send_signal(&mut self, signal: i32) -> io::Result<()> {
+        // Like in `kill`, the pid might have been reused once the child was reaped.
+        if self.status.is_some() {
+            Ok(())
+        } else {
+            cvt(unsafe { libc::kill(self.pid, signal) }).map(drop)
+        }
+    }
+
+    pub fn kill_process_group(&mut self, signal: i32) -> io::Result<()> {
+        let pgroup = match self.pgroup {
+            Some(0) => self.pid,
+            Some(pgroup) => pgroup,
+            None => {
+                return Err(io::const_io_error!(
+                    ErrorKind::InvalidInput,
+                    "the child was not spawned with a process group",
+                ));
+            }
+        };
+        match cvt(unsafe { libc::killpg(pgroup, signal) }) {
+            Ok(_) => Ok(()),
+            // Every process of the group has exited, and the child was reaped.
+            Err(e) if e.raw_os_error() == Some(libc::ESRCH) && self.status.is_some() => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
 }
 
 /// Unix exit statuses
@@ -85,3 +85,49 @@ fn test_command_pidfd() {
         assert!(child.pidfd().is_ok())
     }
 }
+
+#[test]
+#[cfg_attr(target_os = "emscripten", ignore)]
+fn test_child_signal() {
+    use crate::os::unix::process::ChildExt;
+    use crate::process::Stdio;
+
+    let mut child =
+        Command::new("/bin/sh").arg("-c").arg("read line").stdin(Stdio::piped()).spawn().unwrap();
+    child.signal(libc::SIGTERM).unwrap();
+    let status = child.wait().unwrap();
+    assert_eq!(status.signal(), Some(libc::SIGTERM));
+    // Signalling a child that has been waited on does nothing.
+    child.signal(libc::SIGTERM).unwrap();
+}
+
+#[test]
+#[cfg_attr(target_os = "emscripten", ignore)]
+fn test_kill_process_group() {
+    use crate::io::{ErrorKind, Read};
+    use crate::os::unix::process::ChildExt;
+    use crate::process::Stdio;
+    use crate::time::Duration;
+
+    let mut child = Command::new("/bin/sh").arg("-c").arg("true").spawn().unwrap();
+    let err = child.kill_process_group(libc::SIGKILL).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    child.wait().unwrap();
+
+    // The backgrounded `sleep` inherits stdout, so stdout only reaches EOF once
+    // both the shell and the `sleep` are gone.
+    let mut child = Command::new("/bin/sh")
+        .arg("-c")
+        .arg("sleep 60 & wait")
+        .stdout(Stdio::piped())
+        .process_group(0)
+        .spawn()
+        .unwrap();
+    assert!(child.wait_timeout(Duration::from_millis(10)).unwrap().is_none());
+    child.kill_process_group(libc::SIGKILL).unwrap();
+    let mut out = Vec::new();
+    child.stdout.take().unwrap().read_to_end(&mut out).unwrap();
+    assert_eq!(child.wait().unwrap().signal(), Some(libc::SIGKILL));
+    // The group is gone, but that is fine once the child has been waited on.
+    child.kill_process_group(libc::SIGKILL).unwrap();
+}
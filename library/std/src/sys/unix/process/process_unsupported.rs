@@ -3,6 +3,7 @@
 use crate::num::NonZeroI32;
 use crate::sys::process::process_common::*;
 use crate::sys::unix::unsupported::*;
+use crate::time::Duration;
 use core::ffi::NonZero_c_int;
 
 use libc::{c_int, pid_t};
@@ -53,6 +54,18 @@ pub fn wait(&mut self) -> io::Result<ExitStatus> {
     pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         unsupported()
     }
+
+    pub fn wait_timeout(&mut self, _timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        unsupported()
+    }
+
+    pub fn send_signal(&mut self, _signal: i32) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn kill_process_group(&mut self, _signal: i32) -> io::Result<()> {
+        unsupported()
+    }
 }
 
 mod wait_status;
@@ -5,6 +5,7 @@
 use crate::sys::cvt;
 use crate::sys::process::process_common::*;
 use crate::sys_common::thread;
+use crate::time::{Duration, Instant};
 use core::ffi::NonZero_c_int;
 use libc::RTP_ID;
 use libc::{self, c_char, c_int};
@@ -176,6 +177,44 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        // There is nothing to wait on with a timeout, so poll the child,
+        // backing off up to a few milliseconds between attempts.
+        const MAX_POLL_INTERVAL: Duration = Duration::from_millis(10);
+        let deadline = Instant::now().checked_add(timeout);
+        let mut interval = Duration::from_micros(100);
+        loop {
+            if let Some(status) = self.try_wait()? {
+                return Ok(Some(status));
+            }
+            let remaining = match deadline {
+                Some(deadline) => deadline.saturating_duration_since(Instant::now()),
+                None => MAX_POLL_INTERVAL,
+            };
+            if remaining.is_zero() {
+                return Ok(None);
+            }
+            crate::thread::sleep(interval.min(remaining));
+            interval = (interval * 2).min(MAX_POLL_INTERVAL);
+        }
+    }
+
+    pub fn send_signal(&mut self, signal: i32) -> io::Result<()> {
+        // Like in `kill`, the pid might have been reused once the child was reaped.
+        if self.status.is_some() {
+            Ok(())
+        } else {
+            cvt(unsafe { libc::kill(self.pid, signal) }).map(drop)
+        }
+    }
+
+    pub fn kill_process_group(&mut self, _signal: i32) -> io::Result<()> {
+        Err(io::const_io_error!(
+            ErrorKind::Unsupported,
+            "process groups are not supported on VxWorks",
+        ))
+    }
 }
 
 /// Unix exit statuses
@@ -28,6 +28,7 @@
 use crate::sys::stdio;
 use crate::sys_common::process::{CommandEnv, CommandEnvs};
 use crate::sys_common::IntoInner;
+use crate::time::Duration;
 
 use core::ffi::c_void;
 
@@ -691,6 +692,15 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         }
     }
 
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        let timeout = crate::sys::dur2timeout(timeout);
+        match unsafe { c::WaitForSingleObject(self.handle.as_raw_handle(), timeout) } {
+            c::WAIT_OBJECT_0 => self.try_wait(),
+            c::WAIT_TIMEOUT => Ok(None),
+            _ => Err(io::Error::last_os_error()),
+        }
+    }
+
     pub fn handle(&self) -> &Handle {
         &self.handle
     }
@@ -716,6 +716,141 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// Socket builders
+////////////////////////////////////////////////////////////////////////////////
+
+/// A TCP or UDP socket which has been created but not yet connected or
+/// listening, so that options can be set before it is bound.
+pub struct SocketBuilder {
+    inner: Socket,
+    ipv6: bool,
+}
+
+impl SocketBuilder {
+    pub fn tcp(ipv6: bool) -> io::Result<SocketBuilder> {
+        SocketBuilder::new(ipv6, c::SOCK_STREAM)
+    }
+
+    pub fn udp(ipv6: bool) -> io::Result<SocketBuilder> {
+        SocketBuilder::new(ipv6, c::SOCK_DGRAM)
+    }
+
+    fn new(ipv6: bool, ty: c_int) -> io::Result<SocketBuilder> {
+        init();
+
+        // Only the address family is used to create the socket.
+        let addr = if ipv6 {
+            SocketAddr::new(Ipv6Addr::UNSPECIFIED.into(), 0)
+        } else {
+            SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0)
+        };
+        Ok(SocketBuilder { inner: Socket::new(&addr, ty)?, ipv6 })
+    }
+
+    pub fn set_reuse_address(&self, reuse: bool) -> io::Result<()> {
+        setsockopt(&self.inner, c::SOL_SOCKET, c::SO_REUSEADDR, reuse as c_int)
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "netbsd",
+        target_os = "ios",
+        target_os = "tvos",
+        target_os = "macos",
+        target_os = "watchos"
+    ))]
+    pub fn set_reuse_port(&self, reuse: bool) -> io::Result<()> {
+        setsockopt(&self.inner, c::SOL_SOCKET, c::SO_REUSEPORT, reuse as c_int)
+    }
+
+    #[cfg(not(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "netbsd",
+        target_os = "ios",
+        target_os = "tvos",
+        target_os = "macos",
+        target_os = "watchos"
+    )))]
+    pub fn set_reuse_port(&self, _: bool) -> io::Result<()> {
+        Err(io::const_io_error!(
+            ErrorKind::Unsupported,
+            "SO_REUSEPORT is not supported on this platform",
+        ))
+    }
+
+    pub fn set_only_v6(&self, only_v6: bool) -> io::Result<()> {
+        setsockopt(&self.inner, c::IPPROTO_IPV6, c::IPV6_V6ONLY, only_v6 as c_int)
+    }
+
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        let size = cmp::min(size, c_int::MAX as usize) as c_int;
+        setsockopt(&self.inner, c::SOL_SOCKET, c::SO_SNDBUF, size)
+    }
+
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        let size = cmp::min(size, c_int::MAX as usize) as c_int;
+        setsockopt(&self.inner, c::SOL_SOCKET, c::SO_RCVBUF, size)
+    }
+
+    pub fn bind(&self, addr: io::Result<&SocketAddr>) -> io::Result<()> {
+        let (addr, len) = addr?.into_inner();
+        cvt(unsafe { c::bind(self.inner.as_raw(), addr.as_ptr(), len as _) })?;
+        Ok(())
+    }
+
+    pub fn listen(&self, backlog: u32) -> io::Result<()> {
+        let backlog = cmp::min(backlog, c_int::MAX as u32) as c_int;
+        cvt(unsafe { c::listen(self.inner.as_raw(), backlog) })?;
+        Ok(())
+    }
+
+    pub fn connect(&self, addr: io::Result<&SocketAddr>) -> io::Result<()> {
+        self.inner.connect(addr?)
+    }
+
+    pub fn socket_addr(&self) -> io::Result<SocketAddr> {
+        sockname(|buf, len| unsafe { c::getsockname(self.inner.as_raw(), buf, len) })
+    }
+
+    pub fn is_ipv6(&self) -> bool {
+        self.ipv6
+    }
+
+    pub fn into_tcp_listener(self) -> TcpListener {
+        TcpListener { inner: self.inner }
+    }
+
+    pub fn into_tcp_stream(self) -> TcpStream {
+        TcpStream { inner: self.inner }
+    }
+
+    pub fn into_udp_socket(self) -> UdpSocket {
+        UdpSocket { inner: self.inner }
+    }
+}
+
+impl fmt::Debug for SocketBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut res = f.debug_struct("SocketBuilder");
+
+        if let Ok(addr) = self.socket_addr() {
+            res.field("addr", &addr);
+        }
+
+        let name = if cfg!(windows) { "socket" } else { "fd" };
+        res.field(name, &self.inner.as_raw()).finish()
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Converting SocketAddr to libc representation
 ////////////////////////////////////////////////////////////////////////////////
@@ -2,9 +2,10 @@
 
 use std::env;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use super::options::{ColorConfig, Options, OutputFormat, RunIgnored};
-use super::time::TestTimeOptions;
+use super::time::{self, TestTimeOptions};
 use std::io::{self, IsTerminal};
 
 #[derive(Debug)]
@@ -25,6 +26,20 @@ pub struct TestOpts {
     pub shuffle_seed: Option<u64>,
     pub test_threads: Option<usize>,
     pub skip: Vec<String>,
+    /// Only run the tests of one shard, given as `(index, count)`, out of the tests split into
+    /// `count` shards by the hash of their names.
+    pub shard: Option<(usize, usize)>,
+    /// Number of times a failed test is run again before it's reported as failed.
+    pub retries: usize,
+    /// Hard timeout of the tests without a `#[timeout]` attribute.
+    pub test_timeout: Option<Duration>,
+    /// Name of the baseline to save the results of the benchmarks as.
+    pub save_baseline: Option<String>,
+    /// Name of the baseline to compare the results of the benchmarks with.
+    pub baseline: Option<String>,
+    /// Smallest change of the median of a benchmark, in percent, that is reported as a
+    /// regression or an improvement over the baseline.
+    pub baseline_threshold: f64,
     pub time_options: Option<TestTimeOptions>,
     /// Stop at first failing test.
     /// May run a few more tests due to threading, but will
@@ -102,8 +117,10 @@ fn optgroups() -> getopts::Options {
             pretty = Print verbose output;
             terse  = Display one character per test;
             json   = Output a json document;
-            junit  = Output a JUnit document",
-            "pretty|terse|json|junit",
+            junit  = Output a JUnit document;
+            tap    = Output a TAP 14 stream;
+            github = Print verbose output with GitHub Actions annotations",
+            "pretty|terse|json|junit|tap|github",
         )
         .optflag("", "show-output", "Show captured stdout of successful tests")
         .optopt(
@@ -148,6 +165,54 @@ fn optgroups() -> getopts::Options {
             "shuffle-seed",
             "Run tests in random order; seed the random number generator with SEED",
             "SEED",
+        )
+        .optopt(
+            "",
+            "shard-index",
+            "Only run the tests of the shard INDEX, counting from 0, out of the shards \
+             given by --shard-count",
+            "INDEX",
+        )
+        .optopt(
+            "",
+            "shard-count",
+            "Split the tests into COUNT shards by the hash of their names, which only \
+             depends on the names of the tests",
+            "COUNT",
+        )
+        .optopt(
+            "",
+            "retries",
+            "Run failed tests again up to N times, and report the tests that eventually \
+             pass as flaky",
+            "N",
+        )
+        .optopt(
+            "",
+            "test-timeout",
+            "Kill the tests that run for longer than DURATION, such as 30s or 500ms, \
+             unless they have a #[timeout] attribute of their own",
+            "DURATION",
+        )
+        .optopt(
+            "",
+            "save-baseline",
+            "Save the results of the benchmarks as the baseline NAME",
+            "NAME",
+        )
+        .optopt(
+            "",
+            "baseline",
+            "Compare the results of the benchmarks with the baseline NAME, and fail the \
+             benchmarks that regressed",
+            "NAME",
+        )
+        .optopt(
+            "",
+            "baseline-threshold",
+            "Only report changes of the median of at least PERCENT over the baseline \
+             (default: 2)",
+            "PERCENT",
         );
     opts
 }
@@ -171,6 +236,22 @@ fn usage(binary: &str, options: &getopts::Options) {
 tests in the same order again. Note that --shuffle and --shuffle-seed do not
 affect whether the tests are run in parallel.
 
+The tests can be split across several runs of the test binary with
+--shard-index and --shard-count. Each test belongs to exactly one shard,
+determined by the hash of its name, so the runs of all the shards together
+run every test once.
+
+A test that runs for longer than its #[timeout] attribute or the
+--test-timeout option is killed and reported as failed. Only tests built with
+-C panic=abort run in a process of their own that can be killed. Otherwise, the
+test run stops as soon as a test times out.
+
+The results of the benchmarks can be saved with --save-baseline and compared
+with a later run with --baseline. The baselines are saved in the
+bench-baselines directory next to the test binary. A benchmark whose median
+is significantly slower than in the baseline, as determined by a Mann-Whitney U
+test over the samples, fails.
+
 All tests have their standard output and standard error captured by default.
 This can be overridden with the --nocapture flag or setting RUST_TEST_NOCAPTURE
 environment variable to a value other than "0". Logging is not captured by default.
@@ -188,7 +269,9 @@ fn usage(binary: &str, options: &getopts::Options) {
     `#[ignore]`       - When applied to a function which is already attributed as a
                         test, then the test runner will ignore these tests during
                         normal test runs. Running with --ignored or --include-ignored will run
-                        these tests."#,
+                        these tests.
+    `#[timeout = "30s"]` - Kills the test and reports it as failed if it runs for
+                        longer than the given duration (in `ms`, `s`, `m` or `h`)."#,
         usage = options.usage(&message)
     );
 }
@@ -261,6 +344,12 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
     let time_options = get_time_options(&matches, allow_unstable)?;
     let shuffle = get_shuffle(&matches, allow_unstable)?;
     let shuffle_seed = get_shuffle_seed(&matches, allow_unstable)?;
+    let shard = get_shard(&matches, allow_unstable)?;
+    let retries = get_retries(&matches, allow_unstable)?;
+    let test_timeout = get_test_timeout(&matches, allow_unstable)?;
+    let save_baseline = get_baseline_name(&matches, allow_unstable, "save-baseline")?;
+    let baseline = get_baseline_name(&matches, allow_unstable, "baseline")?;
+    let baseline_threshold = get_baseline_threshold(&matches, allow_unstable)?;
 
     let include_ignored = matches.opt_present("include-ignored");
     let quiet = matches.opt_present("quiet");
@@ -298,6 +387,12 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
         shuffle_seed,
         test_threads,
         skip,
+        shard,
+        retries,
+        test_timeout,
+        save_baseline,
+        baseline,
+        baseline_threshold,
         time_options,
         options,
         fail_fast: false,
@@ -375,6 +470,119 @@ fn get_shuffle_seed(matches: &getopts::Matches, allow_unstable: bool) -> OptPart
     Ok(shuffle_seed)
 }
 
+fn get_shard(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+) -> OptPartRes<Option<(usize, usize)>> {
+    let index = unstable_optopt!(matches, allow_unstable, "shard-index");
+    let count = unstable_optopt!(matches, allow_unstable, "shard-count");
+    let (index, count) = match (index, count) {
+        (Some(index), Some(count)) => (index, count),
+        (None, None) => return Ok(None),
+        _ => return Err("the options --shard-index and --shard-count must be used together".into()),
+    };
+
+    let count = match count.parse::<usize>() {
+        Ok(0) => return Err("argument for --shard-count must not be 0".to_string()),
+        Ok(n) => n,
+        Err(e) => {
+            return Err(format!(
+                "argument for --shard-count must be a number > 0 \
+                 (error: {e})"
+            ));
+        }
+    };
+    let index = match index.parse::<usize>() {
+        Ok(n) if n < count => n,
+        Ok(n) => {
+            return Err(format!(
+                "argument for --shard-index must be less than the shard count {count} (was {n})"
+            ));
+        }
+        Err(e) => {
+            return Err(format!(
+                "argument for --shard-index must be a number \
+                 (error: {e})"
+            ));
+        }
+    };
+
+    Ok(Some((index, count)))
+}
+
+fn get_retries(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<usize> {
+    let retries = match unstable_optopt!(matches, allow_unstable, "retries") {
+        Some(n_str) => match n_str.parse::<usize>() {
+            Ok(n) => n,
+            Err(e) => {
+                return Err(format!(
+                    "argument for --retries must be a number \
+                     (error: {e})"
+                ));
+            }
+        },
+        None => 0,
+    };
+
+    Ok(retries)
+}
+
+fn get_test_timeout(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+) -> OptPartRes<Option<Duration>> {
+    let test_timeout = match unstable_optopt!(matches, allow_unstable, "test-timeout") {
+        Some(s) => match time::parse_test_timeout(&s) {
+            Some(timeout) => Some(timeout),
+            None => {
+                return Err(format!(
+                    "argument for --test-timeout must be a positive number followed by \
+                     `ms`, `s`, `m` or `h`, such as `30s` (was {s})"
+                ));
+            }
+        },
+        None => None,
+    };
+
+    Ok(test_timeout)
+}
+
+fn get_baseline_name(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+    option_name: &str,
+) -> OptPartRes<Option<String>> {
+    let name = matches.opt_str(option_name);
+    if !allow_unstable && name.is_some() {
+        return Err(format!(
+            "The \"{option_name}\" option is only accepted on the nightly compiler with -Z unstable-options"
+        ));
+    }
+
+    match name {
+        Some(name) if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') => {
+            Err(format!("argument for --{option_name} must be a file name (was {name})"))
+        }
+        name => Ok(name),
+    }
+}
+
+fn get_baseline_threshold(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<f64> {
+    let threshold = match unstable_optopt!(matches, allow_unstable, "baseline-threshold") {
+        Some(s) => match s.parse::<f64>() {
+            Ok(n) if n >= 0.0 => n,
+            _ => {
+                return Err(format!(
+                    "argument for --baseline-threshold must be a non-negative number (was {s})"
+                ));
+            }
+        },
+        None => 2.0,
+    };
+
+    Ok(threshold)
+}
+
 fn get_test_threads(matches: &getopts::Matches) -> OptPartRes<Option<usize>> {
     let test_threads = match matches.opt_str("test-threads") {
         Some(n_str) => match n_str.parse::<usize>() {
@@ -414,9 +622,21 @@ fn get_format(
             }
             OutputFormat::Junit
         }
+        Some("tap") => {
+            if !allow_unstable {
+                return Err("The \"tap\" format is only accepted on the nightly compiler with -Z unstable-options".into());
+            }
+            OutputFormat::Tap
+        }
+        Some("github") => {
+            if !allow_unstable {
+                return Err("The \"github\" format is only accepted on the nightly compiler with -Z unstable-options".into());
+            }
+            OutputFormat::Github
+        }
         Some(v) => {
             return Err(format!(
-                "argument for --format must be pretty, terse, json or junit (was \
+                "argument for --format must be pretty, terse, json, junit, tap or github (was \
                  {v})"
             ));
         }
@@ -6,12 +6,15 @@
 use std::time::Instant;
 
 use super::{
-    bench::fmt_bench_samples,
+    bench::{fmt_bench_samples, BaselineVerdict, BenchSamples},
     cli::TestOpts,
     event::{CompletedTest, TestEvent},
     filter_tests,
-    formatters::{JsonFormatter, JunitFormatter, OutputFormatter, PrettyFormatter, TerseFormatter},
-    helpers::{concurrency::get_concurrency, metrics::MetricMap},
+    formatters::{
+        GithubFormatter, JsonFormatter, JunitFormatter, OutputFormatter, PrettyFormatter,
+        TapFormatter, TerseFormatter,
+    },
+    helpers::{baseline::Baseline, concurrency::get_concurrency, metrics::MetricMap},
     options::{Options, OutputFormat},
     run_tests, term,
     test_result::TestResult,
@@ -86,6 +89,7 @@ pub struct ConsoleTestState {
     pub total: usize,
     pub passed: usize,
     pub failed: usize,
+    pub flaky: usize,
     pub ignored: usize,
     pub filtered_out: usize,
     pub measured: usize,
@@ -95,6 +99,14 @@ pub struct ConsoleTestState {
     pub not_failures: Vec<(TestDesc, Vec<u8>)>,
     pub ignores: Vec<(TestDesc, Vec<u8>)>,
     pub time_failures: Vec<(TestDesc, Vec<u8>)>,
+    pub flakes: Vec<(TestDesc, Vec<u8>)>,
+    /// The baseline that the benchmarks are compared with.
+    pub baseline: Option<Baseline>,
+    pub baseline_threshold: f64,
+    /// The results of the benchmarks, to save as a baseline.
+    pub benches: Vec<(String, BenchSamples)>,
+    /// Whether the test run stopped before running all the tests.
+    pub aborted: bool,
     pub options: Options,
 }
 
@@ -104,12 +116,17 @@ pub fn new(opts: &TestOpts) -> io::Result<ConsoleTestState> {
             Some(ref path) => Some(File::create(path)?),
             None => None,
         };
+        let baseline = match opts.baseline {
+            Some(ref name) if opts.bench_benchmarks => Some(Baseline::load(name)?),
+            _ => None,
+        };
 
         Ok(ConsoleTestState {
             log_out,
             total: 0,
             passed: 0,
             failed: 0,
+            flaky: 0,
             ignored: 0,
             filtered_out: 0,
             measured: 0,
@@ -119,6 +136,11 @@ pub fn new(opts: &TestOpts) -> io::Result<ConsoleTestState> {
             not_failures: Vec::new(),
             ignores: Vec::new(),
             time_failures: Vec::new(),
+            flakes: Vec::new(),
+            baseline,
+            baseline_threshold: opts.baseline_threshold,
+            benches: Vec::new(),
+            aborted: false,
             options: opts.options,
         })
     }
@@ -161,6 +183,7 @@ pub fn write_log_result(
                     }
                     TestResult::TrBench(ref bs) => fmt_bench_samples(bs),
                     TestResult::TrTimedFail => "failed (time limit exceeded)".to_owned(),
+                    TestResult::TrFlaky(n) => format!("flaky ({n} failed attempts)"),
                 },
                 name,
             )
@@ -172,7 +195,7 @@ pub fn write_log_result(
     }
 
     fn current_test_count(&self) -> usize {
-        self.passed + self.failed + self.ignored + self.measured
+        self.passed + self.failed + self.flaky + self.ignored + self.measured
     }
 }
 
@@ -184,7 +207,7 @@ pub fn list_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Res
     };
 
     let mut out: Box<dyn OutputFormatter> = match opts.format {
-        OutputFormat::Pretty | OutputFormat::Junit => {
+        OutputFormat::Pretty | OutputFormat::Junit | OutputFormat::Tap | OutputFormat::Github => {
             Box::new(PrettyFormatter::new(output, false, 0, false, None))
         }
         OutputFormat::Terse => Box::new(TerseFormatter::new(output, false, 0, false)),
@@ -237,7 +260,28 @@ fn handle_test_result(st: &mut ConsoleTestState, completed_test: CompletedTest)
                 bs.ns_iter_summ.median,
                 bs.ns_iter_summ.max - bs.ns_iter_summ.min,
             );
-            st.measured += 1
+            match bs.baseline {
+                Some(ref cmp) if cmp.verdict == BaselineVerdict::Regressed => {
+                    st.failed += 1;
+                    let mut stdout = stdout;
+                    if !stdout.is_empty() && !stdout.ends_with(b"\n") {
+                        stdout.push(b'\n');
+                    }
+                    stdout.extend_from_slice(
+                        format!(
+                            "note: regressed by {:.2}% over the baseline `{}` (p = {:.3})\n",
+                            cmp.change_pct, cmp.name, cmp.p_value
+                        )
+                        .as_bytes(),
+                    );
+                    st.benches.push((test.name.as_slice().to_string(), bs));
+                    st.failures.push((test, stdout));
+                }
+                _ => {
+                    st.measured += 1;
+                    st.benches.push((test.name.as_slice().to_string(), bs));
+                }
+            }
         }
         TestResult::TrFailed => {
             st.failed += 1;
@@ -253,6 +297,10 @@ fn handle_test_result(st: &mut ConsoleTestState, completed_test: CompletedTest)
             st.failed += 1;
             st.time_failures.push((test, stdout));
         }
+        TestResult::TrFlaky(_) => {
+            st.flaky += 1;
+            st.flakes.push((test, stdout));
+        }
     }
 }
 
@@ -271,9 +319,19 @@ fn on_test_event(
         TestEvent::TeFilteredOut(filtered_out) => {
             st.filtered_out = filtered_out;
         }
+        TestEvent::TeAborted => {
+            st.aborted = true;
+        }
         TestEvent::TeWait(ref test) => out.write_test_start(test)?,
         TestEvent::TeTimeout(ref test) => out.write_timeout(test)?,
-        TestEvent::TeResult(completed_test) => {
+        TestEvent::TeResult(mut completed_test) => {
+            if let (TestResult::TrBench(bs), Some(baseline)) =
+                (&mut completed_test.result, &st.baseline)
+            {
+                let name = completed_test.desc.name.as_slice();
+                bs.baseline = baseline.compare(name, bs, st.baseline_threshold);
+            }
+
             let test = &completed_test.desc;
             let result = &completed_test.result;
             let exec_time = &completed_test.exec_time;
@@ -317,6 +375,14 @@ pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Resu
         }
         OutputFormat::Json => Box::new(JsonFormatter::new(output)),
         OutputFormat::Junit => Box::new(JunitFormatter::new(output)),
+        OutputFormat::Tap => Box::new(TapFormatter::new(output)),
+        OutputFormat::Github => Box::new(GithubFormatter::new(PrettyFormatter::new(
+            output,
+            opts.use_color(),
+            max_name_len,
+            is_multithreaded,
+            opts.time_options,
+        ))),
     };
     let mut st = ConsoleTestState::new(opts)?;
 
@@ -329,7 +395,11 @@ pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Resu
     run_tests(opts, tests, |x| on_test_event(&x, &mut st, &mut *out))?;
     st.exec_time = start_time.map(|t| TestSuiteExecTime(t.elapsed()));
 
-    assert!(opts.fail_fast || st.current_test_count() == st.total);
+    assert!(opts.fail_fast || st.aborted || st.current_test_count() == st.total);
+
+    if let Some(ref name) = opts.save_baseline {
+        Baseline::save(name, &st.benches)?;
+    }
 
     out.write_run_finish(&st)
 }
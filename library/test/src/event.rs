@@ -33,4 +33,6 @@ pub enum TestEvent {
     TeResult(CompletedTest),
     TeTimeout(TestDesc),
     TeFilteredOut(usize),
+    /// The test run stopped early, because a test that can't be killed timed out.
+    TeAborted,
 }
@@ -0,0 +1,109 @@
+use std::{io, io::prelude::Write};
+
+use super::{FailureLocation, OutputFormatter, PrettyFormatter};
+use crate::{
+    console::{ConsoleTestDiscoveryState, ConsoleTestState},
+    test_result::TestResult,
+    time,
+    types::TestDesc,
+};
+
+/// Writes the same output as the pretty formatter, along with the [workflow commands] that make
+/// GitHub Actions annotate the source lines where tests failed.
+///
+/// [workflow commands]: https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions
+pub(crate) struct GithubFormatter<T> {
+    pretty: PrettyFormatter<T>,
+}
+
+impl<T: Write> GithubFormatter<T> {
+    pub fn new(pretty: PrettyFormatter<T>) -> Self {
+        Self { pretty }
+    }
+
+    fn write_annotation(
+        &mut self,
+        command: &str,
+        desc: &TestDesc,
+        message: Option<&str>,
+        stdout: &[u8],
+    ) -> io::Result<()> {
+        let output = String::from_utf8_lossy(stdout);
+        let location = FailureLocation::find(desc, &output);
+
+        let mut properties = String::new();
+        if let Some(FailureLocation { file, line, col, .. }) = location {
+            properties.push_str(&format!("file={},line={line},col={col},", escape_property(file)));
+        }
+        properties.push_str(&format!("title={}", escape_property(desc.name.as_slice())));
+
+        let message = message
+            .or(location.as_ref().and_then(|l| l.message.as_deref()))
+            .unwrap_or("test failed");
+        self.pretty.write_plain(format!("::{command} {properties}::{}\n", escape_data(message)))
+    }
+}
+
+/// Escapes the message of a workflow command.
+fn escape_data(s: &str) -> String {
+    s.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// Escapes the value of a property of a workflow command.
+fn escape_property(s: &str) -> String {
+    escape_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+impl<T: Write> OutputFormatter for GithubFormatter<T> {
+    fn write_discovery_start(&mut self) -> io::Result<()> {
+        self.pretty.write_discovery_start()
+    }
+
+    fn write_test_discovered(&mut self, desc: &TestDesc, test_type: &str) -> io::Result<()> {
+        self.pretty.write_test_discovered(desc, test_type)
+    }
+
+    fn write_discovery_finish(&mut self, state: &ConsoleTestDiscoveryState) -> io::Result<()> {
+        self.pretty.write_discovery_finish(state)
+    }
+
+    fn write_run_start(&mut self, test_count: usize, shuffle_seed: Option<u64>) -> io::Result<()> {
+        self.pretty.write_run_start(test_count, shuffle_seed)
+    }
+
+    fn write_test_start(&mut self, desc: &TestDesc) -> io::Result<()> {
+        self.pretty.write_test_start(desc)
+    }
+
+    fn write_timeout(&mut self, desc: &TestDesc) -> io::Result<()> {
+        self.pretty.write_timeout(desc)
+    }
+
+    fn write_result(
+        &mut self,
+        desc: &TestDesc,
+        result: &TestResult,
+        exec_time: Option<&time::TestExecTime>,
+        stdout: &[u8],
+        state: &ConsoleTestState,
+    ) -> io::Result<()> {
+        self.pretty.write_result(desc, result, exec_time, stdout, state)?;
+
+        match *result {
+            TestResult::TrFailed => self.write_annotation("error", desc, None, stdout),
+            TestResult::TrFailedMsg(ref m) => self.write_annotation("error", desc, Some(m), stdout),
+            TestResult::TrTimedFail => {
+                self.write_annotation("error", desc, Some("time limit exceeded"), stdout)
+            }
+            TestResult::TrFlaky(failed_attempts) => {
+                let message = format!("test passed after {failed_attempts} failed attempts");
+                self.write_annotation("warning", desc, Some(&message), stdout)
+            }
+            TestResult::TrOk | TestResult::TrIgnored | TestResult::TrBench(_) => Ok(()),
+        }
+    }
+
+    fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
+        self.pretty.write_run_finish(state)
+    }
+}
@@ -7,14 +7,18 @@
     types::{TestDesc, TestName},
 };
 
+mod github;
 mod json;
 mod junit;
 mod pretty;
+mod tap;
 mod terse;
 
-pub(crate) use self::json::JsonFormatter;
+pub(crate) use self::github::GithubFormatter;
+pub(crate) use self::json::{EscapedString, JsonFormatter};
 pub(crate) use self::junit::JunitFormatter;
 pub(crate) use self::pretty::PrettyFormatter;
+pub(crate) use self::tap::TapFormatter;
 pub(crate) use self::terse::TerseFormatter;
 
 pub(crate) trait OutputFormatter {
@@ -44,3 +48,61 @@ pub(crate) fn write_stderr_delimiter(test_output: &mut Vec<u8>, test_name: &Test
     }
     writeln!(test_output, "---- {test_name} stderr ----").unwrap();
 }
+
+/// Where a test failed, as found in its output.
+#[derive(Debug, PartialEq)]
+pub(crate) struct FailureLocation<'a> {
+    pub file: &'a str,
+    pub line: usize,
+    pub col: usize,
+    /// The panic message, if the location is the one of a panic.
+    pub message: Option<String>,
+}
+
+impl<'a> FailureLocation<'a> {
+    /// Returns the location of the first panic in the captured output of a failed test, or the
+    /// location of the test itself if it didn't panic, e.g. because it returned an error.
+    pub(crate) fn find(desc: &'a TestDesc, output: &'a str) -> Option<Self> {
+        find_panic(output).or_else(|| {
+            (!desc.source_file.is_empty()).then(|| FailureLocation {
+                file: desc.source_file,
+                line: desc.start_line,
+                col: desc.start_col,
+                message: None,
+            })
+        })
+    }
+}
+
+/// Parses the first message of the default panic hook, which looks like:
+///
+/// ```text
+/// thread 'tests::it_works' panicked at src/lib.rs:10:9:
+/// assertion `left == right` failed
+///   left: 4
+///  right: 5
+/// note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+/// ```
+fn find_panic(output: &str) -> Option<FailureLocation<'_>> {
+    let mut lines = output.lines();
+    let location = lines.find_map(|line| {
+        let (_, location) = line.strip_prefix("thread '")?.split_once(" panicked at ")?;
+        let mut parts = location.strip_suffix(':')?.rsplitn(3, ':');
+        let col = parts.next()?.parse().ok()?;
+        let line = parts.next()?.parse().ok()?;
+        Some((parts.next()?, line, col))
+    })?;
+
+    // The message ends where the output of the panic hook or another test's output begins.
+    let message = lines
+        .take_while(|line| {
+            !line.starts_with("note: ")
+                && !line.starts_with("stack backtrace:")
+                && !line.starts_with("thread '")
+                && !line.starts_with("---- ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let (file, line, col) = location;
+    Some(FailureLocation { file, line, col, message: Some(message) })
+}
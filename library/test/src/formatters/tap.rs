@@ -0,0 +1,187 @@
+use std::{io, io::prelude::Write};
+
+use super::{EscapedString, FailureLocation, OutputFormatter};
+use crate::{
+    bench::fmt_bench_samples,
+    console::{ConsoleTestDiscoveryState, ConsoleTestState, OutputLocation},
+    test_result::TestResult,
+    time,
+    types::TestDesc,
+};
+
+/// Writes the results as a [TAP 14](https://testanything.org/tap-version-14-specification.html)
+/// stream, with the details of failures in YAML diagnostics blocks.
+pub(crate) struct TapFormatter<T> {
+    out: OutputLocation<T>,
+    /// Number of the last test point written
+    test_number: usize,
+}
+
+/// Replaces the line breaks in `s` with spaces, since they would end a TAP line early.
+fn single_line(s: &str) -> String {
+    s.replace(['\n', '\r'], " ")
+}
+
+impl<T: Write> TapFormatter<T> {
+    pub fn new(out: OutputLocation<T>) -> Self {
+        Self { out, test_number: 0 }
+    }
+
+    #[cfg(test)]
+    pub fn output_location(&self) -> &OutputLocation<T> {
+        &self.out
+    }
+
+    fn write_line(&mut self, s: &str) -> io::Result<()> {
+        assert!(!s.contains('\n'));
+
+        self.out.write_all(s.as_bytes())?;
+        self.out.write_all(b"\n")
+    }
+
+    fn write_test_point(&mut self, ok: bool, desc: &TestDesc, directive: &str) -> io::Result<()> {
+        self.test_number += 1;
+        let status = if ok { "ok" } else { "not ok" };
+        // A `#` starts a directive, so it has to be escaped in the description.
+        let name = single_line(desc.name.as_slice()).replace('\\', "\\\\").replace('#', "\\#");
+        self.write_line(&format!("{status} {} - {name}{directive}", self.test_number))
+    }
+
+    /// Writes a YAML block of `key: value` lines, where the values are already YAML.
+    fn write_yaml_block(&mut self, entries: &[(&str, String)]) -> io::Result<()> {
+        self.write_line("  ---")?;
+        for (key, value) in entries {
+            self.write_line(&format!("  {key}: {value}"))?;
+        }
+        self.write_line("  ...")
+    }
+
+    fn write_failure(
+        &mut self,
+        desc: &TestDesc,
+        message: Option<&str>,
+        stdout: &[u8],
+    ) -> io::Result<()> {
+        self.write_test_point(false, desc, "")?;
+
+        let output = String::from_utf8_lossy(stdout);
+        let location = FailureLocation::find(desc, &output);
+        let message = message.or(location.as_ref().and_then(|l| l.message.as_deref()));
+
+        // JSON strings are valid YAML, and can hold any output, unlike YAML block scalars.
+        let mut entries = Vec::new();
+        if let Some(message) = message {
+            entries.push(("message", format!("\"{}\"", EscapedString(message))));
+        }
+        entries.push(("severity", String::from("fail")));
+        if let Some(FailureLocation { file, line, col, .. }) = location {
+            let at =
+                format!("{{ file: \"{}\", line: {line}, column: {col} }}", EscapedString(file));
+            entries.push(("at", at));
+        }
+        if !output.is_empty() {
+            entries.push(("output", format!("\"{}\"", EscapedString(&output))));
+        }
+        self.write_yaml_block(&entries)
+    }
+}
+
+impl<T: Write> OutputFormatter for TapFormatter<T> {
+    fn write_discovery_start(&mut self) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::NotFound, "Not yet implemented!"))
+    }
+
+    fn write_test_discovered(&mut self, _desc: &TestDesc, _test_type: &str) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::NotFound, "Not yet implemented!"))
+    }
+
+    fn write_discovery_finish(&mut self, _state: &ConsoleTestDiscoveryState) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::NotFound, "Not yet implemented!"))
+    }
+
+    fn write_run_start(&mut self, test_count: usize, shuffle_seed: Option<u64>) -> io::Result<()> {
+        self.write_line("TAP version 14")?;
+        if let Some(shuffle_seed) = shuffle_seed {
+            self.write_line(&format!("# shuffle seed: {shuffle_seed}"))?;
+        }
+        self.write_line(&format!("1..{test_count}"))
+    }
+
+    fn write_test_start(&mut self, _desc: &TestDesc) -> io::Result<()> {
+        // We do not output anything on test start.
+        Ok(())
+    }
+
+    fn write_timeout(&mut self, desc: &TestDesc) -> io::Result<()> {
+        self.write_line(&format!(
+            "# test {} has been running for over {} seconds",
+            single_line(desc.name.as_slice()),
+            time::TEST_WARN_TIMEOUT_S
+        ))
+    }
+
+    fn write_result(
+        &mut self,
+        desc: &TestDesc,
+        result: &TestResult,
+        _exec_time: Option<&time::TestExecTime>,
+        stdout: &[u8],
+        _state: &ConsoleTestState,
+    ) -> io::Result<()> {
+        match *result {
+            TestResult::TrOk => self.write_test_point(true, desc, ""),
+            TestResult::TrIgnored => {
+                let directive = match desc.ignore_message {
+                    Some(msg) => format!(" # SKIP {}", single_line(msg)),
+                    None => String::from(" # SKIP"),
+                };
+                self.write_test_point(true, desc, &directive)
+            }
+            TestResult::TrFailed => self.write_failure(desc, None, stdout),
+            TestResult::TrFailedMsg(ref m) => self.write_failure(desc, Some(m), stdout),
+            TestResult::TrTimedFail => {
+                self.write_failure(desc, Some("time limit exceeded"), stdout)
+            }
+            TestResult::TrFlaky(failed_attempts) => {
+                self.write_test_point(true, desc, "")?;
+                let output = String::from_utf8_lossy(stdout);
+                self.write_yaml_block(&[
+                    ("failed_attempts", failed_attempts.to_string()),
+                    ("output", format!("\"{}\"", EscapedString(&output))),
+                ])
+            }
+            TestResult::TrBench(ref bs) => {
+                self.write_test_point(true, desc, "")?;
+                let bench = format!("\"{}\"", EscapedString(fmt_bench_samples(bs)));
+                self.write_yaml_block(&[("bench", bench)])
+            }
+        }
+    }
+
+    fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
+        if state.aborted {
+            self.write_line(
+                "Bail out! A test timed out in the test process, where it can't be killed",
+            )?;
+        }
+
+        let flaky =
+            if state.flaky > 0 { format!("{} flaky; ", state.flaky) } else { String::new() };
+        let time = match state.exec_time {
+            Some(ref exec_time) => format!("; finished in {exec_time}"),
+            None => String::new(),
+        };
+        self.write_line(&format!(
+            "# test result: {}. {} passed; {} failed; {flaky}{} ignored; {} measured; \
+             {} filtered out{time}",
+            if state.failed == 0 { "ok" } else { "FAILED" },
+            state.passed,
+            state.failed,
+            state.ignored,
+            state.measured,
+            state.filtered_out,
+        ))?;
+
+        Ok(state.failed == 0)
+    }
+}
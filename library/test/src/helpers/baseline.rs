@@ -0,0 +1,306 @@
+//! Saving the results of benchmarks as named baselines, and comparing later runs against them.
+//!
+//! A baseline is a JSON file in the `bench-baselines` directory next to the test binary, which is
+//! `target/<profile>/bench-baselines` for Cargo projects. It holds the summary and the samples of
+//! each benchmark:
+//!
+//! ```json
+//! { "benchmarks": {
+//!   "tests::bench_sum": { "median": 812.5, "mean": 815.1, ..., "samples": [808.4, 812.5, ...] }
+//! } }
+//! ```
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::bench::{BaselineComparison, BaselineVerdict, BenchSamples};
+use crate::formatters::EscapedString;
+use crate::stats::{self, Summary};
+
+/// Largest p-value of the Mann-Whitney U test for a change to be significant.
+const SIGNIFICANCE_LEVEL: f64 = 0.05;
+
+/// The saved results of a run of the benchmarks.
+#[derive(Debug, Default, PartialEq)]
+pub struct Baseline {
+    name: String,
+    /// The samples and MB/s of each benchmark, by name.
+    benchmarks: BTreeMap<String, (Vec<f64>, usize)>,
+}
+
+impl Baseline {
+    /// Loads the baseline `name`, which has to exist.
+    pub fn load(name: &str) -> io::Result<Baseline> {
+        let path = baseline_path(name)?;
+        let json = fs::read_to_string(&path).map_err(|e| {
+            io::Error::new(e.kind(), format!("failed to read baseline `{name}` from {path:?}: {e}"))
+        })?;
+        Baseline::from_json(name, &json).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("invalid baseline file {path:?}"))
+        })
+    }
+
+    /// Saves the results of the benchmarks as the baseline `name`, keeping the results of other
+    /// benchmarks of the baseline, such as the ones of another test binary. An existing baseline
+    /// that can't be read is left untouched, rather than losing the results it holds.
+    pub fn save(name: &str, results: &[(String, BenchSamples)]) -> io::Result<()> {
+        let path = baseline_path(name)?;
+        let mut baseline = match fs::read_to_string(&path) {
+            Ok(json) => Baseline::from_json(name, &json).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid baseline file {path:?}"),
+                )
+            })?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Baseline::default(),
+            Err(e) => {
+                return Err(io::Error::new(
+                    e.kind(),
+                    format!("failed to read baseline `{name}` from {path:?}: {e}"),
+                ));
+            }
+        };
+        for (bench, bs) in results {
+            if !bs.samples.is_empty() {
+                baseline.benchmarks.insert(bench.clone(), (bs.samples.clone(), bs.mb_s));
+            }
+        }
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, baseline.to_json())
+    }
+
+    /// Compares the samples of a benchmark with the ones of the baseline, which are significantly
+    /// different if their medians differ by at least `threshold_pct` percent, and the
+    /// Mann-Whitney U test says that the difference is unlikely to be noise.
+    pub fn compare(
+        &self,
+        bench: &str,
+        bs: &BenchSamples,
+        threshold_pct: f64,
+    ) -> Option<BaselineComparison> {
+        let (samples, _) = self.benchmarks.get(bench)?;
+        if samples.is_empty() || bs.samples.is_empty() {
+            return None;
+        }
+
+        let median = Summary::new(samples).median;
+        let change_pct =
+            if median > 0.0 { (bs.ns_iter_summ.median - median) / median * 100.0 } else { 0.0 };
+        let p_value = stats::mann_whitney_u(samples, &bs.samples);
+        let verdict = if p_value >= SIGNIFICANCE_LEVEL || change_pct.abs() < threshold_pct {
+            BaselineVerdict::Unchanged
+        } else if change_pct > 0.0 {
+            BaselineVerdict::Regressed
+        } else {
+            BaselineVerdict::Improved
+        };
+
+        Some(BaselineComparison { name: self.name.clone(), median, change_pct, p_value, verdict })
+    }
+
+    pub(crate) fn to_json(&self) -> String {
+        let mut json = String::from("{ \"benchmarks\": {\n");
+        for (i, (bench, (samples, mb_s))) in self.benchmarks.iter().enumerate() {
+            let summ = Summary::new(samples);
+            let samples = samples.iter().map(|&x| number(x)).collect::<Vec<_>>().join(", ");
+            write!(
+                json,
+                "  \"{}\": {{ \"median\": {}, \"mean\": {}, \"min\": {}, \"max\": {}, \
+                 \"std_dev\": {}, \"median_abs_dev\": {}, \"mb_s\": {mb_s}, \"samples\": [{samples}] }}",
+                EscapedString(bench),
+                number(summ.median),
+                number(summ.mean),
+                number(summ.min),
+                number(summ.max),
+                number(summ.std_dev),
+                number(summ.median_abs_dev),
+            )
+            .unwrap();
+            json.push_str(if i + 1 < self.benchmarks.len() { ",\n" } else { "\n" });
+        }
+        json.push_str("} }\n");
+        json
+    }
+
+    pub(crate) fn from_json(name: &str, json: &str) -> Option<Baseline> {
+        let mut parser = Parser { s: json.as_bytes(), pos: 0 };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.pos != json.len() {
+            return None;
+        }
+
+        let mut benchmarks = BTreeMap::new();
+        for (bench, entry) in value.get("benchmarks")?.as_object()? {
+            let samples = entry.get("samples")?.as_array()?;
+            let samples = samples.iter().map(Json::as_f64).collect::<Option<Vec<_>>>()?;
+            let mb_s = entry.get("mb_s").and_then(Json::as_f64).unwrap_or(0.0);
+            benchmarks.insert(bench.clone(), (samples, mb_s as usize));
+        }
+        Some(Baseline { name: name.to_string(), benchmarks })
+    }
+}
+
+/// Returns the path of the baseline `name`.
+fn baseline_path(name: &str) -> io::Result<PathBuf> {
+    let exe = env::current_exe()?;
+    let mut dir = exe.parent().ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+    // Cargo puts test binaries in `target/<profile>/deps`.
+    if dir.ends_with("deps") {
+        dir = dir.parent().unwrap_or(dir);
+    }
+    Ok(dir.join("bench-baselines").join(format!("{name}.json")))
+}
+
+fn number(x: f64) -> String {
+    if x.is_finite() { x.to_string() } else { String::from("null") }
+}
+
+/// The JSON values that baselines are made of.
+enum Json {
+    Null,
+    Number(f64),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn get(&self, key: &str) -> Option<&Json> {
+        self.as_object()?.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    fn as_object(&self) -> Option<&[(String, Json)]> {
+        if let Json::Object(entries) = self { Some(entries) } else { None }
+    }
+
+    fn as_array(&self) -> Option<&[Json]> {
+        if let Json::Array(values) = self { Some(values) } else { None }
+    }
+
+    /// Returns the number, or NaN for `null`, which non-finite numbers are saved as.
+    fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Json::Number(x) => Some(x),
+            Json::Null => Some(f64::NAN),
+            _ => None,
+        }
+    }
+}
+
+/// A small JSON parser, since libtest can't depend on a JSON crate.
+struct Parser<'a> {
+    s: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.s.get(self.pos).is_some_and(|b| b.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        let eaten = self.s[self.pos..].starts_with(token.as_bytes());
+        if eaten {
+            self.pos += token.len();
+        }
+        eaten
+    }
+
+    fn value(&mut self) -> Option<Json> {
+        self.skip_whitespace();
+        match *self.s.get(self.pos)? {
+            b'{' => {
+                self.pos += 1;
+                let mut entries = Vec::new();
+                if !self.eat("}") {
+                    loop {
+                        self.skip_whitespace();
+                        let key = self.string()?;
+                        if !self.eat(":") {
+                            return None;
+                        }
+                        entries.push((key, self.value()?));
+                        if self.eat("}") {
+                            break;
+                        }
+                        if !self.eat(",") {
+                            return None;
+                        }
+                    }
+                }
+                Some(Json::Object(entries))
+            }
+            b'[' => {
+                self.pos += 1;
+                let mut values = Vec::new();
+                if !self.eat("]") {
+                    loop {
+                        values.push(self.value()?);
+                        if self.eat("]") {
+                            break;
+                        }
+                        if !self.eat(",") {
+                            return None;
+                        }
+                    }
+                }
+                Some(Json::Array(values))
+            }
+            _ if self.eat("null") => Some(Json::Null),
+            _ => {
+                let len = self.s[self.pos..]
+                    .iter()
+                    .take_while(|b| matches!(b, b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E'))
+                    .count();
+                let number = std::str::from_utf8(&self.s[self.pos..self.pos + len]).ok()?;
+                self.pos += len;
+                number.parse().ok().map(Json::Number)
+            }
+        }
+    }
+
+    fn string(&mut self) -> Option<String> {
+        if self.s.get(self.pos) != Some(&b'"') {
+            return None;
+        }
+        self.pos += 1;
+        let mut bytes = Vec::new();
+        loop {
+            match *self.s.get(self.pos)? {
+                b'"' => break,
+                b'\\' => {
+                    self.pos += 1;
+                    let escaped = match *self.s.get(self.pos)? {
+                        b'n' => '\n',
+                        b't' => '\t',
+                        b'r' => '\r',
+                        b'b' => '\x08',
+                        b'f' => '\x0c',
+                        b'u' => {
+                            let hex = self.s.get(self.pos + 1..self.pos + 5)?;
+                            let code = u32::from_str_radix(std::str::from_utf8(hex).ok()?, 16);
+                            self.pos += 4;
+                            char::from_u32(code.ok()?)?
+                        }
+                        b => b as char,
+                    };
+                    bytes.extend_from_slice(escaped.encode_utf8(&mut [0; 4]).as_bytes());
+                }
+                b => bytes.push(b),
+            }
+            self.pos += 1;
+        }
+        self.pos += 1;
+        String::from_utf8(bytes).ok()
+    }
+}
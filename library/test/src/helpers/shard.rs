@@ -0,0 +1,12 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Returns whether the test named `name` belongs to the shard `index` out of `count` shards.
+///
+/// The shard only depends on the name of the test, so every run of the same test binary splits
+/// the tests the same way, whichever tests are filtered out.
+pub fn is_in_shard(name: &str, index: usize, count: usize) -> bool {
+    let mut s = DefaultHasher::new();
+    name.hash(&mut s);
+    s.finish() % count as u64 == index as u64
+}
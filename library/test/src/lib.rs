@@ -33,6 +33,8 @@
 pub use self::types::*;
 pub use self::ColorConfig::*;
 pub use cli::TestOpts;
+#[doc(hidden)]
+pub use time::timeout_attr_ms;
 
 // Module to be used by rustc to compile tests in libtest
 pub mod test {
@@ -86,6 +88,7 @@ pub mod test {
 use event::{CompletedTest, TestEvent};
 use helpers::concurrency::get_concurrency;
 use helpers::exit_code::get_exit_code;
+use helpers::shard::is_in_shard;
 use helpers::shuffle::{get_shuffle_seed, shuffle_tests};
 use options::RunStrategy;
 use test_result::*;
@@ -219,6 +222,16 @@ fn make_owned_test(test: &&TestDescAndFn) -> TestDescAndFn {
     }
 }
 
+/// Clones a test so that it can be run again, which is only possible for static tests.
+fn try_clone_test(test: &TestDescAndFn) -> Option<TestDescAndFn> {
+    let testfn = match test.testfn {
+        StaticTestFn(f) => StaticTestFn(f),
+        StaticBenchAsTestFn(f) => StaticBenchAsTestFn(f),
+        _ => return None,
+    };
+    Some(TestDescAndFn { desc: test.desc.clone(), testfn })
+}
+
 /// Invoked when unit tests terminate. Returns `Result::Err` if the test is
 /// considered a failure. By default, invokes `report()` and checks for a `0`
 /// result.
@@ -295,6 +308,16 @@ struct TimeoutEntry {
         timeout: Instant,
     }
 
+    /// A test that can be run again if it fails, with the output of its failed attempts.
+    struct RetryEntry {
+        test: TestDescAndFn,
+        failed_attempts: usize,
+        stdout: Vec<u8>,
+    }
+
+    type RetryMap =
+        HashMap<TestId, RetryEntry, BuildHasherDefault<collections::hash_map::DefaultHasher>>;
+
     let tests_len = tests.len();
 
     let mut filtered = FilteredTests { tests: Vec::new(), benches: Vec::new(), next_id: 0 };
@@ -344,6 +367,17 @@ struct TimeoutEntry {
     let mut remaining = VecDeque::from(remaining);
     let mut pending = 0;
 
+    if opts.retries > 0 {
+        let not_retryable =
+            remaining.iter().filter(|(_, test)| try_clone_test(test).is_none()).count();
+        if not_retryable > 0 {
+            eprintln!(
+                "warning: `--retries` has no effect on {not_retryable} test(s) that can't be \
+                 run again, such as tests created at runtime"
+            );
+        }
+    }
+
     let (tx, rx) = channel::<CompletedTest>();
     let run_strategy = if opts.options.panic_abort && !opts.force_run_in_process {
         RunStrategy::SpawnPrimary
@@ -353,6 +387,15 @@ struct TimeoutEntry {
 
     let mut running_tests: TestMap = HashMap::default();
     let mut timeout_queue: VecDeque<TimeoutEntry> = VecDeque::new();
+    let mut retry_map: RetryMap = HashMap::default();
+    // The deadlines of the tests with a timeout that run in this process, where they can't be
+    // killed once they time out.
+    let mut hang_deadlines: Vec<TimeoutEntry> = Vec::new();
+
+    let hang_deadline = |test: &TestDescAndFn| match run_strategy {
+        RunStrategy::InProcess => test.desc.hard_timeout(opts.test_timeout).map(|t| Instant::now() + t),
+        RunStrategy::SpawnPrimary => None,
+    };
 
     fn get_timed_out_tests(
         running_tests: &TestMap,
@@ -379,18 +422,122 @@ fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
         })
     }
 
+    /// Remembers a test that is about to run so that it can be run again, and returns whether
+    /// this is the first attempt at running it.
+    fn start_attempt(
+        opts: &TestOpts,
+        retry_map: &mut RetryMap,
+        id: TestId,
+        test: &TestDescAndFn,
+    ) -> bool {
+        if opts.retries == 0 {
+            return true;
+        }
+        if let Some(entry) = retry_map.get(&id) {
+            return entry.failed_attempts == 0;
+        }
+        if let Some(test) = try_clone_test(test) {
+            retry_map.insert(id, RetryEntry { test, failed_attempts: 0, stdout: Vec::new() });
+        }
+        true
+    }
+
+    /// Queues a failed test to run again if it has retries left, in which case its result
+    /// isn't reported. A test that passes after failing is reported as flaky, with the output of
+    /// its failed attempts. Returns the result to report, if any.
+    fn finish_attempt(
+        opts: &TestOpts,
+        retry_map: &mut RetryMap,
+        remaining: &mut VecDeque<(TestId, TestDescAndFn)>,
+        mut completed_test: CompletedTest,
+    ) -> Option<CompletedTest> {
+        let id = completed_test.id;
+        let Some(entry) = retry_map.get_mut(&id) else {
+            return Some(completed_test);
+        };
+        match completed_test.result {
+            TrFailed | TrFailedMsg(_) | TrTimedFail if entry.failed_attempts < opts.retries => {
+                entry.failed_attempts += 1;
+                let header = format!("---- attempt {} ----\n", entry.failed_attempts);
+                entry.stdout.extend_from_slice(header.as_bytes());
+                entry.stdout.extend_from_slice(&completed_test.stdout);
+                if let TrFailedMsg(ref msg) = completed_test.result {
+                    entry.stdout.extend_from_slice(format!("note: {msg}\n").as_bytes());
+                }
+                let test = try_clone_test(&entry.test).unwrap();
+                remaining.push_front((id, test));
+                return None;
+            }
+            TrOk if entry.failed_attempts > 0 => {
+                let entry = retry_map.remove(&id).unwrap();
+                completed_test.result = TrFlaky(entry.failed_attempts);
+                completed_test.stdout = entry.stdout;
+            }
+            _ => {
+                // Keep the output of all the attempts of a test that failed every time.
+                let entry = retry_map.remove(&id).unwrap();
+                if entry.failed_attempts > 0 {
+                    let mut stdout = entry.stdout;
+                    let header = format!("---- attempt {} ----\n", entry.failed_attempts + 1);
+                    stdout.extend_from_slice(header.as_bytes());
+                    stdout.extend_from_slice(&completed_test.stdout);
+                    completed_test.stdout = stdout;
+                }
+            }
+        }
+        Some(completed_test)
+    }
+
+    /// Reports a test that timed out in this process, where it can't be killed, and stops the
+    /// test run.
+    fn report_hung_test<F>(
+        id: TestId,
+        desc: TestDesc,
+        notify_about_test_event: &mut F,
+    ) -> io::Result<()>
+    where
+        F: FnMut(TestEvent) -> io::Result<()>,
+    {
+        let completed_test = CompletedTest::new(id, desc, TrTimedFail, None, Vec::new());
+        notify_about_test_event(TestEvent::TeResult(completed_test))?;
+        notify_about_test_event(TestEvent::TeAborted)
+    }
+
     if concurrency == 1 {
         while !remaining.is_empty() {
             let (id, test) = remaining.pop_front().unwrap();
-            let event = TestEvent::TeWait(test.desc.clone());
-            notify_about_test_event(event)?;
+            if start_attempt(opts, &mut retry_map, id, &test) {
+                let event = TestEvent::TeWait(test.desc.clone());
+                notify_about_test_event(event)?;
+            }
+            let desc = test.desc.clone();
+            let deadline = hang_deadline(&test);
             let join_handle = run_test(opts, !opts.run_tests, id, test, run_strategy, tx.clone());
             // Wait for the test to complete.
-            let mut completed_test = rx.recv().unwrap();
+            let mut completed_test = match deadline {
+                Some(deadline) => {
+                    match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                        Ok(completed_test) => completed_test,
+                        Err(_) => {
+                            report_hung_test(id, desc, &mut notify_about_test_event)?;
+                            // Prevent the hung test thread from panicking
+                            std::mem::forget(rx);
+                            return Ok(());
+                        }
+                    }
+                }
+                None => rx.recv().unwrap(),
+            };
             RunningTest { join_handle }.join(&mut completed_test);
 
+            let Some(completed_test) =
+                finish_attempt(opts, &mut retry_map, &mut remaining, completed_test)
+            else {
+                continue;
+            };
+
             let fail_fast = match completed_test.result {
-                TrIgnored | TrOk | TrBench(_) => false,
+                TrIgnored | TrOk | TrFlaky(_) | TrBench(_) => false,
                 TrFailed | TrFailedMsg(_) | TrTimedFail => opts.fail_fast,
             };
 
@@ -408,8 +555,13 @@ fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
                 let timeout = time::get_default_test_timeout();
                 let desc = test.desc.clone();
 
-                let event = TestEvent::TeWait(desc.clone());
-                notify_about_test_event(event)?; //here no pad
+                if start_attempt(opts, &mut retry_map, id, &test) {
+                    let event = TestEvent::TeWait(desc.clone());
+                    notify_about_test_event(event)?; //here no pad
+                }
+                if let Some(deadline) = hang_deadline(&test) {
+                    hang_deadlines.push(TimeoutEntry { id, desc: desc.clone(), timeout: deadline });
+                }
                 let join_handle =
                     run_test(opts, !opts.run_tests, id, test, run_strategy, tx.clone());
                 running_tests.insert(id, RunningTest { join_handle });
@@ -419,7 +571,11 @@ fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
 
             let mut res;
             loop {
-                if let Some(timeout) = calc_timeout(&timeout_queue) {
+                let now = Instant::now();
+                let hang_timeout =
+                    hang_deadlines.iter().map(|entry| entry.timeout.saturating_duration_since(now));
+                let timeout = calc_timeout(&timeout_queue).into_iter().chain(hang_timeout).min();
+                if let Some(timeout) = timeout {
                     res = rx.recv_timeout(timeout);
                     for test in get_timed_out_tests(&running_tests, &mut timeout_queue) {
                         let event = TestEvent::TeTimeout(test);
@@ -428,6 +584,14 @@ fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
 
                     match res {
                         Err(RecvTimeoutError::Timeout) => {
+                            let now = Instant::now();
+                            if let Some(i) = hang_deadlines.iter().position(|e| e.timeout <= now) {
+                                let TimeoutEntry { id, desc, .. } = hang_deadlines.swap_remove(i);
+                                report_hung_test(id, desc, &mut notify_about_test_event)?;
+                                // Prevent the hung test threads from panicking
+                                std::mem::forget(rx);
+                                return Ok(());
+                            }
                             // Result is not yet ready, continue waiting.
                         }
                         _ => {
@@ -442,17 +606,24 @@ fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
             }
 
             let mut completed_test = res.unwrap();
+            hang_deadlines.retain(|entry| entry.id != completed_test.id);
             let running_test = running_tests.remove(&completed_test.id).unwrap();
             running_test.join(&mut completed_test);
+            pending -= 1;
+
+            let Some(completed_test) =
+                finish_attempt(opts, &mut retry_map, &mut remaining, completed_test)
+            else {
+                continue;
+            };
 
             let fail_fast = match completed_test.result {
-                TrIgnored | TrOk | TrBench(_) => false,
+                TrIgnored | TrOk | TrFlaky(_) | TrBench(_) => false,
                 TrFailed | TrFailedMsg(_) | TrTimedFail => opts.fail_fast,
             };
 
             let event = TestEvent::TeResult(completed_test);
             notify_about_test_event(event)?;
-            pending -= 1;
 
             if fail_fast {
                 // Prevent remaining test threads from panicking
@@ -500,6 +671,11 @@ pub fn filter_tests(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> Vec<TestDescA
         filtered.retain(|test| !opts.skip.iter().any(|sf| matches_filter(test, sf)));
     }
 
+    // Only keep the tests of the selected shard
+    if let Some((index, count)) = opts.shard {
+        filtered.retain(|test| is_in_shard(test.desc.name.as_slice(), index, count));
+    }
+
     // Excludes #[should_panic] tests
     if opts.exclude_should_panic {
         filtered.retain(|test| test.desc.should_panic == ShouldPanic::No);
@@ -569,6 +745,7 @@ pub fn run_test(
             let nocapture = opts.nocapture;
             let time_options = opts.time_options;
             let bench_benchmarks = opts.bench_benchmarks;
+            let timeout = desc.hard_timeout(opts.test_timeout);
 
             let runtest = move || match strategy {
                 RunStrategy::InProcess => run_test_in_process(
@@ -588,6 +765,7 @@ pub fn run_test(
                     monitor_ch,
                     time_options,
                     bench_benchmarks,
+                    timeout,
                 ),
             };
 
@@ -686,6 +864,7 @@ fn spawn_test_subprocess(
     monitor_ch: Sender<CompletedTest>,
     time_opts: Option<time::TestTimeOptions>,
     bench_benchmarks: bool,
+    timeout: Option<Duration>,
 ) {
     let (result, test_output, exec_time) = (|| {
         let args = env::args().collect::<Vec<_>>();
@@ -702,7 +881,17 @@ fn spawn_test_subprocess(
         }
 
         let start = report_time.then(Instant::now);
-        let output = match command.output() {
+        let output = match timeout {
+            Some(timeout) => {
+                if !nocapture {
+                    command.stdout(process::Stdio::piped());
+                    command.stderr(process::Stdio::piped());
+                }
+                output_with_timeout(&mut command, timeout)
+            }
+            None => command.output().map(|output| (output, false)),
+        };
+        let (output, timed_out) = match output {
             Ok(out) => out,
             Err(e) => {
                 let err = format!("Failed to spawn {} as child for test: {:?}", args[0], e);
@@ -719,6 +908,16 @@ fn spawn_test_subprocess(
         formatters::write_stderr_delimiter(&mut test_output, &desc.name);
         test_output.extend_from_slice(&stderr);
 
+        if timed_out {
+            let timeout = timeout.unwrap();
+            writeln!(
+                &mut test_output,
+                "note: test did not finish within {timeout:?} and was killed"
+            )
+            .unwrap();
+            return (TrTimedFail, test_output, exec_time);
+        }
+
         let result = match (|| -> Result<TestResult, String> {
             let exit_code = get_exit_code(status)?;
             Ok(get_result_from_exit_code(&desc, exit_code, &time_opts, &exec_time))
@@ -737,6 +936,49 @@ fn spawn_test_subprocess(
     monitor_ch.send(message).unwrap();
 }
 
+/// Runs `command` like `Command::output`, but kills it once it runs for longer than `timeout`.
+/// Also returns whether it was killed.
+fn output_with_timeout(
+    command: &mut Command,
+    timeout: Duration,
+) -> io::Result<(process::Output, bool)> {
+    fn read_pipe<R: io::Read + Send + 'static>(
+        pipe: Option<R>,
+    ) -> thread::JoinHandle<io::Result<Vec<u8>>> {
+        thread::spawn(move || {
+            let mut output = Vec::new();
+            if let Some(mut pipe) = pipe {
+                pipe.read_to_end(&mut output)?;
+            }
+            Ok(output)
+        })
+    }
+
+    let deadline = Instant::now() + timeout;
+    let mut child = command.stdin(process::Stdio::null()).spawn()?;
+    // Read the output on other threads, so that the child doesn't block on a full pipe.
+    let stdout = read_pipe(child.stdout.take());
+    let stderr = read_pipe(child.stderr.take());
+
+    let mut timed_out = false;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            timed_out = true;
+            child.kill()?;
+            break child.wait()?;
+        }
+        thread::sleep((deadline - now).min(Duration::from_millis(10)));
+    };
+
+    let stdout = stdout.join().unwrap()?;
+    let stderr = stderr.join().unwrap()?;
+    Ok((process::Output { status, stdout, stderr }, timed_out))
+}
+
 fn run_test_in_spawned_subprocess(desc: TestDesc, runnable_test: RunnableTest) -> ! {
     let builtin_panic_hook = panic::take_hook();
     let record_result = Arc::new(move |panic_info: Option<&'_ PanicInfo<'_>>| {
@@ -36,6 +36,10 @@ pub enum OutputFormat {
     Json,
     /// JUnit output
     Junit,
+    /// TAP 14 output
+    Tap,
+    /// Pretty output with GitHub Actions annotations
+    Github,
 }
 
 /// Whether ignored test should be run or not
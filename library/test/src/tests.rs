@@ -3,7 +3,7 @@
 use crate::{
     bench::Bencher,
     console::OutputLocation,
-    formatters::PrettyFormatter,
+    formatters::{FailureLocation, OutputFormatter, PrettyFormatter, TapFormatter},
     options::OutputFormat,
     test::{
         filter_tests,
@@ -49,6 +49,12 @@ fn new() -> TestOpts {
             shuffle_seed: None,
             test_threads: None,
             skip: vec![],
+            shard: None,
+            retries: 0,
+            test_timeout: None,
+            save_baseline: None,
+            baseline: None,
+            baseline_threshold: 2.0,
             time_options: None,
             options: Options::new(),
             fail_fast: false,
@@ -71,6 +77,8 @@ fn one_ignored_one_unignored_test() -> Vec<TestDescAndFn> {
                 should_panic: ShouldPanic::No,
                 compile_fail: false,
                 no_run: false,
+                #[cfg(not(bootstrap))]
+                timeout: None,
                 test_type: TestType::Unknown,
             },
             testfn: DynTestFn(Box::new(move || Ok(()))),
@@ -88,6 +96,8 @@ fn one_ignored_one_unignored_test() -> Vec<TestDescAndFn> {
                 should_panic: ShouldPanic::No,
                 compile_fail: false,
                 no_run: false,
+                #[cfg(not(bootstrap))]
+                timeout: None,
                 test_type: TestType::Unknown,
             },
             testfn: DynTestFn(Box::new(move || Ok(()))),
@@ -113,6 +123,8 @@ fn f() -> Result<(), String> {
             should_panic: ShouldPanic::No,
             compile_fail: false,
             no_run: false,
+            #[cfg(not(bootstrap))]
+            timeout: None,
             test_type: TestType::Unknown,
         },
         testfn: DynTestFn(Box::new(f)),
@@ -141,6 +153,8 @@ fn f() -> Result<(), String> {
             should_panic: ShouldPanic::No,
             compile_fail: false,
             no_run: false,
+            #[cfg(not(bootstrap))]
+            timeout: None,
             test_type: TestType::Unknown,
         },
         testfn: DynTestFn(Box::new(f)),
@@ -172,6 +186,8 @@ fn f() -> Result<(), String> {
             should_panic: ShouldPanic::Yes,
             compile_fail: false,
             no_run: false,
+            #[cfg(not(bootstrap))]
+            timeout: None,
             test_type: TestType::Unknown,
         },
         testfn: DynTestFn(Box::new(f)),
@@ -203,6 +219,8 @@ fn f() -> Result<(), String> {
             should_panic: ShouldPanic::YesWithMessage("error message"),
             compile_fail: false,
             no_run: false,
+            #[cfg(not(bootstrap))]
+            timeout: None,
             test_type: TestType::Unknown,
         },
         testfn: DynTestFn(Box::new(f)),
@@ -239,6 +257,8 @@ fn f() -> Result<(), String> {
             should_panic: ShouldPanic::YesWithMessage(expected),
             compile_fail: false,
             no_run: false,
+            #[cfg(not(bootstrap))]
+            timeout: None,
             test_type: TestType::Unknown,
         },
         testfn: DynTestFn(Box::new(f)),
@@ -279,6 +299,8 @@ fn f() -> Result<(), String> {
             should_panic: ShouldPanic::YesWithMessage(expected),
             compile_fail: false,
             no_run: false,
+            #[cfg(not(bootstrap))]
+            timeout: None,
             test_type: TestType::Unknown,
         },
         testfn: DynTestFn(Box::new(f)),
@@ -313,6 +335,8 @@ fn f() -> Result<(), String> {
                 should_panic,
                 compile_fail: false,
                 no_run: false,
+                #[cfg(not(bootstrap))]
+                timeout: None,
                 test_type: TestType::Unknown,
             },
             testfn: DynTestFn(Box::new(f)),
@@ -346,6 +370,8 @@ fn f() -> Result<(), String> {
             should_panic: ShouldPanic::No,
             compile_fail: false,
             no_run: false,
+            #[cfg(not(bootstrap))]
+            timeout: None,
             test_type: TestType::Unknown,
         },
         testfn: DynTestFn(Box::new(f)),
@@ -388,6 +414,8 @@ fn f() -> Result<(), String> {
             should_panic: ShouldPanic::No,
             compile_fail: false,
             no_run: false,
+            #[cfg(not(bootstrap))]
+            timeout: None,
             test_type,
         },
         testfn: DynTestFn(Box::new(f)),
@@ -432,6 +460,8 @@ fn typed_test_desc(test_type: TestType) -> TestDesc {
         should_panic: ShouldPanic::No,
         compile_fail: false,
         no_run: false,
+        #[cfg(not(bootstrap))]
+        timeout: None,
         test_type,
     }
 }
@@ -474,6 +504,71 @@ fn test_time_options_threshold() {
     }
 }
 
+#[test]
+fn test_timeout_parsing() {
+    use crate::time::parse_test_timeout;
+
+    assert_eq!(parse_test_timeout("500ms"), Some(Duration::from_millis(500)));
+    assert_eq!(parse_test_timeout("30s"), Some(Duration::from_secs(30)));
+    assert_eq!(parse_test_timeout("2m"), Some(Duration::from_secs(120)));
+    assert_eq!(parse_test_timeout("1h"), Some(Duration::from_secs(3600)));
+    assert_eq!(parse_test_timeout("30"), None);
+    assert_eq!(parse_test_timeout("0s"), None);
+    assert_eq!(parse_test_timeout("1.5s"), None);
+    assert_eq!(parse_test_timeout("s"), None);
+    assert_eq!(parse_test_timeout("18446744073709551616ms"), None);
+    assert_eq!(parse_test_timeout("18446744073709551615s"), None);
+}
+
+#[test]
+#[cfg(not(target_os = "emscripten"))]
+fn hung_test_in_process_stops_the_run() {
+    fn hang() -> Result<(), String> {
+        std::thread::sleep(Duration::from_secs(3600));
+        Ok(())
+    }
+    fn pass() -> Result<(), String> {
+        Ok(())
+    }
+    let test = |name, testfn| TestDescAndFn {
+        desc: TestDesc {
+            name: StaticTestName(name),
+            ignore: false,
+            ignore_message: None,
+            source_file: "",
+            start_line: 0,
+            start_col: 0,
+            end_line: 0,
+            end_col: 0,
+            should_panic: ShouldPanic::No,
+            compile_fail: false,
+            no_run: false,
+            #[cfg(not(bootstrap))]
+            timeout: None,
+            test_type: TestType::Unknown,
+        },
+        testfn: StaticTestFn(testfn),
+    };
+    let (tx, rx) = channel();
+    let notify = move |event: TestEvent| {
+        match event {
+            TestEvent::TeResult(result) => tx.send(Some(result.result)).unwrap(),
+            TestEvent::TeAborted => tx.send(None).unwrap(),
+            _ => {}
+        }
+        Ok(())
+    };
+    let opts = TestOpts {
+        run_tests: true,
+        test_threads: Some(1),
+        test_timeout: Some(Duration::from_millis(10)),
+        ..TestOpts::new()
+    };
+    run_tests(&opts, vec![test("a", hang), test("b", pass)], notify).unwrap();
+    // The second test never runs.
+    assert_eq!(rx.iter().collect::<Vec<_>>(), [Some(TrTimedFail), None]);
+}
+
 #[test]
 fn parse_ignored_flag() {
     let args = vec!["progname".to_string(), "filter".to_string(), "--ignored".to_string()];
@@ -549,6 +644,8 @@ pub fn exclude_should_panic_option() {
             should_panic: ShouldPanic::Yes,
             compile_fail: false,
             no_run: false,
+            #[cfg(not(bootstrap))]
+            timeout: None,
             test_type: TestType::Unknown,
         },
         testfn: DynTestFn(Box::new(move || Ok(()))),
@@ -578,6 +675,8 @@ fn tests() -> Vec<TestDescAndFn> {
                     should_panic: ShouldPanic::No,
                     compile_fail: false,
                     no_run: false,
+                    #[cfg(not(bootstrap))]
+                    timeout: None,
                     test_type: TestType::Unknown,
                 },
                 testfn: DynTestFn(Box::new(move || Ok(()))),
@@ -674,6 +773,8 @@ fn testfn() -> Result<(), String> {
                 should_panic: ShouldPanic::No,
                 compile_fail: false,
                 no_run: false,
+                #[cfg(not(bootstrap))]
+                timeout: None,
                 test_type: TestType::Unknown,
             },
             testfn: DynTestFn(Box::new(testfn)),
@@ -683,6 +784,94 @@ fn testfn() -> Result<(), String> {
     tests
 }
 
+#[test]
+pub fn shards_partition_tests() {
+    let names = |tests: Vec<TestDescAndFn>| {
+        tests.into_iter().map(|test| test.desc.name.to_string()).collect::<Vec<_>>()
+    };
+    let all = names(sample_tests());
+
+    let mut sharded = Vec::new();
+    for index in 0..3 {
+        let opts = TestOpts { shard: Some((index, 3)), ..TestOpts::new() };
+        let shard = names(filter_tests(&opts, sample_tests()));
+        assert!(shard.len() < all.len());
+        sharded.extend(shard);
+    }
+
+    // Every test belongs to exactly one shard.
+    sharded.sort();
+    let mut all = all;
+    all.sort();
+    assert_eq!(sharded, all);
+}
+
+#[test]
+fn parse_shard_options() {
+    let args = |extra: &[&str]| {
+        let mut args = vec!["progname".to_string(), "-Zunstable-options".to_string()];
+        args.extend(extra.iter().map(|arg| arg.to_string()));
+        args
+    };
+
+    let opts = parse_opts(&args(&["--shard-index=1", "--shard-count=4"])).unwrap().unwrap();
+    assert_eq!(opts.shard, Some((1, 4)));
+
+    assert!(parse_opts(&args(&["--shard-index=1"])).unwrap().is_err());
+    assert!(parse_opts(&args(&["--shard-index=4", "--shard-count=4"])).unwrap().is_err());
+    assert!(parse_opts(&args(&["--shard-index=0", "--shard-count=0"])).unwrap().is_err());
+}
+
+#[test]
+#[cfg(not(target_os = "emscripten"))]
+fn retried_test_is_reported_as_flaky() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static ATTEMPTS: AtomicUsize = AtomicUsize::new(0);
+
+    fn f() -> Result<(), String> {
+        if ATTEMPTS.fetch_add(1, Ordering::SeqCst) < 2 { Err("not yet".into()) } else { Ok(()) }
+    }
+    let test = || TestDescAndFn {
+        desc: TestDesc {
+            name: StaticTestName("whatever"),
+            ignore: false,
+            ignore_message: None,
+            source_file: "",
+            start_line: 0,
+            start_col: 0,
+            end_line: 0,
+            end_col: 0,
+            should_panic: ShouldPanic::No,
+            compile_fail: false,
+            no_run: false,
+            #[cfg(not(bootstrap))]
+            timeout: None,
+            test_type: TestType::Unknown,
+        },
+        testfn: StaticTestFn(f),
+    };
+    let run = |retries| {
+        let (tx, rx) = channel();
+        let notify = move |event: TestEvent| {
+            if let TestEvent::TeResult(result) = event {
+                tx.send(result).unwrap();
+            }
+            Ok(())
+        };
+        let opts = TestOpts { run_tests: true, retries, ..TestOpts::new() };
+        run_tests(&opts, vec![test()], notify).unwrap();
+        rx.iter().map(|completed_test| completed_test.result).collect::<Vec<_>>()
+    };
+
+    // Not enough retries, so only the last failure is reported.
+    assert_eq!(run(1), [TrFailed]);
+    assert_eq!(ATTEMPTS.swap(0, Ordering::SeqCst), 2);
+
+    assert_eq!(run(2), [TrFlaky(2)]);
+    assert_eq!(ATTEMPTS.load(Ordering::SeqCst), 3);
+}
+
 #[test]
 pub fn shuffle_tests() {
     let mut opts = TestOpts::new();
@@ -808,6 +997,8 @@ fn f(_: &mut Bencher) -> Result<(), String> {
         should_panic: ShouldPanic::No,
         compile_fail: false,
         no_run: false,
+        #[cfg(not(bootstrap))]
+        timeout: None,
         test_type: TestType::Unknown,
     };
 
@@ -836,6 +1027,8 @@ fn f(b: &mut Bencher) -> Result<(), String> {
         should_panic: ShouldPanic::No,
         compile_fail: false,
         no_run: false,
+        #[cfg(not(bootstrap))]
+        timeout: None,
         test_type: TestType::Unknown,
     };
 
@@ -857,6 +1050,8 @@ fn should_sort_failures_before_printing_them() {
         should_panic: ShouldPanic::No,
         compile_fail: false,
         no_run: false,
+        #[cfg(not(bootstrap))]
+        timeout: None,
         test_type: TestType::Unknown,
     };
 
@@ -872,6 +1067,8 @@ fn should_sort_failures_before_printing_them() {
         should_panic: ShouldPanic::No,
         compile_fail: false,
         no_run: false,
+        #[cfg(not(bootstrap))]
+        timeout: None,
         test_type: TestType::Unknown,
     };
 
@@ -882,6 +1079,7 @@ fn should_sort_failures_before_printing_them() {
         total: 0,
         passed: 0,
         failed: 0,
+        flaky: 0,
         ignored: 0,
         filtered_out: 0,
         measured: 0,
@@ -892,6 +1090,11 @@ fn should_sort_failures_before_printing_them() {
         not_failures: Vec::new(),
         ignores: Vec::new(),
         time_failures: Vec::new(),
+        flakes: Vec::new(),
+        baseline: None,
+        baseline_threshold: 2.0,
+        benches: Vec::new(),
+        aborted: false,
     };
 
     out.write_failures(&st).unwrap();
@@ -905,6 +1108,140 @@ fn should_sort_failures_before_printing_them() {
     assert!(apos < bpos);
 }
 
+fn located_test_desc(name: &'static str) -> TestDesc {
+    TestDesc {
+        name: StaticTestName(name),
+        ignore: false,
+        ignore_message: None,
+        source_file: "src/lib.rs",
+        start_line: 3,
+        start_col: 4,
+        end_line: 3,
+        end_col: 12,
+        should_panic: ShouldPanic::No,
+        compile_fail: false,
+        no_run: false,
+        #[cfg(not(bootstrap))]
+        timeout: None,
+        test_type: TestType::UnitTest,
+    }
+}
+
+#[test]
+fn failure_location_is_found_in_panic_message() {
+    let desc = located_test_desc("tests::it_fails");
+    let output = "before\n\
+                  thread 'tests::it_fails' panicked at src/lib.rs:10:9:\n\
+                  assertion `left == right` failed\n  left: 4\n right: 5\n\
+                  note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace\n";
+    assert_eq!(
+        FailureLocation::find(&desc, output),
+        Some(FailureLocation {
+            file: "src/lib.rs",
+            line: 10,
+            col: 9,
+            message: Some("assertion `left == right` failed\n  left: 4\n right: 5".to_string()),
+        })
+    );
+
+    // Without a panic, e.g. for tests that return an error, the test itself is the location.
+    assert_eq!(
+        FailureLocation::find(&desc, "Error: \"oops\"\n"),
+        Some(FailureLocation { file: "src/lib.rs", line: 3, col: 4, message: None })
+    );
+}
+
+#[test]
+fn tap_output() {
+    let mut out = TapFormatter::new(OutputLocation::Raw(Vec::new()));
+    let st = console::ConsoleTestState::new(&TestOpts::new()).unwrap();
+    let mut ignored = located_test_desc("it_is_ignored");
+    ignored.ignore_message = Some("slow");
+
+    out.write_run_start(3, None).unwrap();
+    out.write_result(&located_test_desc("it#works"), &TrOk, None, b"", &st).unwrap();
+    out.write_result(&ignored, &TrIgnored, None, b"", &st).unwrap();
+    let output = b"thread 'it_fails' panicked at src/lib.rs:7:5:\nexplicit panic\n";
+    out.write_result(&located_test_desc("it_fails"), &TrFailed, None, output, &st).unwrap();
+
+    let s = match out.output_location() {
+        &OutputLocation::Raw(ref m) => String::from_utf8_lossy(&m[..]),
+        &OutputLocation::Pretty(_) => unreachable!(),
+    };
+    assert_eq!(
+        s,
+        "TAP version 14\n\
+         1..3\n\
+         ok 1 - it\\#works\n\
+         ok 2 - it_is_ignored # SKIP slow\n\
+         not ok 3 - it_fails\n  \
+         ---\n  \
+         message: \"explicit panic\"\n  \
+         severity: fail\n  \
+         at: { file: \"src/lib.rs\", line: 7, column: 5 }\n  \
+         output: \"thread 'it_fails' panicked at src/lib.rs:7:5:\\nexplicit panic\\n\"\n  \
+         ...\n"
+    );
+}
+
+#[test]
+fn tap_output_multiline_name() {
+    let mut out = TapFormatter::new(OutputLocation::Raw(Vec::new()));
+    let st = console::ConsoleTestState::new(&TestOpts::new()).unwrap();
+    let mut desc = located_test_desc("");
+    desc.name = DynTestName(String::from("first line\nsecond #line\r\n"));
+
+    out.write_timeout(&desc).unwrap();
+    out.write_result(&desc, &TrOk, None, b"", &st).unwrap();
+
+    let s = match out.output_location() {
+        &OutputLocation::Raw(ref m) => String::from_utf8_lossy(&m[..]),
+        &OutputLocation::Pretty(_) => unreachable!(),
+    };
+    assert_eq!(
+        s,
+        format!(
+            "# test first line second #line   has been running for over {} seconds\n\
+             ok 1 - first line second \\#line  \n",
+            crate::time::TEST_WARN_TIMEOUT_S
+        )
+    );
+}
+
+#[test]
+fn bench_baseline_comparison() {
+    use crate::bench::{BaselineVerdict, BenchSamples};
+    use crate::helpers::baseline::Baseline;
+    use crate::stats::Summary;
+
+    fn bench_samples(samples: Vec<f64>) -> BenchSamples {
+        BenchSamples { ns_iter_summ: Summary::new(&samples), mb_s: 0, samples, baseline: None }
+    }
+    let old = bench_samples((0..50).map(|i| 100.0 + i as f64).collect());
+    let slower = bench_samples((0..50).map(|i| 130.0 + i as f64).collect());
+    let faster = bench_samples((0..50).map(|i| 80.0 + i as f64).collect());
+    let same = bench_samples((0..50).map(|i| 100.5 + i as f64).collect());
+
+    let json = Baseline::from_json("main", r#"{ "benchmarks": {} }"#).unwrap().to_json();
+    assert_eq!(json, "{ \"benchmarks\": {\n} }\n");
+    let json = format!(
+        r#"{{ "benchmarks": {{ "b\"1": {{ "median": 124.5, "mb_s": 0, "samples": [{}] }} }} }}"#,
+        old.samples.iter().map(f64::to_string).collect::<Vec<_>>().join(", "),
+    );
+    let baseline = Baseline::from_json("main", &json).unwrap();
+    assert_eq!(Baseline::from_json("main", &baseline.to_json()), Some(baseline));
+    let baseline = Baseline::from_json("main", &json).unwrap();
+
+    let verdict = |bs| baseline.compare("b\"1", bs, 2.0).unwrap().verdict;
+    assert_eq!(verdict(&slower), BaselineVerdict::Regressed);
+    assert_eq!(verdict(&faster), BaselineVerdict::Improved);
+    assert_eq!(verdict(&same), BaselineVerdict::Unchanged);
+    let cmp = baseline.compare("b\"1", &slower, 50.0).unwrap();
+    assert_eq!((cmp.median, cmp.change_pct), (124.5, 30.0 / 124.5 * 100.0));
+    assert_eq!(cmp.verdict, BaselineVerdict::Unchanged);
+    assert_eq!(baseline.compare("other", &slower, 2.0), None);
+}
+
 #[test]
 #[cfg(not(target_os = "emscripten"))]
 fn test_dyn_bench_returning_err_fails_when_run_as_test() {
@@ -924,6 +1261,8 @@ fn f(_: &mut Bencher) -> Result<(), String> {
             should_panic: ShouldPanic::No,
             compile_fail: false,
             no_run: false,
+            #[cfg(not(bootstrap))]
+            timeout: None,
             test_type: TestType::Unknown,
         },
         testfn: DynBenchFn(Box::new(f)),
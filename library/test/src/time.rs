@@ -61,6 +61,58 @@ pub fn get_default_test_timeout() -> Instant {
     Instant::now() + Duration::from_secs(TEST_WARN_TIMEOUT_S)
 }
 
+/// Parses the hard timeout of a test in milliseconds, given as a positive number followed by one of
+/// the units `ms`, `s`, `m` or `h`. This is the syntax of both `--test-timeout` and the
+/// `#[timeout]` attribute, which is parsed when evaluating the constant of its test.
+pub const fn parse_timeout_ms(s: &str) -> Option<u64> {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    let mut n: u64 = 0;
+    while i < bytes.len() && bytes[i].is_ascii_digit() {
+        n = match n.checked_mul(10) {
+            Some(n) => match n.checked_add((bytes[i] - b'0') as u64) {
+                Some(n) => n,
+                None => return None,
+            },
+            None => return None,
+        };
+        i += 1;
+    }
+    if i == 0 {
+        return None;
+    }
+    let factor = match bytes.split_at(i).1 {
+        b"ms" => 1,
+        b"s" => 1000,
+        b"m" => 60 * 1000,
+        b"h" => 60 * 60 * 1000,
+        _ => return None,
+    };
+    match n.checked_mul(factor) {
+        Some(0) | None => None,
+        ms => ms,
+    }
+}
+
+/// Parses the value of `--test-timeout`, see [`parse_timeout_ms`].
+pub fn parse_test_timeout(s: &str) -> Option<Duration> {
+    parse_timeout_ms(s).map(Duration::from_millis)
+}
+
+/// Checks the `#[timeout]` attribute of a test in a constant generated by `#[test]`, so that an
+/// invalid timeout fails the compilation at the attribute.
+#[doc(hidden)]
+#[track_caller]
+pub const fn timeout_attr_ms(s: &str) -> u64 {
+    match parse_timeout_ms(s) {
+        Some(ms) => ms,
+        None => panic!(
+            "invalid test timeout, expected a positive number followed by `ms`, `s`, `m` or `h`, \
+             such as `30s`"
+        ),
+    }
+}
+
 /// The measured execution time of a unit test.
 #[derive(Debug, Clone, PartialEq)]
 pub struct TestExecTime(pub Duration);
@@ -3,6 +3,7 @@
 use std::borrow::Cow;
 use std::fmt;
 use std::sync::mpsc::Sender;
+use std::time::Duration;
 
 use super::__rust_begin_short_backtrace;
 use super::bench::Bencher;
@@ -207,6 +208,10 @@ pub struct TestDesc {
     pub should_panic: options::ShouldPanic,
     pub compile_fail: bool,
     pub no_run: bool,
+    /// The hard timeout of the test from its `#[timeout]` attribute, which the compiler has
+    /// checked to be valid.
+    #[cfg(not(bootstrap))]
+    pub timeout: Option<&'static str>,
     pub test_type: TestType,
 }
 
@@ -224,6 +229,16 @@ pub fn padded_name(&self, column_count: usize, align: NamePadding) -> String {
         }
     }
 
+    /// Returns the hard timeout of the test, after which it's killed, either from its
+    /// `#[timeout]` attribute or the `--test-timeout` option.
+    pub fn hard_timeout(&self, default: Option<Duration>) -> Option<Duration> {
+        #[cfg(not(bootstrap))]
+        if let Some(timeout) = self.timeout.and_then(super::time::parse_test_timeout) {
+            return Some(timeout);
+        }
+        default
+    }
+
     /// Returns None for ignored test or tests that are just run, otherwise returns a description of the type of test.
     /// Descriptions include "should panic", "compile fail" and "compile".
     pub fn test_mode(&self) -> Option<&'static str> {
@@ -550,6 +550,7 @@ fn run(self, builder: &Builder<'_>) {
             // lldb debugger scripts
             builder.install(&builder.src.join("src/etc/rust-lldb"), &sysroot.join("bin"), 0o755);
 
+            cp_debugger_script("lldb_embedded_formatters.py");
             cp_debugger_script("lldb_lookup.py");
             cp_debugger_script("lldb_providers.py");
             cp_debugger_script("lldb_commands")
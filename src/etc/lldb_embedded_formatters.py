@@ -0,0 +1,125 @@
+import os
+import re
+import tempfile
+
+import lldb
+
+####################################################################################################
+# Loads the LLDB formatters that crates embed with `#[debugger_visualizer(lldb_python_file = "...")]`
+# and `#[debugger_visualizer(lldb_type_summary_file = "...")]`.
+#
+# rustc collects them into the `.lldbformatters` section (`__TEXT,__lldbformatters` on Mach-O) of
+# the final binary. The section is a sequence of entries, each of which is made of:
+#   - a kind byte: 1 for a Python script, 2 for a list of LLDB commands,
+#   - the name of the formatter, terminated by a newline,
+#   - the source of the formatter, terminated by a NUL byte.
+#
+# Python scripts are imported like `command script import` does, so they can register their
+# formatters in `__lldb_init_module` and refer to their own functions through `__name__`.
+#
+# Importing this module only registers the `rust-load-embedded-formatters` command. Since the
+# formatters run arbitrary code from the binary, they are only loaded when that command is run,
+# which `rust-lldb` only does if RUST_LLDB_LOAD_EMBEDDED_FORMATTERS=1 is set.
+####################################################################################################
+
+SECTION_NAMES = (".lldbformatters", "__lldbformatters")
+
+KIND_PYTHON = 1
+KIND_COMMANDS = 2
+
+_loaded_modules = set()
+_script_dir = None
+
+
+def _find_section(sections):
+    # On Mach-O the section is nested in the `__TEXT` segment.
+    for section in sections:
+        if section.GetName() in SECTION_NAMES:
+            return section
+        subsections = [section.GetSubSectionAtIndex(i) for i in range(section.GetNumSubSections())]
+        found = _find_section(subsections)
+        if found is not None:
+            return found
+    return None
+
+
+def _read_section(section):
+    data = section.GetSectionData()
+    error = lldb.SBError()
+    contents = data.ReadRawData(error, 0, data.GetByteSize())
+    if error.Fail():
+        return None
+    return contents
+
+
+def _parse_entries(contents):
+    """Yields the `(kind, name, source)` entries of an `.lldbformatters` section"""
+    offset = 0
+    while offset < len(contents):
+        kind = contents[offset]
+        name_end = contents.find(b"\n", offset + 1)
+        source_end = contents.find(b"\0", name_end + 1) if name_end != -1 else -1
+        if source_end == -1:
+            # The section is truncated or malformed, the last entry is skipped.
+            return
+        name = contents[offset + 1:name_end].decode("utf-8")
+        source = contents[name_end + 1:source_end].decode("utf-8")
+        yield kind, name, source
+        offset = source_end + 1
+
+
+def _import_script(debugger, name, source):
+    global _script_dir
+    if _script_dir is None:
+        _script_dir = tempfile.mkdtemp(prefix="rust-lldb-formatters-")
+
+    # LLDB imports the script as a Python module named after the file.
+    module_name = re.sub(r"\W", "_", name)
+    path = os.path.join(_script_dir, module_name + ".py")
+    with open(path, "w") as f:
+        f.write(source)
+    debugger.HandleCommand("command script import \"%s\"" % path)
+
+
+def _run_commands(debugger, source):
+    for line in source.splitlines():
+        line = line.strip()
+        if line and not line.startswith("#"):
+            debugger.HandleCommand(line)
+
+
+def load_embedded_formatters(debugger, target):
+    """Loads the formatters embedded into the modules of `target` that haven't been loaded yet"""
+    for module in target.module_iter():
+        key = (module.GetFileSpec().fullpath, module.GetUUIDString())
+        if key in _loaded_modules:
+            continue
+        _loaded_modules.add(key)
+
+        section = _find_section(module.section_iter())
+        if section is None:
+            continue
+        contents = _read_section(section)
+        if contents is None:
+            continue
+
+        for kind, name, source in _parse_entries(contents):
+            if kind == KIND_PYTHON:
+                _import_script(debugger, name, source)
+            elif kind == KIND_COMMANDS:
+                _run_commands(debugger, source)
+
+
+def load_embedded_formatters_command(debugger, command, result, internal_dict):
+    """Loads the LLDB formatters embedded into the binaries of the current target by
+    `#[debugger_visualizer]`."""
+    target = debugger.GetSelectedTarget()
+    # There is nothing to load if LLDB was started without a file.
+    if target.IsValid():
+        load_embedded_formatters(debugger, target)
+
+
+def __lldb_init_module(debugger, internal_dict):
+    debugger.HandleCommand(
+        "command script add -f %s.load_embedded_formatters_command rust-load-embedded-formatters"
+        % __name__)
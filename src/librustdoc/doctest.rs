@@ -1057,6 +1057,8 @@ fn add_test(&mut self, test: String, config: LangString, line: usize) {
                 should_panic: test::ShouldPanic::No,
                 compile_fail: config.compile_fail,
                 no_run,
+                #[cfg(not(bootstrap))]
+                timeout: None,
                 test_type: test::TestType::DocTest,
             },
             testfn: test::DynTestFn(Box::new(move || {
@@ -995,6 +995,8 @@ macro_rules! decision {
         should_panic,
         compile_fail: false,
         no_run: false,
+        #[cfg(not(bootstrap))]
+        timeout: None,
         test_type: test::TestType::Unknown,
     }
 }
@@ -109,6 +109,11 @@ pub(super) fn handle_needs(
             condition: config.debugger != Some(Debugger::Lldb) || config.lldb_native_rust,
             ignore_reason: "ignored on targets without Rust's LLDB",
         },
+        Need {
+            name: "needs-cranelift-backend",
+            condition: cache.cranelift_backend,
+            ignore_reason: "ignored when the Cranelift codegen backend is not built",
+        },
         Need {
             name: "needs-i686-dlltool",
             condition: cache.i686_dlltool,
@@ -202,6 +207,7 @@ pub(super) struct CachedNeedsConditions {
     profiler_support: bool,
     xray: bool,
     rust_lld: bool,
+    cranelift_backend: bool,
     i686_dlltool: bool,
     x86_64_dlltool: bool,
     dlltool: bool,
@@ -261,6 +267,25 @@ pub(super) fn load(config: &Config) -> Self {
                 .join(if config.host.contains("windows") { "rust-lld.exe" } else { "rust-lld" })
                 .exists(),
 
+            // `-Zcodegen-backend=cranelift` loads the backend from the `codegen-backends`
+            // directory of the sysroot, next to `rust-lld`, but for the host.
+            cranelift_backend: config
+                .compile_lib_path
+                .parent()
+                .expect("couldn't traverse to the parent of the specified --compile-lib-path")
+                .join("lib")
+                .join("rustlib")
+                .join(&config.host)
+                .join("codegen-backends")
+                .read_dir()
+                .map_or(false, |mut entries| {
+                    entries.any(|entry| {
+                        entry.map_or(false, |entry| {
+                            entry.file_name().to_string_lossy().contains("rustc_codegen_cranelift")
+                        })
+                    })
+                }),
+
             #[cfg(windows)]
             i686_dlltool: dlltool,
             #[cfg(windows)]
@@ -1499,6 +1499,14 @@ fn run_debuginfo_lldb_test_no_opt(&self) {
         }
         script_str.push_str("type category enable Rust\n");
 
+        // Make the formatters embedded by `#[debugger_visualizer]` loadable through the
+        // `rust-load-embedded-formatters` command, like the `rust-lldb` wrapper does.
+        let embedded_formatters_path = rust_src_root.join("src/etc/lldb_embedded_formatters.py");
+        script_str.push_str(&format!(
+            "command script import {}\n",
+            embedded_formatters_path.to_str().unwrap()
+        ));
+
         // Set breakpoints on every line that contains the string "#break"
         let source_file_name = self.testpaths.file.file_name().unwrap().to_string_lossy();
         for line in &dbg_cmds.breakpoint_lines {
@@ -256,6 +256,58 @@ fn assert_panic(
                     StackPopCleanup::Goto { ret: None, unwind },
                 )?;
             }
+            NullPointerDereference => {
+                // Forward to `panic_null_pointer_dereference` lang item.
+                let panic_null_pointer_dereference =
+                    this.tcx.lang_items().panic_null_pointer_dereference_fn().unwrap();
+                let panic_null_pointer_dereference =
+                    ty::Instance::mono(this.tcx.tcx, panic_null_pointer_dereference);
+                this.call_function(
+                    panic_null_pointer_dereference,
+                    Abi::Rust,
+                    &[],
+                    None,
+                    StackPopCleanup::Goto { ret: None, unwind },
+                )?;
+            }
+            InvalidValue { value } => {
+                // Forward to `panic_invalid_value` lang item.
+
+                // First arg: value.
+                let value = this.read_scalar(&this.eval_operand(value, None)?)?;
+
+                // Call the lang item.
+                let panic_invalid_value = this.tcx.lang_items().panic_invalid_value_fn().unwrap();
+                let panic_invalid_value = ty::Instance::mono(this.tcx.tcx, panic_invalid_value);
+                this.call_function(
+                    panic_invalid_value,
+                    Abi::Rust,
+                    &[value.into()],
+                    None,
+                    StackPopCleanup::Goto { ret: None, unwind },
+                )?;
+            }
+            SliceFromRawPartsOverflow { len, size } => {
+                // Forward to `panic_slice_from_raw_parts_overflow` lang item.
+
+                // First arg: len.
+                let len = this.read_scalar(&this.eval_operand(len, None)?)?;
+                // Second arg: size.
+                let size = this.read_scalar(&this.eval_operand(size, None)?)?;
+
+                // Call the lang item.
+                let panic_slice_from_raw_parts_overflow =
+                    this.tcx.lang_items().panic_slice_from_raw_parts_overflow_fn().unwrap();
+                let panic_slice_from_raw_parts_overflow =
+                    ty::Instance::mono(this.tcx.tcx, panic_slice_from_raw_parts_overflow);
+                this.call_function(
+                    panic_slice_from_raw_parts_overflow,
+                    Abi::Rust,
+                    &[len.into(), size.into()],
+                    None,
+                    StackPopCleanup::Goto { ret: None, unwind },
+                )?;
+            }
 
             _ => {
                 // Forward everything else to `panic` lang item.
@@ -0,0 +1,18 @@
+// compile-flags:-g
+// ignore-gdb
+// no-prefer-dynamic
+
+#![feature(lldb_debugger_visualizer)]
+#![debugger_visualizer(lldb_type_summary_file = "dependency-with-embedded-lldb-formatters.lldb")]
+#![crate_type = "rlib"]
+
+pub struct Person {
+    name: String,
+    age: i32,
+}
+
+impl Person {
+    pub fn new(name: String, age: i32) -> Person {
+        Person { name: name, age: age }
+    }
+}
@@ -0,0 +1,70 @@
+// Check the variable debuginfo of the Cranelift backend, for locals and arguments that live in
+// registers, including fat pointers which are described piece by piece, and on the stack.
+
+// needs-cranelift-backend
+// ignore-lldb
+
+// compile-flags:-g -Zcodegen-backend=cranelift
+
+// === GDB TESTS ===================================================================================
+
+// gdb-command:run
+
+// gdb-command:print a
+// gdb-check:$1 = 1
+// gdb-command:print b
+// gdb-check:$2 = -2.5
+// gdb-command:print c
+// gdb-check:$3 = true
+// gdb-command:print s.length
+// gdb-check:$4 = 5
+// gdb-command:print/d *s.data_ptr
+// gdb-check:$5 = 104
+// gdb-command:print slice.length
+// gdb-check:$6 = 3
+// gdb-command:print slice.data_ptr[2]
+// gdb-check:$7 = 30
+// gdb-command:print point.y
+// gdb-check:$8 = 4
+// gdb-command:continue
+
+// gdb-command:print x
+// gdb-check:$9 = 42
+// gdb-command:print name.length
+// gdb-check:$10 = 3
+// gdb-command:print/d name.data_ptr[1]
+// gdb-check:$11 = 98
+// gdb-command:continue
+
+#![allow(unused_variables)]
+
+use std::hint::black_box;
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn main() {
+    let a = 1u32;
+    let b = -2.5f64;
+    let c = true;
+    let s: &str = black_box("hello");
+    let slice: &[i32] = black_box(&[10, 20, 30]);
+    let point = Point { x: 3, y: 4 };
+    let point_ref = &point;
+
+    zzz(); // #break
+
+    // Keep everything alive across the breakpoint.
+    black_box((a, b, c, s, slice, point_ref));
+    fun(black_box(42), black_box("abc"));
+}
+
+fn fun(x: u64, name: &str) {
+    zzz(); // #break
+    black_box((x, name));
+}
+
+#[inline(never)]
+fn zzz() {}
@@ -0,0 +1,10 @@
+def point_summary(valobj, internal_dict):
+    x = valobj.GetChildMemberWithName("x").GetValueAsSigned()
+    y = valobj.GetChildMemberWithName("y").GetValueAsSigned()
+    return "({}, {})".format(x, y)
+
+
+def __lldb_init_module(debugger, internal_dict):
+    debugger.HandleCommand(
+        "type summary add -F {}.point_summary embedded_lldb_formatters::point::Point"
+        .format(__name__))
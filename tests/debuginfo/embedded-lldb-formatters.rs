@@ -0,0 +1,68 @@
+// compile-flags:-g
+// ignore-gdb
+// ignore-windows // LLDB formatters are only embedded into ELF and Mach-O binaries
+
+// === LLDB TESTS ==================================================================================
+
+// lldb-command:rust-load-embedded-formatters
+// lldb-command:run
+
+// lldb-command:print point_a
+// lldb-check:[...](0, 0)[...]
+// lldb-command:print point_b
+// lldb-check:[...](5, 8)[...]
+// lldb-command:print line
+// lldb-check:[...]line from (0, 0) to (5, 8)[...]
+// lldb-command:print person
+// lldb-check:[...]"Person A" is 10 years old.[...]
+
+#![allow(unused_variables)]
+#![feature(lldb_debugger_visualizer)]
+#![debugger_visualizer(lldb_type_summary_file = "embedded-lldb-formatters.lldb")]
+
+// aux-build: dependency-with-embedded-lldb-formatters.rs
+extern crate dependency_with_embedded_lldb_formatters;
+
+use dependency_with_embedded_lldb_formatters::Person;
+
+#[debugger_visualizer(lldb_python_file = "embedded-lldb-formatters-point.py")]
+mod point {
+    pub struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    impl Point {
+        pub fn new(x: i32, y: i32) -> Point {
+            Point { x: x, y: y }
+        }
+    }
+}
+
+use point::Point;
+
+pub struct Line {
+    a: Point,
+    b: Point,
+}
+
+impl Line {
+    pub fn new(a: Point, b: Point) -> Line {
+        Line { a: a, b: b }
+    }
+}
+
+fn main() {
+    let point_a = Point::new(0, 0);
+    let point_b = Point::new(5, 8);
+    let line = Line::new(point_a, point_b);
+
+    let name = String::from("Person A");
+    let person = Person::new(name, 10);
+
+    zzz(); // #break
+}
+
+fn zzz() {
+    ()
+}
@@ -0,0 +1,11 @@
+#![crate_type = "lib"]
+
+#[inline(never)]
+pub fn checksum(data: &[u8]) -> u32 {
+    data.iter().map(|&b| b as u32).fold(0, |acc, b| acc.rotate_left(5) ^ b)
+}
+
+#[inline(never)]
+pub fn unrelated(x: u64) -> u64 {
+    x.wrapping_mul(0x9E37_79B9_7F4A_7C15)
+}
@@ -0,0 +1,5 @@
+#![crate_type = "rlib"]
+
+pub fn moved_twice(v: Vec<u8>) -> (Vec<u8>, Vec<u8>) {
+    (v, v)
+}
@@ -0,0 +1,16 @@
+#![crate_type = "rlib"]
+
+pub struct Foo {
+    pub field: i32,
+}
+
+#[inline]
+pub fn inlined(x: i32) -> i32 {
+    x * 2
+}
+
+pub fn generic<T: Clone + Default>(x: &[T]) -> Vec<T> {
+    let mut v: Vec<T> = x.iter().cloned().collect();
+    v.push(T::default());
+    v
+}
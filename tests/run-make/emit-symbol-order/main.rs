@@ -0,0 +1,30 @@
+use std::hint::black_box;
+
+#[no_mangle]
+#[inline(never)]
+pub fn cold(x: u32) -> u32 {
+    x.wrapping_sub(11)
+}
+
+#[no_mangle]
+#[inline(never)]
+pub fn warm(x: u32) -> u32 {
+    x.wrapping_add(7)
+}
+
+#[no_mangle]
+#[inline(never)]
+pub fn hot(x: u32) -> u32 {
+    x.wrapping_mul(3)
+}
+
+fn main() {
+    let mut x = warm(black_box(1));
+    for _ in 0..1000 {
+        x = hot(black_box(x));
+    }
+    if black_box(x) == 0 {
+        x = cold(x);
+    }
+    println!("{x}");
+}
@@ -0,0 +1,17 @@
+#![crate_type = "cdylib"]
+
+#[no_mangle]
+pub extern "C" fn foo_v1() -> u32 {
+    1
+}
+
+#[no_mangle]
+pub extern "C" fn foo_v2() -> u32 {
+    2
+}
+
+#[no_mangle]
+pub extern "C" fn foo_hook() {}
+
+#[no_mangle]
+pub extern "C" fn foo_internal() {}
@@ -0,0 +1,27 @@
+mod a {
+    pub fn name() -> &'static str {
+        "a"
+    }
+}
+
+mod b {
+    pub fn name() -> String {
+        [super::a::name()].iter().map(|_| "b").collect()
+    }
+}
+
+mod c {
+    #[cfg(not(changed))]
+    pub fn name() -> String {
+        format!("{}", 'c')
+    }
+
+    #[cfg(changed)]
+    pub fn name() -> String {
+        format!("{} changed", 'c')
+    }
+}
+
+fn main() {
+    println!("{} {} {}", a::name(), b::name(), c::name());
+}
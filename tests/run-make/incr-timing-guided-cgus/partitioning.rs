@@ -0,0 +1,48 @@
+// `big` is cheap by size estimate but expensive for the backend, because of the large static.
+// `many` is the other way around. With two codegen units, size-based partitioning keeps `many`
+// alone and merges `big` with the others, timing-guided partitioning should do the opposite
+// given timings that reflect this.
+
+use std::hint::black_box;
+
+macro_rules! repeat4 {
+    ($($t:tt)*) => { $($t)* $($t)* $($t)* $($t)* };
+}
+
+mod big {
+    pub static BIG: [u8; 32 << 20] = [1; 32 << 20];
+}
+
+mod many {
+    use std::hint::black_box;
+
+    #[inline(never)]
+    pub fn work() {
+        repeat4!(repeat4!(repeat4!(repeat4!(black_box(1);))));
+    }
+}
+
+mod b {
+    use std::hint::black_box;
+
+    #[inline(never)]
+    pub fn work() {
+        repeat4!(repeat4!(black_box(2);));
+    }
+}
+
+mod c {
+    use std::hint::black_box;
+
+    #[inline(never)]
+    pub fn work() {
+        repeat4!(repeat4!(black_box(3);));
+    }
+}
+
+fn main() {
+    black_box(&big::BIG);
+    many::work();
+    b::work();
+    c::work();
+}
@@ -0,0 +1,6 @@
+extern crate mir_summaries_lib;
+
+fn main() {
+    let x = mir_summaries_lib::small_callee(std::env::args().count() as u32);
+    println!("{}", mir_summaries_lib::large_callee(x));
+}
@@ -0,0 +1,21 @@
+#[inline(never)]
+pub fn opaque(x: u32) -> u32 {
+    x.rotate_left(3)
+}
+
+#[inline]
+pub fn small_callee(x: u32) -> u32 {
+    x + 1
+}
+
+// Each call costs more than the inlining threshold allows for a handful of them.
+#[inline]
+pub fn large_callee(x: u32) -> u32 {
+    let mut y = opaque(x);
+    y = opaque(y) ^ opaque(y + 1);
+    y = opaque(y) ^ opaque(y + 2);
+    y = opaque(y) ^ opaque(y + 3);
+    y = opaque(y) ^ opaque(y + 4);
+    y = opaque(y) ^ opaque(y + 5);
+    y
+}
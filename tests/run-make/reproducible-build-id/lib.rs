@@ -0,0 +1,10 @@
+#![crate_type = "rlib"]
+
+pub fn answer() -> u32 {
+    42
+}
+
+#[cfg(changed)]
+pub fn question() -> u32 {
+    6 * 9
+}
@@ -0,0 +1,6 @@
+#![crate_type = "staticlib"]
+
+#[no_mangle]
+pub extern "C" fn answer() -> u32 {
+    lib::answer()
+}
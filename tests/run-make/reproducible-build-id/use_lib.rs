@@ -0,0 +1,3 @@
+fn main() {
+    assert_eq!(lib::answer(), 42);
+}
@@ -0,0 +1,14 @@
+#![crate_type = "rlib"]
+
+pub struct Wrapper<T>(pub T);
+
+#[inline(never)]
+pub fn sum<T: Copy + Into<u64>>(items: &[T]) -> u64 {
+    items.iter().map(|&item| item.into()).sum()
+}
+
+pub fn sums() -> u64 {
+    sum(&[1u8, 2]) + sum(&[3u16, 4]) + sum(&[5u32, 6])
+}
+
+pub static TABLE: [u64; 4] = [1, 2, 3, 4];
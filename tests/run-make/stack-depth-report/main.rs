@@ -0,0 +1,28 @@
+use std::hint::black_box;
+
+static CALLBACK: &(dyn Fn() -> u32 + Sync) = &|| 1;
+
+#[no_mangle]
+pub extern "C" fn exported_recursive(n: u32) -> u32 {
+    if black_box(n) == 0 { 0 } else { exported_recursive(n - 1) + 1 }
+}
+
+#[no_mangle]
+pub extern "C" fn exported_dyn() -> u32 {
+    CALLBACK()
+}
+
+#[no_mangle]
+pub extern "C" fn exported_fn_ptr(f: extern "C" fn() -> u32) -> u32 {
+    f()
+}
+
+#[inline(never)]
+fn leaf() -> u8 {
+    let buf = black_box([1u8; 256]);
+    buf.iter().fold(0, |acc, x| acc ^ x)
+}
+
+fn main() {
+    black_box(leaf());
+}
@@ -0,0 +1,18 @@
+// compile-flags: -O -Zencode-mir-summaries
+
+#[inline]
+pub fn small<T: Copy>(x: T) -> (T, T) {
+    (x, x)
+}
+
+pub fn large<T: Clone + std::fmt::Debug>(v: &[T]) -> Vec<String> {
+    let mut out = Vec::new();
+    for x in v {
+        let y = x.clone();
+        out.push(format!("{:?}", y));
+        if out.len() > 10 {
+            out.clear();
+        }
+    }
+    out
+}
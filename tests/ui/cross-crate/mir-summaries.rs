@@ -0,0 +1,12 @@
+// run-pass
+// aux-build:mir_summaries_lib.rs
+// compile-flags: -O -Zinline-mir
+// Check that crates built with `-Zencode-mir-summaries` can be used by downstream crates, whose
+// MIR inliner and CGU partitioning then consult the summaries.
+
+extern crate mir_summaries_lib;
+
+fn main() {
+    assert_eq!(mir_summaries_lib::small(3u8), (3, 3));
+    assert_eq!(mir_summaries_lib::large(&[1, 2]), vec!["1".to_string(), "2".to_string()]);
+}
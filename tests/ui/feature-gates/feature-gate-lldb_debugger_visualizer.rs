@@ -0,0 +1,6 @@
+#![debugger_visualizer(lldb_python_file = "formatters.py")]
+//~^ ERROR embedding LLDB formatters is experimental
+#![debugger_visualizer(lldb_type_summary_file = "formatters.lldb")]
+//~^ ERROR embedding LLDB formatters is experimental
+
+fn main() {}
@@ -0,0 +1,5 @@
+// compile-flags: --test
+
+#[test]
+#[case(1)] //~ ERROR the `#[case]` attribute is an experimental feature
+fn f(_: u32) {}
@@ -0,0 +1,5 @@
+// compile-flags: --test
+
+#[test]
+#[timeout = "10s"] //~ ERROR the `#[timeout]` attribute is an experimental feature
+fn f() {}
@@ -321,8 +321,8 @@ mod hash_map {
         //~| ERROR struct takes at least 2
         //~| HELP add missing
 
-        type D = HashMap<usize, String, char, f64>;
-        //~^ ERROR struct takes at most 3
+        type D = HashMap<usize, String, char, f64, u8>;
+        //~^ ERROR struct takes at most 4
         //~| HELP remove this
 
         type E = HashMap<>;
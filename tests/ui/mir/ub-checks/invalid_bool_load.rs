@@ -0,0 +1,11 @@
+// run-fail
+// ignore-wasm32-bare: No panic messages
+// compile-flags: -Z ub-checks=loads
+// error-pattern: invalid value 0x2 read through a pointer or transmuted into a type that does not allow it
+
+fn main() {
+    let byte = 2u8;
+    let ptr = &byte as *const u8 as *const bool;
+    let b = unsafe { *ptr };
+    println!("{b}");
+}
@@ -0,0 +1,10 @@
+// run-fail
+// ignore-wasm32-bare: No panic messages
+// compile-flags: -Z ub-checks=transmute
+// error-pattern: invalid value 0xd800 read through a pointer or transmuted into a type that does not allow it
+
+fn main() {
+    let surrogate = std::hint::black_box(0xD800u32);
+    let c: char = unsafe { std::mem::transmute(surrogate) };
+    println!("{c}");
+}
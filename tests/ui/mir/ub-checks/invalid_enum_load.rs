@@ -0,0 +1,22 @@
+// run-fail
+// ignore-wasm32-bare: No panic messages
+// compile-flags: -Z ub-checks=loads
+// error-pattern: invalid value 0x3 read through a pointer or transmuted into a type that does not allow it
+
+#[derive(Clone, Copy)]
+#[repr(u8)]
+enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+fn main() {
+    let byte = 3u8;
+    let ptr = &byte as *const u8 as *const Color;
+    match unsafe { *ptr } {
+        Color::Red => println!("red"),
+        Color::Green => println!("green"),
+        Color::Blue => println!("blue"),
+    }
+}
@@ -0,0 +1,11 @@
+// run-fail
+// ignore-wasm32-bare: No panic messages
+// compile-flags: -Z ub-checks=null
+// error-pattern: null pointer dereference occurred
+
+fn main() {
+    let ptr: *mut u32 = std::ptr::null_mut();
+    unsafe {
+        *ptr = 42;
+    }
+}
@@ -0,0 +1,9 @@
+// run-pass
+// compile-flags: -Z ub-checks=null
+
+// Accessing a zero-sized place never touches memory, so it isn't checked.
+
+fn main() {
+    let ptr: *const () = std::ptr::null();
+    let _: () = unsafe { *ptr };
+}
@@ -0,0 +1,19 @@
+// run-pass
+// compile-flags: -Z ub-checks
+
+// Loads from the fields of packed structs aren't checked, as the check would itself read the
+// field with an aligned load.
+
+#[repr(C, packed)]
+struct Packed {
+    tag: u8,
+    c: char,
+    b: bool,
+}
+
+fn main() {
+    let packed = Packed { tag: 1, c: 'x', b: true };
+    let ptr = std::ptr::addr_of!(packed);
+    let (tag, c, b) = unsafe { ((*ptr).tag, (*ptr).c, (*ptr).b) };
+    assert_eq!((tag, c, b), (1, 'x', true));
+}
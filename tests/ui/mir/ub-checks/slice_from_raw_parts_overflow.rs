@@ -0,0 +1,13 @@
+// run-fail
+// ignore-wasm32-bare: No panic messages
+// compile-flags: -Z ub-checks=from-raw-parts
+// error-pattern: slice::from_raw_parts called with 4611686018427387904 elements of 4 bytes
+
+// only-64bit
+
+fn main() {
+    let x = [0u32; 4];
+    let len = std::hint::black_box(1usize << 62);
+    let s = unsafe { std::slice::from_raw_parts(x.as_ptr(), len) };
+    println!("{}", s[0]);
+}
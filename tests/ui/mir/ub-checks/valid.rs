@@ -0,0 +1,29 @@
+// run-pass
+// compile-flags: -Z ub-checks
+
+// Sound code doesn't trip any of the checks.
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+fn main() {
+    let colors = [Color::Red, Color::Green, Color::Blue];
+    let ptr = colors.as_ptr();
+    assert_eq!(unsafe { *ptr.add(2) }, Color::Blue);
+
+    let flags = [true, false];
+    let flags = unsafe { std::slice::from_raw_parts(flags.as_ptr(), flags.len()) };
+    assert_eq!(flags, [true, false]);
+
+    let c: char = unsafe { std::mem::transmute(0x1F980u32) };
+    assert_eq!(c, '🦀');
+
+    let mut x = 0u32;
+    let ptr = &mut x as *mut u32;
+    unsafe { *ptr = 42 };
+    assert_eq!(x, 42);
+}
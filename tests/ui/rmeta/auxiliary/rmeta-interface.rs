@@ -0,0 +1,16 @@
+// no-prefer-dynamic
+// compile-flags: --emit=metadata-interface -Zunstable-options
+
+#![crate_type="rlib"]
+
+pub struct Foo {
+    pub field: i32,
+}
+
+pub const fn len() -> usize {
+    4
+}
+
+pub fn generic<T: Clone>(x: &T) -> T {
+    x.clone()
+}
@@ -0,0 +1,13 @@
+// aux-build:rmeta-interface.rs
+// no-prefer-dynamic
+// build-fail
+
+// Check that code generation refuses to use a crate that was only emitted as a metadata
+// interface.
+
+extern crate rmeta_interface;
+use rmeta_interface::Foo;
+
+fn main() {
+    let _ = Foo { field: 42 };
+}
@@ -0,0 +1,16 @@
+// compile-flags: --emit=metadata
+// aux-build:rmeta-interface.rs
+// no-prefer-dynamic
+// build-pass
+
+// Check that a metadata interface can be used to type check a dependent crate,
+// including evaluating its constants.
+
+extern crate rmeta_interface;
+use rmeta_interface::{generic, len, Foo};
+
+pub fn main() {
+    let _ = Foo { field: 42 };
+    let _: [u8; len()] = [0; 4];
+    let _: i32 = generic(&1);
+}
@@ -0,0 +1,27 @@
+// compile-flags: --test
+
+#![feature(parameterized_tests)]
+#![feature(test)]
+
+extern crate test;
+
+#[test]
+#[case(1)]
+#[case(1, 2)] //~ ERROR this case passes 2 arguments to a test that takes 1
+#[case()] //~ ERROR this case passes 0 arguments to a test that takes 1
+fn one_arg(_: u32) {}
+
+#[test]
+#[case = "1"] //~ ERROR malformed `case` attribute input
+fn malformed(_: u32) {}
+
+#[test]
+#[case(1; skip)] //~ ERROR malformed options of a `case`
+fn bad_options(_: u32) {}
+
+#[bench]
+#[case(1)] //~ ERROR `#[case]` can only be used on tests, not benchmarks
+fn bench(_: &mut test::Bencher, _: u32) {}
+
+#[case(1)] //~ ERROR cannot find attribute `case` in this scope
+fn not_a_test(_: u32) {}
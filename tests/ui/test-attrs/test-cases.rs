@@ -0,0 +1,72 @@
+// no-prefer-dynamic
+// compile-flags: --test
+// run-flags: --test-threads=1 --skip square::case_2
+// run-pass
+// check-run-results
+// normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+// ignore-emscripten no threads support
+
+// Tests that every case of a parameterized test is run, filtered and reported on its own.
+
+#![cfg(test)]
+#![feature(parameterized_tests)]
+
+#[test]
+#[case(1, 2, 3)]
+#[case(2, 2, 4)]
+#[case(-1, 1, 0)]
+fn add(a: i32, b: i32, sum: i32) {
+    assert_eq!(a + b, sum);
+}
+
+#[test]
+#[case("1")]
+#[case(concat!("4", "2"))]
+fn parses(s: &str) -> Result<(), std::num::ParseIntError> {
+    s.parse::<u32>().map(drop)
+}
+
+#[test]
+#[should_panic]
+#[case(0)]
+#[case(1 - 1)]
+fn divides_by_zero(d: u32) {
+    let _ = 1 / d;
+}
+
+#[test]
+#[ignore]
+#[case(1)]
+#[case(2)]
+fn ignored(_: u32) {}
+
+#[test]
+#[case(1)]
+#[case(2; ignore)]
+#[case(3; ignore = "not yet")]
+fn partly_ignored(n: u32) {
+    assert_eq!(n, 1);
+}
+
+// The numbers of the cases are zero-padded, so that `case_10` comes after `case_09`.
+#[test]
+#[case(1)]
+#[case(2)]
+#[case(3)]
+#[case(4)]
+#[case(5)]
+#[case(6)]
+#[case(7)]
+#[case(8)]
+#[case(9)]
+#[case(10)]
+fn many(n: u32) {
+    assert!(n <= 10);
+}
+
+#[test]
+#[case(4, 2)]
+#[case(9, 4)] // This case would fail, but it is filtered out.
+fn square(n: u32, root: u32) {
+    assert_eq!(root * root, n);
+}
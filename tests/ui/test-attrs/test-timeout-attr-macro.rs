@@ -0,0 +1,18 @@
+// check-pass
+// aux-build:timeout_attr.rs
+// compile-flags: --test
+
+// Checks that `#[timeout = "..."]` of tests doesn't take the name from attribute macros, which are
+// used for the other forms of `#[timeout]`.
+
+extern crate timeout_attr;
+
+use timeout_attr::timeout;
+
+#[timeout(100)]
+#[test]
+fn before_test() {}
+
+#[test]
+#[timeout(100)]
+fn after_test() {}
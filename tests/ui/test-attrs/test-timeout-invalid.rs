@@ -0,0 +1,11 @@
+// compile-flags: --test
+
+#![feature(test_timeout)]
+
+#[test]
+#[timeout = "10"] //~ ERROR evaluation of constant value failed
+fn no_unit() {}
+
+#[test]
+#[timeout = "0s"] //~ ERROR evaluation of constant value failed
+fn zero() {}
@@ -0,0 +1,16 @@
+// compile-flags: --test
+
+#![feature(test_timeout)]
+
+#[test]
+#[timeout = 10] //~ ERROR malformed `timeout` attribute input
+fn not_a_string() {}
+
+#[test]
+#[timeout = "1s"]
+#[timeout = "2s"] //~ ERROR multiple `timeout` attributes
+fn twice() {}
+
+#[timeout = "1s"] //~ ERROR cannot find attribute `timeout` in this scope
+#[test]
+fn before_test() {}
@@ -0,0 +1,28 @@
+// no-prefer-dynamic
+// compile-flags: --test -Cpanic=abort -Zpanic_abort_tests
+// run-flags: --test-threads=1
+// run-fail
+// check-run-results
+// exec-env:RUST_BACKTRACE=0
+// normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+
+// ignore-wasm no subprocess support
+// ignore-emscripten no subprocess support
+// ignore-sgx no subprocess support
+
+#![cfg(test)]
+#![feature(test_timeout)]
+
+use std::thread;
+
+// Never finishes, so it's only reported once it's killed.
+#[test]
+#[timeout = "10ms"]
+fn it_hangs() {
+    loop {
+        thread::park();
+    }
+}
+
+#[test]
+fn it_works() {}
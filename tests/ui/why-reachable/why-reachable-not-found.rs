@@ -0,0 +1,8 @@
+// build-pass
+// compile-flags: -Z why-reachable=why_reachable_not_found::unused
+
+#![allow(dead_code)]
+
+fn unused() {}
+
+fn main() {}
@@ -0,0 +1,21 @@
+// Checks that `-Z why-reachable` prints the shortest chain of uses leading to every instance of
+// the given function.
+
+// build-pass
+// compile-flags: -Z why-reachable=why_reachable::leaf
+
+fn leaf<T>() {}
+
+fn middle<T>() {
+    leaf::<T>();
+}
+
+#[no_mangle]
+pub fn exported() {
+    leaf::<u8>();
+}
+
+fn main() {
+    middle::<u32>();
+    exported();
+}